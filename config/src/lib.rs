@@ -22,6 +22,8 @@ pub struct Config {
     pub owner: OwnerConfig,
     #[serde(default)]
     pub debug_overrides: DebugOverrides,
+    #[serde(default)]
+    pub urls: UrlConfig,
 }
 
 #[derive(Deserialize)]
@@ -66,6 +68,18 @@ pub struct TwitchConfig {
 #[derive(Deserialize)]
 pub struct EventSubConfig {
     pub secret: String,
+    #[serde(default)]
+    pub transport: EventSubTransport,
+}
+
+#[derive(Deserialize, Default, Debug, Copy, Clone, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum EventSubTransport {
+    /// Twitch sends events to `{server.url}/api/v1/eventsub/reward`.
+    #[default]
+    Webhook,
+    /// The bot connects to Twitch - no public callback is required.
+    Websocket,
 }
 
 #[derive(Deserialize)]
@@ -99,12 +113,19 @@ pub struct SevenTvConfig {
 pub struct SpotifyConfig {
     pub client_id: String,
     pub client_secret: String,
+    /// Seconds between requests for the now-playing overlay
+    #[serde(default = "default_overlay_interval")]
+    pub overlay_interval: u64,
 }
 
 #[derive(Deserialize)]
+#[serde(rename_all = "kebab-case")]
 pub struct BotConfig {
     #[serde(default = "default_prefix")]
     pub prefix: String,
+    /// Directory with the translations of the chat messages
+    #[serde(default = "default_locales_dir")]
+    pub locales_dir: String,
 }
 
 #[derive(Deserialize, Default)]
@@ -118,6 +139,14 @@ fn default_prefix() -> String {
     "::".to_string()
 }
 
+fn default_locales_dir() -> String {
+    "locales".to_string()
+}
+
+fn default_overlay_interval() -> u64 {
+    5
+}
+
 #[derive(Deserialize, Default)]
 pub struct AnnounceConfig {
     pub discord: bool,
@@ -161,12 +190,85 @@ impl DebugOverrides {
     }
 }
 
+/// Base URLs of the external APIs (without a trailing slash).
+#[derive(Deserialize)]
+#[serde(rename_all = "kebab-case", default)]
+pub struct UrlConfig {
+    /// Overrides `TWITCH_HELIX_URL` used by `twitch_api`.
+    pub twitch_helix: Option<String>,
+    /// Overrides `TWITCH_OAUTH2_URL` used by `twitch_oauth2`.
+    pub twitch_oauth2: Option<String>,
+    pub twitch_eventsub_ws: String,
+    pub bttv: String,
+    pub bttv_cdn: String,
+    pub ffz_api: String,
+    pub ffz: String,
+    pub ffz_cdn: String,
+    pub seven_tv: String,
+    pub seven_tv_cdn: String,
+    pub spotify_api: String,
+    pub spotify_accounts: String,
+    pub ivr: String,
+    pub supinic: String,
+}
+
+impl UrlConfig {
+    /// Points `twitch_api` and `twitch_oauth2` to the configured URLs.
+    /// They read them from the environment and expect a trailing slash.
+    pub fn export_twitch_urls(&self) {
+        for (var, url) in [
+            ("TWITCH_HELIX_URL", &self.twitch_helix),
+            ("TWITCH_OAUTH2_URL", &self.twitch_oauth2),
+        ] {
+            if let Some(url) = url {
+                std::env::set_var(
+                    var,
+                    format!("{}/", url.trim_end_matches('/')),
+                );
+            }
+        }
+    }
+
+    /// Base URL of the Twitch OAuth2 endpoints, used for the authorize
+    /// redirect.
+    pub fn twitch_oauth2(&self) -> &str {
+        self.twitch_oauth2
+            .as_deref()
+            .map(|url| url.trim_end_matches('/'))
+            .unwrap_or("https://id.twitch.tv/oauth2")
+    }
+}
+
+impl Default for UrlConfig {
+    fn default() -> Self {
+        Self {
+            twitch_helix: None,
+            twitch_oauth2: None,
+            twitch_eventsub_ws: "wss://eventsub.wss.twitch.tv/ws".to_string(),
+            bttv: "https://api.betterttv.net/3".to_string(),
+            bttv_cdn: "https://cdn.betterttv.net".to_string(),
+            ffz_api: "https://api.frankerfacez.com/v1".to_string(),
+            ffz: "https://www.frankerfacez.com".to_string(),
+            ffz_cdn: "https://cdn.frankerfacez.com".to_string(),
+            seven_tv: "https://7tv.io".to_string(),
+            seven_tv_cdn: "https://cdn.7tv.app".to_string(),
+            spotify_api: "https://api.spotify.com/v1".to_string(),
+            spotify_accounts: "https://accounts.spotify.com".to_string(),
+            ivr: "https://api.ivr.fi/v2".to_string(),
+            supinic: "https://supinic.com/api".to_string(),
+        }
+    }
+}
+
 lazy_static! {
     pub static ref CONFIG: Config = read_config().unwrap();
 }
 
 /// This blocks!
 fn read_config() -> AnyResult<Config> {
-    let config = toml::from_str(&std::fs::read_to_string("config.toml")?)?;
+    // the tests point this to a generated config
+    let path = std::env::var("REWARDS_CONFIG")
+        .unwrap_or_else(|_| "config.toml".to_string());
+    let config = toml::from_str(&std::fs::read_to_string(path)?)?;
     Ok(config)
 }
@@ -0,0 +1 @@
+alter table users add revoked bool default false not null;
@@ -0,0 +1,2 @@
+alter type timed_mode add value if not exists 'slow';
+alter type timed_mode add value if not exists 'followers';
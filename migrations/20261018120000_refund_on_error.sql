@@ -0,0 +1,4 @@
+create type refund_policy as enum ('always', 'never', 'user_error_only');
+
+alter table rewards
+    add refund_on_error refund_policy default 'always' not null;
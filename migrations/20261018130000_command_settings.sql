@@ -0,0 +1,14 @@
+create table command_settings
+(
+    user_id  varchar(16) not null
+        constraint command_settings_pk
+            primary key
+        constraint command_settings_users_id_fk
+            references users
+            on delete cascade,
+    prefix   varchar(8),
+    commands jsonb default '{}' not null
+);
+
+comment on column command_settings.prefix is 'Overrides the global prefix if set';
+comment on column command_settings.commands is 'Overrides for the builtin commands keyed by their name';
@@ -0,0 +1,17 @@
+create table custom_commands
+(
+    id               serial
+        constraint custom_commands_pk
+            primary key,
+    user_id          varchar(16)  not null
+        constraint custom_commands_users_id_fk
+            references users
+            on delete cascade,
+    name             varchar(25)  not null,
+    response         varchar(500) not null,
+    user_cooldown    int default 5 not null,
+    channel_cooldown int default 1 not null
+);
+
+create unique index custom_commands_name_uindex
+    on custom_commands (user_id, name);
@@ -0,0 +1,7 @@
+alter table rewards
+    add reply_templates jsonb default '{}' not null;
+
+alter table users
+    add reply_templates jsonb default '{}' not null;
+
+comment on column users.reply_templates is 'Default reply templates for all rewards of the user';
@@ -0,0 +1,4 @@
+alter table users
+    add locale varchar(10) default 'en' not null;
+
+comment on column users.locale is 'Locale of the messages the bot sends in chat';
@@ -0,0 +1,28 @@
+-- Swap and slot redemptions waiting for a moderator to approve them
+create table pending_approvals
+(
+    id            serial
+        constraint pending_approvals_pk
+            primary key,
+    user_id       varchar(16) not null
+        constraint pending_approvals_users_id_fk
+            references users
+            on delete cascade,
+    reward_id     varchar(36) not null
+        constraint pending_approvals_rewards_id_fk
+            references rewards
+            on delete cascade,
+    redemption_id varchar(36) not null,
+    redeemed_by   varchar(25) not null,
+    user_input    text        not null,
+    -- the full redemption is executed once it's approved
+    redemption    jsonb       not null,
+    created_at    timestamptz not null default now(),
+    expires_at    timestamptz not null
+);
+
+create index pending_approvals_user_id_index
+    on pending_approvals (user_id);
+
+create index pending_approvals_expires_at_index
+    on pending_approvals (expires_at);
@@ -0,0 +1,22 @@
+create type ban_rule_kind as enum ('name', 'owner', 'tag', 'zero_width', 'nsfw', 'animated', 'static', 'hash');
+
+-- Bans emotes by their properties instead of their id
+create table emote_ban_rules
+(
+    id         serial
+        constraint emote_ban_rules_pk
+            primary key,
+    channel_id varchar(16)   not null
+        constraint emote_ban_rules_users_id_fk
+            references users
+            on delete cascade,
+    -- null matches all platforms
+    platform   slot_platform,
+    kind       ban_rule_kind not null,
+    -- the regex, owner id, tag or hash depending on the kind
+    value      varchar(200),
+    created_at timestamptz   not null default now()
+);
+
+create index emote_ban_rules_channel_id_index
+    on emote_ban_rules (channel_id);
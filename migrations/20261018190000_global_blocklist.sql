@@ -0,0 +1,17 @@
+create type blocklist_kind as enum ('emote', 'owner');
+
+-- Emotes and uploaders that can't be added in any channel
+create table global_blocklist
+(
+    id         serial
+        constraint global_blocklist_pk
+            primary key,
+    platform   slot_platform  not null,
+    kind       blocklist_kind not null,
+    -- the emote id or the owner's id on the platform
+    value      varchar(64)    not null,
+    reason     varchar(200),
+    created_at timestamptz    not null default now(),
+    constraint global_blocklist_entry_unique
+        unique (platform, kind, value)
+);
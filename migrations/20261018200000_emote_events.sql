@@ -0,0 +1,27 @@
+create type emote_event_kind as enum ('added', 'removed');
+create type emote_event_reason as enum ('redeemed', 'swapped_out', 'expired', 'refreshed', 'ejected', 'injected', 'banned', 'removed', 'untracked');
+
+-- Append-only history of all emotes added or removed by the bot
+create table emote_events
+(
+    id         bigserial
+        constraint emote_events_pk
+            primary key,
+    channel_id varchar(16)        not null
+        constraint emote_events_users_id_fk
+            references users
+            on delete cascade,
+    platform   slot_platform      not null,
+    kind       emote_event_kind   not null,
+    reason     emote_event_reason not null,
+    emote_id   varchar(64)        not null,
+    emote_name varchar(100)       not null,
+    -- the login of the user who caused the event, null if it's the bot
+    actor      varchar(32),
+    reward_id  varchar(64),
+    command    varchar(32),
+    created_at timestamptz        not null default now()
+);
+
+create index emote_events_channel_id_created_at_index
+    on emote_events (channel_id asc, created_at desc);
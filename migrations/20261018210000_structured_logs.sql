@@ -0,0 +1,12 @@
+create type log_entry_type as enum ('message', 'reward_executed', 'reward_failed', 'emote_added', 'emote_removed', 'settings_changed', 'editor_changed');
+
+-- existing entries only have their text, so they're plain messages
+alter table logs
+    add type      log_entry_type not null default 'message',
+    add actor     varchar(32),
+    add target    varchar(100),
+    add reward_id varchar(64),
+    add payload   jsonb;
+
+create index logs_user_id_id_index
+    on logs (user_id asc, id desc);
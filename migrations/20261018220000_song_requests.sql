@@ -0,0 +1,34 @@
+create type song_request_status as enum ('pending', 'queued', 'sent', 'played', 'removed', 'rejected');
+
+-- Tracks requested through spotify::queue rewards. They're sent to Spotify one at a time.
+create table song_requests
+(
+    id            serial
+        constraint song_requests_pk
+            primary key,
+    user_id       varchar(16)         not null
+        constraint song_requests_users_id_fk
+            references users
+            on delete cascade,
+    reward_id     varchar(36)         not null
+        constraint song_requests_rewards_id_fk
+            references rewards
+            on delete cascade,
+    redemption_id varchar(36)         not null,
+    requested_by  varchar(25)         not null,
+    track_uri     varchar(64)         not null,
+    track_name    text                not null,
+    artists       text                not null,
+    duration_ms   int                 not null,
+    status        song_request_status not null,
+    created_at    timestamptz         not null default now(),
+    sent_at       timestamptz,
+    -- the moderator who approved, rejected or removed the request
+    resolved_by   varchar(25)
+);
+
+create index song_requests_user_id_status_index
+    on song_requests (user_id, status);
+
+create index song_requests_user_id_track_uri_index
+    on song_requests (user_id, track_uri);
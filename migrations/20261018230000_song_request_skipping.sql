@@ -0,0 +1,2 @@
+-- removed while in Spotify's queue, the track is skipped once it comes up
+alter type song_request_status add value 'skipping' after 'sent';
@@ -0,0 +1,3 @@
+-- secret of the now-playing overlay, null if it's disabled
+alter table spotify
+    add overlay_token varchar(64) unique;
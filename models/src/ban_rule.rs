@@ -0,0 +1,128 @@
+use crate::emote::SlotPlatform;
+use chrono::{DateTime, Utc};
+use errors::sql::SqlResult;
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, PgPool};
+
+/// What a [BanRule] matches on.
+#[derive(
+    sqlx::Type,
+    derive_more::Display,
+    Debug,
+    Serialize,
+    Deserialize,
+    Clone,
+    Copy,
+    Eq,
+    PartialEq,
+)]
+#[sqlx(type_name = "ban_rule_kind", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum BanRuleKind {
+    /// The name matches the regex in `value`
+    #[display("name")]
+    Name,
+    /// The emote was uploaded by the user in `value`
+    #[display("owner")]
+    Owner,
+    /// The emote is tagged with `value` (7TV only)
+    #[display("tag")]
+    Tag,
+    /// The emote is zero-width (7TV only)
+    #[display("zero-width")]
+    ZeroWidth,
+    /// The emote is flagged as NSFW (7TV only)
+    #[display("nsfw")]
+    Nsfw,
+    #[display("animated")]
+    Animated,
+    #[display("static")]
+    Static,
+    /// The image has the SHA-256 hash in `value`
+    #[display("hash")]
+    Hash,
+}
+
+impl BanRuleKind {
+    pub fn has_value(self) -> bool {
+        matches!(self, Self::Name | Self::Owner | Self::Tag | Self::Hash)
+    }
+}
+
+#[derive(FromRow, Serialize, Deserialize, Debug, Clone)]
+pub struct BanRule {
+    pub id: i32,
+    pub channel_id: String,
+    /// `None` matches emotes from all platforms
+    pub platform: Option<SlotPlatform>,
+    pub kind: BanRuleKind,
+    pub value: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl BanRule {
+    pub async fn get_all_for_channel(
+        channel_id: &str,
+        pool: &PgPool,
+    ) -> SqlResult<Vec<Self>> {
+        // language=PostgreSQL
+        let rules = sqlx::query_as!(
+            Self,
+            r#"
+            SELECT id, channel_id, platform as "platform: _", kind as "kind: _", value, created_at
+            FROM emote_ban_rules
+            WHERE channel_id = $1
+            ORDER BY id
+            "#,
+            channel_id
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(rules)
+    }
+
+    pub async fn create(
+        channel_id: &str,
+        platform: Option<SlotPlatform>,
+        kind: BanRuleKind,
+        value: Option<&str>,
+        pool: &PgPool,
+    ) -> SqlResult<Self> {
+        // language=PostgreSQL
+        let rule = sqlx::query_as!(
+            Self,
+            r#"
+            INSERT INTO emote_ban_rules (channel_id, platform, kind, value)
+            VALUES ($1, $2, $3, $4)
+            RETURNING id, channel_id, platform as "platform: _", kind as "kind: _", value, created_at
+            "#,
+            channel_id,
+            platform as _,
+            kind as _,
+            value
+        )
+        .fetch_one(pool)
+        .await?;
+
+        Ok(rule)
+    }
+
+    /// Returns `false` if the rule doesn't exist.
+    pub async fn delete(
+        id: i32,
+        channel_id: &str,
+        pool: &PgPool,
+    ) -> SqlResult<bool> {
+        // language=PostgreSQL
+        let res = sqlx::query!(
+            "DELETE FROM emote_ban_rules WHERE id = $1 AND channel_id = $2",
+            id,
+            channel_id
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(res.rows_affected() != 0)
+    }
+}
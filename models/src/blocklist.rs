@@ -0,0 +1,119 @@
+use crate::emote::SlotPlatform;
+use chrono::{DateTime, Utc};
+use errors::sql::SqlResult;
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, PgPool};
+
+#[derive(
+    sqlx::Type,
+    derive_more::Display,
+    Debug,
+    Serialize,
+    Deserialize,
+    Clone,
+    Copy,
+    Eq,
+    PartialEq,
+)]
+#[sqlx(type_name = "blocklist_kind", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum BlocklistKind {
+    #[display("emote")]
+    Emote,
+    /// All emotes uploaded by this user
+    #[display("owner")]
+    Owner,
+}
+
+/// An entry in the instance-wide blocklist, managed by the owner.
+#[derive(FromRow, Serialize, Deserialize, Debug, Clone)]
+pub struct BlocklistEntry {
+    pub id: i32,
+    pub platform: SlotPlatform,
+    pub kind: BlocklistKind,
+    pub value: String,
+    pub reason: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl BlocklistEntry {
+    pub async fn get_all(pool: &PgPool) -> SqlResult<Vec<Self>> {
+        // language=PostgreSQL
+        let entries = sqlx::query_as!(
+            Self,
+            r#"
+            SELECT id, platform as "platform: _", kind as "kind: _", value, reason, created_at
+            FROM global_blocklist
+            ORDER BY id
+            "#
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(entries)
+    }
+
+    /// Returns the first entry that blocks the emote.
+    pub async fn find_blocking(
+        platform: SlotPlatform,
+        emote_id: &str,
+        owner_id: Option<&str>,
+        pool: &PgPool,
+    ) -> SqlResult<Option<Self>> {
+        // language=PostgreSQL
+        let entry = sqlx::query_as!(
+            Self,
+            r#"
+            SELECT id, platform as "platform: _", kind as "kind: _", value, reason, created_at
+            FROM global_blocklist
+            WHERE platform = $1
+              AND ((kind = 'emote' AND value = $2) OR (kind = 'owner' AND value = $3))
+            LIMIT 1
+            "#,
+            platform as _,
+            emote_id,
+            owner_id
+        )
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(entry)
+    }
+
+    pub async fn add(
+        platform: SlotPlatform,
+        kind: BlocklistKind,
+        value: &str,
+        reason: Option<&str>,
+        pool: &PgPool,
+    ) -> SqlResult<Self> {
+        // language=PostgreSQL
+        let entry = sqlx::query_as!(
+            Self,
+            r#"
+            INSERT INTO global_blocklist (platform, kind, value, reason)
+            VALUES ($1, $2, $3, $4)
+            RETURNING id, platform as "platform: _", kind as "kind: _", value, reason, created_at
+            "#,
+            platform as _,
+            kind as _,
+            value,
+            reason
+        )
+        .fetch_one(pool)
+        .await?;
+
+        Ok(entry)
+    }
+
+    /// Returns `false` if the entry doesn't exist.
+    pub async fn remove(id: i32, pool: &PgPool) -> SqlResult<bool> {
+        // language=PostgreSQL
+        let res =
+            sqlx::query!("DELETE FROM global_blocklist WHERE id = $1", id)
+                .execute(pool)
+                .await?;
+
+        Ok(res.rows_affected() != 0)
+    }
+}
@@ -0,0 +1,140 @@
+use errors::sql::SqlResult;
+use serde::{Deserialize, Serialize};
+use sqlx::{types::Json, PgPool};
+use std::collections::HashMap;
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct CommandSettings {
+    /// Overrides the global prefix
+    pub prefix: Option<String>,
+    /// Overrides for the builtin commands keyed by their name
+    #[serde(default)]
+    pub commands: HashMap<String, CommandOverride>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct CommandOverride {
+    #[serde(default = "always_true")]
+    pub enabled: bool,
+    /// Additional names for the command
+    #[serde(default)]
+    pub aliases: Vec<String>,
+    /// If this is false, the command can only be used with its aliases
+    #[serde(default = "always_true")]
+    pub default_names: bool,
+    /// The level required to run the command.
+    /// Subcommands requiring a higher level by default still require it.
+    #[serde(default)]
+    pub permission: Option<PermissionLevel>,
+    /// Cooldown for each user in seconds
+    #[serde(default)]
+    pub user_cooldown: Option<u32>,
+    /// Cooldown for the whole channel in seconds
+    #[serde(default)]
+    pub channel_cooldown: Option<u32>,
+}
+
+/// Levels are ordered - each level includes all levels below it.
+#[derive(
+    Serialize, Deserialize, Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum PermissionLevel {
+    Everyone,
+    Subscriber,
+    Vip,
+    Moderator,
+    Editor,
+    Broadcaster,
+}
+
+fn always_true() -> bool {
+    true
+}
+
+impl Default for CommandOverride {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            aliases: Vec::new(),
+            default_names: true,
+            permission: None,
+            user_cooldown: None,
+            channel_cooldown: None,
+        }
+    }
+}
+
+impl CommandSettings {
+    pub async fn get(user_id: &str, pool: &PgPool) -> SqlResult<Self> {
+        // language=PostgreSQL
+        let settings = sqlx::query!(
+            r#"
+            SELECT prefix, commands as "commands: Json<HashMap<String, CommandOverride>>"
+            FROM command_settings
+            WHERE user_id = $1
+            "#,
+            user_id
+        )
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(settings
+            .map(|s| Self {
+                prefix: s.prefix,
+                commands: s.commands.0,
+            })
+            .unwrap_or_default())
+    }
+
+    /// Gets all channels with a custom prefix and their prefix.
+    pub async fn all_prefixes(
+        pool: &PgPool,
+    ) -> SqlResult<HashMap<String, String>> {
+        // language=PostgreSQL
+        let prefixes = sqlx::query!(
+            r#"
+            SELECT user_id, prefix as "prefix!"
+            FROM command_settings
+            WHERE prefix IS NOT NULL
+            "#
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(prefixes
+            .into_iter()
+            .map(|p| (p.user_id, p.prefix))
+            .collect())
+    }
+
+    pub async fn save(&self, user_id: &str, pool: &PgPool) -> SqlResult<()> {
+        // language=PostgreSQL
+        sqlx::query!(
+            "
+            INSERT INTO command_settings (user_id, prefix, commands)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (user_id) DO UPDATE SET prefix = excluded.prefix, commands = excluded.commands
+            ",
+            user_id,
+            self.prefix,
+            Json(&self.commands) as _
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn delete(user_id: &str, pool: &PgPool) -> SqlResult<()> {
+        // language=PostgreSQL
+        sqlx::query!(
+            "DELETE FROM command_settings WHERE user_id = $1",
+            user_id
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,101 @@
+use errors::sql::SqlResult;
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, PgPool};
+
+#[derive(FromRow, Serialize, Deserialize, Clone, Debug)]
+pub struct CustomCommand {
+    pub id: i32,
+    pub user_id: String,
+    pub name: String,
+    pub response: String,
+    /// Cooldown for each user in seconds
+    pub user_cooldown: i32,
+    /// Cooldown for the whole channel in seconds
+    pub channel_cooldown: i32,
+}
+
+impl CustomCommand {
+    pub async fn get_all_for_user(
+        user_id: &str,
+        pool: &PgPool,
+    ) -> SqlResult<Vec<Self>> {
+        // language=PostgreSQL
+        let commands = sqlx::query_as!(
+            Self,
+            "SELECT * FROM custom_commands WHERE user_id = $1 ORDER BY name",
+            user_id
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(commands)
+    }
+
+    pub async fn create(
+        user_id: &str,
+        name: &str,
+        response: &str,
+        user_cooldown: i32,
+        channel_cooldown: i32,
+        pool: &PgPool,
+    ) -> SqlResult<Self> {
+        // language=PostgreSQL
+        let command = sqlx::query_as!(
+            Self,
+            "
+            INSERT INTO custom_commands (user_id, name, response, user_cooldown, channel_cooldown)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING *
+            ",
+            user_id,
+            name,
+            response,
+            user_cooldown,
+            channel_cooldown
+        )
+        .fetch_one(pool)
+        .await?;
+
+        Ok(command)
+    }
+
+    /// Returns `false` if the command doesn't exist.
+    pub async fn update(&self, pool: &PgPool) -> SqlResult<bool> {
+        // language=PostgreSQL
+        let res = sqlx::query!(
+            "
+            UPDATE custom_commands
+            SET name = $3, response = $4, user_cooldown = $5, channel_cooldown = $6
+            WHERE id = $1 AND user_id = $2
+            ",
+            self.id,
+            self.user_id,
+            self.name,
+            self.response,
+            self.user_cooldown,
+            self.channel_cooldown
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(res.rows_affected() != 0)
+    }
+
+    /// Returns `false` if the command doesn't exist.
+    pub async fn delete(
+        id: i32,
+        user_id: &str,
+        pool: &PgPool,
+    ) -> SqlResult<bool> {
+        // language=PostgreSQL
+        let res = sqlx::query!(
+            "DELETE FROM custom_commands WHERE id = $1 AND user_id = $2",
+            id,
+            user_id
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(res.rows_affected() != 0)
+    }
+}
@@ -1,7 +1,15 @@
 use serde::{Deserialize, Serialize};
 
 #[derive(
-    sqlx::Type, Debug, derive_more::Display, Serialize, Deserialize, Clone, Copy,
+    sqlx::Type,
+    Debug,
+    derive_more::Display,
+    Serialize,
+    Deserialize,
+    Clone,
+    Copy,
+    Eq,
+    PartialEq,
 )]
 #[sqlx(type_name = "slot_platform", rename_all = "snake_case")]
 pub enum SlotPlatform {
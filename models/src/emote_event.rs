@@ -0,0 +1,218 @@
+use crate::emote::SlotPlatform;
+use chrono::{DateTime, Utc};
+use errors::sql::SqlResult;
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, PgPool};
+
+#[derive(
+    sqlx::Type,
+    derive_more::Display,
+    Debug,
+    Serialize,
+    Deserialize,
+    Clone,
+    Copy,
+    Eq,
+    PartialEq,
+)]
+#[sqlx(type_name = "emote_event_kind", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum EmoteEventKind {
+    #[display("added")]
+    Added,
+    #[display("removed")]
+    Removed,
+}
+
+/// Why an emote was added or removed.
+#[derive(
+    sqlx::Type,
+    derive_more::Display,
+    Debug,
+    Serialize,
+    Deserialize,
+    Clone,
+    Copy,
+    Eq,
+    PartialEq,
+)]
+#[sqlx(type_name = "emote_event_reason", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum EmoteEventReason {
+    /// Added by a swap or slot reward
+    #[display("redeemed")]
+    Redeemed,
+    /// Removed to make room for a new swap emote
+    #[display("swapped_out")]
+    SwappedOut,
+    /// The slot expired
+    #[display("expired")]
+    Expired,
+    /// The emote was removed on the platform and the bot noticed it
+    #[display("refreshed")]
+    Refreshed,
+    #[display("ejected")]
+    Ejected,
+    #[display("injected")]
+    Injected,
+    #[display("banned")]
+    Banned,
+    /// Removed by a reward that removes emotes
+    #[display("removed")]
+    Removed,
+    /// Untracked from the dashboard
+    #[display("untracked")]
+    Untracked,
+}
+
+#[derive(FromRow, Serialize, Deserialize, Debug)]
+pub struct EmoteEvent {
+    pub id: i64,
+    pub channel_id: String,
+    pub platform: SlotPlatform,
+    pub kind: EmoteEventKind,
+    pub reason: EmoteEventReason,
+    pub emote_id: String,
+    pub emote_name: String,
+    pub actor: Option<String>,
+    pub reward_id: Option<String>,
+    pub command: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// An event that's about to be recorded.
+pub struct NewEmoteEvent<'a> {
+    pub channel_id: &'a str,
+    pub platform: SlotPlatform,
+    pub kind: EmoteEventKind,
+    pub reason: EmoteEventReason,
+    pub emote_id: &'a str,
+    pub emote_name: &'a str,
+    pub actor: Option<&'a str>,
+    pub reward_id: Option<&'a str>,
+    pub command: Option<&'a str>,
+}
+
+/// Filters for [EmoteEvent::query]. All filters are optional.
+#[derive(Deserialize, Debug, Default)]
+pub struct EmoteEventFilter {
+    pub platform: Option<SlotPlatform>,
+    pub kind: Option<EmoteEventKind>,
+    pub reason: Option<EmoteEventReason>,
+    pub actor: Option<String>,
+    /// Matches a part of the emote's name
+    pub emote: Option<String>,
+    pub reward_id: Option<String>,
+    pub since: Option<DateTime<Utc>>,
+    pub until: Option<DateTime<Utc>>,
+}
+
+impl<'a> NewEmoteEvent<'a> {
+    pub fn new(
+        channel_id: &'a str,
+        platform: SlotPlatform,
+        kind: EmoteEventKind,
+        reason: EmoteEventReason,
+        emote_id: &'a str,
+        emote_name: &'a str,
+    ) -> Self {
+        Self {
+            channel_id,
+            platform,
+            kind,
+            reason,
+            emote_id,
+            emote_name,
+            actor: None,
+            reward_id: None,
+            command: None,
+        }
+    }
+
+    pub fn actor(mut self, actor: &'a str) -> Self {
+        self.actor = Some(actor);
+        self
+    }
+
+    pub fn reward(mut self, reward_id: &'a str) -> Self {
+        self.reward_id = Some(reward_id);
+        self
+    }
+
+    pub fn command(mut self, command: &'a str) -> Self {
+        self.command = Some(command);
+        self
+    }
+
+    pub async fn insert(self, pool: &PgPool) -> SqlResult<()> {
+        // language=PostgreSQL
+        sqlx::query!(
+            r#"
+            INSERT INTO emote_events (channel_id, platform, kind, reason, emote_id, emote_name, actor, reward_id, command)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            "#,
+            self.channel_id,
+            self.platform as _,
+            self.kind as _,
+            self.reason as _,
+            self.emote_id,
+            self.emote_name,
+            self.actor,
+            self.reward_id,
+            self.command
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+}
+
+impl EmoteEvent {
+    /// Returns at most `limit` events older than the event with the id
+    /// `before`, newest first.
+    pub async fn query(
+        channel_id: &str,
+        filter: &EmoteEventFilter,
+        before: Option<i64>,
+        limit: i64,
+        pool: &PgPool,
+    ) -> SqlResult<Vec<Self>> {
+        // language=PostgreSQL
+        let events = sqlx::query_as!(
+            Self,
+            r#"
+            SELECT id, channel_id, platform as "platform: _", kind as "kind: _", reason as "reason: _",
+                   emote_id, emote_name, actor, reward_id, command, created_at
+            FROM emote_events
+            WHERE channel_id = $1
+              AND ($2::bigint IS NULL OR id < $2)
+              AND ($3::slot_platform IS NULL OR platform = $3)
+              AND ($4::emote_event_kind IS NULL OR kind = $4)
+              AND ($5::emote_event_reason IS NULL OR reason = $5)
+              AND ($6::text IS NULL OR actor = lower($6))
+              AND ($7::text IS NULL OR emote_name ILIKE '%' || $7 || '%')
+              AND ($8::text IS NULL OR reward_id = $8)
+              AND ($9::timestamptz IS NULL OR created_at >= $9)
+              AND ($10::timestamptz IS NULL OR created_at < $10)
+            ORDER BY id DESC
+            LIMIT $11
+            "#,
+            channel_id,
+            before,
+            filter.platform as _,
+            filter.kind as _,
+            filter.reason as _,
+            filter.actor,
+            filter.emote,
+            filter.reward_id,
+            filter.since,
+            filter.until,
+            limit
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(events)
+    }
+}
@@ -32,6 +32,17 @@ pub async fn remove(id: &EventSubIdRef, pool: &PgPool) -> SqlResult<()> {
     Ok(())
 }
 
+pub async fn remove_all_for_user(
+    user_id: &str,
+    pool: &PgPool,
+) -> SqlResult<()> {
+    sqlx::query_scalar!("DELETE FROM eventsubs WHERE user_id = $1", user_id)
+        .execute(pool)
+        .await?;
+
+    Ok(())
+}
+
 pub async fn all_for_user(
     user_id: &str,
     pool: &PgPool,
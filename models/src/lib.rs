@@ -1,12 +1,19 @@
+pub mod ban_rule;
 pub mod banned_emote;
+pub mod blocklist;
+pub mod command_settings;
 pub mod config;
+pub mod custom_command;
 pub mod discord;
 pub mod editor;
 pub mod emote;
+pub mod emote_event;
 pub mod eventsub;
 pub mod log_entry;
+pub mod pending_approval;
 pub mod reward;
 pub mod slot;
+pub mod song_request;
 pub mod spotify;
 pub mod swap_emote;
 pub mod timed_mode;
@@ -1,42 +1,196 @@
 use chrono::{DateTime, Utc};
 use errors::sql::SqlResult;
-use serde::Serialize;
-use sqlx::{FromRow, PgPool};
+use serde::{de::IntoDeserializer, Deserialize, Deserializer, Serialize};
+use serde_json::Value;
+use sqlx::{types::Json, FromRow, PgPool};
+
+#[derive(
+    sqlx::Type,
+    derive_more::Display,
+    Debug,
+    Serialize,
+    Deserialize,
+    Clone,
+    Copy,
+    Eq,
+    PartialEq,
+)]
+#[sqlx(type_name = "log_entry_type", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum LogEntryType {
+    /// Only has its content (e.g. entries from before types existed)
+    #[display("message")]
+    Message,
+    #[display("reward_executed")]
+    RewardExecuted,
+    #[display("reward_failed")]
+    RewardFailed,
+    #[display("emote_added")]
+    EmoteAdded,
+    #[display("emote_removed")]
+    EmoteRemoved,
+    #[display("settings_changed")]
+    SettingsChanged,
+    #[display("editor_changed")]
+    EditorChanged,
+}
 
 #[derive(FromRow, Serialize)]
 pub struct LogEntry {
+    pub id: i32,
     pub date: DateTime<Utc>,
+    #[serde(rename = "type")]
+    pub kind: LogEntryType,
+    /// A human-readable summary
     pub content: String,
+    /// The login of the user who caused this entry
+    pub actor: Option<String>,
+    /// What the entry is about (e.g. an emote or an editor)
+    pub target: Option<String>,
+    pub reward_id: Option<String>,
+    pub payload: Option<Json<Value>>,
 }
 
-impl LogEntry {
-    pub async fn get_for_user(id: &str, pool: &PgPool) -> SqlResult<Vec<Self>> {
-        // language=PostgreSQL
-        let logs = sqlx::query_as!(
-            Self,
-            "SELECT date, content FROM logs WHERE user_id = $1 ORDER BY date desc",
-            id
-        )
-        .fetch_all(pool)
-        .await?;
+/// A log entry that's about to be saved.
+pub struct NewLogEntry<'a> {
+    pub user_id: &'a str,
+    pub kind: LogEntryType,
+    pub content: String,
+    pub actor: Option<&'a str>,
+    pub target: Option<&'a str>,
+    pub reward_id: Option<&'a str>,
+    pub payload: Option<Value>,
+}
 
-        Ok(logs)
+/// Filters for [LogEntry::query]. All filters are optional.
+#[derive(Deserialize, Debug, Default)]
+pub struct LogFilter {
+    /// Only entries with one of these types (comma-separated in queries)
+    #[serde(default, deserialize_with = "comma_separated")]
+    pub types: Option<Vec<LogEntryType>>,
+    pub since: Option<DateTime<Utc>>,
+    pub until: Option<DateTime<Utc>>,
+}
+
+impl<'a> NewLogEntry<'a> {
+    pub fn actor(mut self, actor: &'a str) -> Self {
+        self.actor = Some(actor);
+        self
     }
 
-    pub async fn create(
-        id: &str,
-        content: &str,
-        pool: &PgPool,
-    ) -> SqlResult<()> {
+    pub fn target(mut self, target: &'a str) -> Self {
+        self.target = Some(target);
+        self
+    }
+
+    pub fn reward(mut self, reward_id: &'a str) -> Self {
+        self.reward_id = Some(reward_id);
+        self
+    }
+
+    pub fn payload(mut self, payload: Value) -> Self {
+        self.payload = Some(payload);
+        self
+    }
+
+    pub async fn insert(self, pool: &PgPool) -> SqlResult<()> {
         // language=PostgreSQL
         sqlx::query!(
-            "INSERT INTO logs (user_id, date, content) VALUES ($1, $2, $3)",
-            id,
+            r#"
+            INSERT INTO logs (user_id, date, type, content, actor, target, reward_id, payload)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            "#,
+            self.user_id,
             Utc::now(),
-            content
+            self.kind as _,
+            self.content,
+            self.actor,
+            self.target,
+            self.reward_id,
+            self.payload
         )
         .execute(pool)
         .await?;
         Ok(())
     }
 }
+
+impl LogEntry {
+    pub fn new(
+        user_id: &str,
+        kind: LogEntryType,
+        content: impl Into<String>,
+    ) -> NewLogEntry<'_> {
+        NewLogEntry {
+            user_id,
+            kind,
+            content: content.into(),
+            actor: None,
+            target: None,
+            reward_id: None,
+            payload: None,
+        }
+    }
+
+    /// Returns at most `limit` entries older than the entry with the id
+    /// `before`, newest first.
+    pub async fn query(
+        user_id: &str,
+        filter: &LogFilter,
+        before: Option<i32>,
+        limit: i64,
+        pool: &PgPool,
+    ) -> SqlResult<Vec<Self>> {
+        let types = filter
+            .types
+            .as_ref()
+            .map(|t| t.iter().map(ToString::to_string).collect::<Vec<_>>());
+        // language=PostgreSQL
+        let logs = sqlx::query_as!(
+            Self,
+            r#"
+            SELECT id, date, type as "kind: _", content, actor, target, reward_id,
+                   payload as "payload: Json<Value>"
+            FROM logs
+            WHERE user_id = $1
+              AND ($2::int IS NULL OR id < $2)
+              AND ($3::text[] IS NULL OR type::text = ANY($3))
+              AND ($4::timestamptz IS NULL OR date >= $4)
+              AND ($5::timestamptz IS NULL OR date < $5)
+            ORDER BY id DESC
+            LIMIT $6
+            "#,
+            user_id,
+            before,
+            types.as_deref(),
+            filter.since,
+            filter.until,
+            limit
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(logs)
+    }
+}
+
+fn comma_separated<'de, D>(
+    deserializer: D,
+) -> Result<Option<Vec<LogEntryType>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let Some(types) = Option::<String>::deserialize(deserializer)? else {
+        return Ok(None);
+    };
+    types
+        .split(',')
+        .filter(|t| !t.is_empty())
+        .map(|t| {
+            LogEntryType::deserialize(
+                IntoDeserializer::<D::Error>::into_deserializer(t),
+            )
+        })
+        .collect::<Result<_, _>>()
+        .map(Some)
+}
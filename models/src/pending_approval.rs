@@ -0,0 +1,127 @@
+use chrono::{DateTime, Utc};
+use errors::sql::SqlResult;
+use serde::{Deserialize, Serialize};
+use sqlx::{types::Json, FromRow, PgPool};
+use twitch_api::eventsub::channel::ChannelPointsCustomRewardRedemptionAddV1Payload as Redemption;
+
+/// A redemption that's only executed once a moderator approved it.
+#[derive(FromRow, Serialize, Deserialize, Debug)]
+pub struct PendingApproval {
+    pub id: i32,
+    pub user_id: String,
+    pub reward_id: String,
+    pub redemption_id: String,
+    pub redeemed_by: String,
+    pub user_input: String,
+    #[serde(skip_serializing)]
+    pub redemption: Json<Redemption>,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
+
+impl PendingApproval {
+    pub async fn create(
+        redemption: &Redemption,
+        expires_at: DateTime<Utc>,
+        pool: &PgPool,
+    ) -> SqlResult<Self> {
+        // language=PostgreSQL
+        let pending = sqlx::query_as!(
+            Self,
+            r#"
+            INSERT INTO pending_approvals (user_id, reward_id, redemption_id, redeemed_by, user_input, redemption, expires_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            RETURNING id, user_id, reward_id, redemption_id, redeemed_by, user_input,
+                      redemption as "redemption: Json<Redemption>", created_at, expires_at
+            "#,
+            redemption.broadcaster_user_id.as_str(),
+            redemption.reward.id.as_str(),
+            redemption.id.as_str(),
+            redemption.user_login.as_str(),
+            redemption.user_input,
+            Json(redemption) as _,
+            expires_at
+        )
+        .fetch_one(pool)
+        .await?;
+
+        Ok(pending)
+    }
+
+    pub async fn get_all_for_user(
+        user_id: &str,
+        pool: &PgPool,
+    ) -> SqlResult<Vec<Self>> {
+        // language=PostgreSQL
+        let pending = sqlx::query_as!(
+            Self,
+            r#"
+            SELECT id, user_id, reward_id, redemption_id, redeemed_by, user_input,
+                   redemption as "redemption: Json<Redemption>", created_at, expires_at
+            FROM pending_approvals
+            WHERE user_id = $1
+            ORDER BY created_at
+            "#,
+            user_id
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(pending)
+    }
+
+    /// The ids of all redemptions waiting for approval.
+    pub async fn get_all_redemption_ids(
+        pool: &PgPool,
+    ) -> SqlResult<Vec<String>> {
+        // language=PostgreSQL
+        let ids =
+            sqlx::query_scalar!("SELECT redemption_id FROM pending_approvals")
+                .fetch_all(pool)
+                .await?;
+
+        Ok(ids)
+    }
+
+    /// Removes the pending redemption so it can only be resolved once.
+    pub async fn take(
+        id: i32,
+        user_id: &str,
+        pool: &PgPool,
+    ) -> SqlResult<Option<Self>> {
+        // language=PostgreSQL
+        let pending = sqlx::query_as!(
+            Self,
+            r#"
+            DELETE FROM pending_approvals
+            WHERE id = $1 AND user_id = $2
+            RETURNING id, user_id, reward_id, redemption_id, redeemed_by, user_input,
+                      redemption as "redemption: Json<Redemption>", created_at, expires_at
+            "#,
+            id,
+            user_id
+        )
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(pending)
+    }
+
+    /// Removes and returns all redemptions that weren't resolved in time.
+    pub async fn take_expired(pool: &PgPool) -> SqlResult<Vec<Self>> {
+        // language=PostgreSQL
+        let expired = sqlx::query_as!(
+            Self,
+            r#"
+            DELETE FROM pending_approvals
+            WHERE expires_at < now()
+            RETURNING id, user_id, reward_id, redemption_id, redeemed_by, user_input,
+                      redemption as "redemption: Json<Redemption>", created_at, expires_at
+            "#
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(expired)
+    }
+}
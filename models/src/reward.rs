@@ -21,6 +21,52 @@ pub struct Reward {
     pub data: Json<RewardData>,
     pub live_delay: Option<String>,
     pub auto_accept: bool,
+    pub refund_on_error: RefundPolicy,
+    pub reply_templates: Json<ReplyTemplates>,
+}
+
+/// Custom replies sent to chat. Unset templates fall back to the
+/// channel's default and then to the built-in messages.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct ReplyTemplates {
+    /// Sent after a successful redemption
+    #[serde(default)]
+    pub success: Option<String>,
+    /// Sent if the redemption failed
+    #[serde(default)]
+    pub error: Option<String>,
+}
+
+/// Decides if a redemption is refunded when it couldn't be executed.
+#[derive(
+    sqlx::Type,
+    Debug,
+    Serialize,
+    Deserialize,
+    Clone,
+    Copy,
+    Eq,
+    PartialEq,
+    Default,
+)]
+#[sqlx(type_name = "refund_policy", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum RefundPolicy {
+    #[default]
+    Always,
+    Never,
+    /// Only refund if the redemption itself was invalid (e.g. bad input)
+    UserErrorOnly,
+}
+
+impl RefundPolicy {
+    pub fn should_refund(self, is_user_error: bool) -> bool {
+        match self {
+            RefundPolicy::Always => true,
+            RefundPolicy::Never => false,
+            RefundPolicy::UserErrorOnly => is_user_error,
+        }
+    }
 }
 
 #[derive(FromRow)]
@@ -54,12 +100,18 @@ pub enum RewardData {
     SubOnly(String),
     #[display("mode::emote")]
     EmoteOnly(String),
+    #[display("mode::slow")]
+    SlowMode(SlowModeRewardData),
+    #[display("mode::followers")]
+    FollowerOnly(FollowerOnlyRewardData),
     #[display("swap::bttv")]
     BttvSwap(#[serde(default)] SwapRewardData),
     #[display("swap::ffz")]
     FfzSwap(#[serde(default)] SwapRewardData),
     #[display("swap::7tv")]
     SevenTvSwap(#[serde(default)] SwapRewardData),
+    #[display("swap::any")]
+    AnySwap(AnySwapRewardData),
     #[display("slot::bttv")]
     BttvSlot(SlotRewardData),
     #[display("slot::ffz")]
@@ -69,11 +121,31 @@ pub enum RewardData {
     #[display("spotify::skip")]
     SpotifySkip(()),
     #[display("spotify::queue")]
-    SpotifyQueue(SpotifyPlayOptions),
+    SpotifyQueue(SpotifyQueueOptions),
     #[display("spotify::play")]
     SpotifyPlay(SpotifyPlayOptions),
     #[display("rem-emote")]
     RemEmote(RemEmoteRewardData),
+    #[display("poll")]
+    Poll(PollRewardData),
+    #[display("prediction")]
+    Prediction(PollRewardData),
+}
+
+impl RewardData {
+    /// Whether a moderator has to approve redemptions before they're executed
+    pub fn requires_approval(&self) -> bool {
+        match self {
+            Self::BttvSwap(data)
+            | Self::FfzSwap(data)
+            | Self::SevenTvSwap(data) => data.require_approval,
+            Self::AnySwap(data) => data.swap.require_approval,
+            Self::BttvSlot(data)
+            | Self::FfzSlot(data)
+            | Self::SevenTvSlot(data) => data.require_approval,
+            _ => false,
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -83,16 +155,36 @@ pub struct TimeoutRewardData {
     pub vip: bool,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SlowModeRewardData {
+    pub duration: String,
+    /// Time a user has to wait between messages
+    pub wait_time: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct FollowerOnlyRewardData {
+    pub duration: String,
+    /// Time a user has to follow the channel before chatting
+    pub follow_age: String,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct SlotRewardData {
     pub slots: usize,
     pub expiration: String,
     #[serde(default = "always_true")]
     pub allow_unlisted: bool,
+    /// Resolve emote names using the platform's search if set
+    #[serde(default)]
+    pub search: Option<EmoteSearchMode>,
     /// Only controls the "ok" case
     /// Errors are always printed
     #[serde(default = "always_true")]
     pub reply: bool,
+    /// Only add the emote once a moderator approved it
+    #[serde(default)]
+    pub require_approval: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -100,10 +192,55 @@ pub struct SwapRewardData {
     pub limit: Option<u16>,
     #[serde(default = "always_true")]
     pub allow_unlisted: bool,
+    /// How many emotes can be added in a single redemption
+    #[serde(default)]
+    pub max_per_redemption: Option<u8>,
+    /// Resolve emote names using the platform's search if set
+    #[serde(default)]
+    pub search: Option<EmoteSearchMode>,
     /// Only controls the "ok" case
     /// Errors are always printed
     #[serde(default = "always_true")]
     pub reply: bool,
+    /// Only add the emote once a moderator approved it
+    #[serde(default)]
+    pub require_approval: bool,
+}
+
+/// Picks an emote if multiple emotes match the searched name.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Eq, PartialEq)]
+pub enum EmoteSearchMode {
+    /// Only emotes with exactly the same name
+    Exact,
+    /// Prefer exact matches, otherwise take the most used emote with the
+    /// same name in a different case
+    MostUsed,
+    /// Fail if more than one emote has the name
+    ErrorIfAmbiguous,
+}
+
+/// A swap reward where the platform is detected from the input.
+/// The limit is shared across all platforms.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AnySwapRewardData {
+    #[serde(flatten)]
+    pub swap: SwapRewardData,
+    #[serde(default = "always_true")]
+    pub bttv: bool,
+    #[serde(default = "always_true")]
+    pub ffz: bool,
+    #[serde(default = "always_true")]
+    pub seventv: bool,
+}
+
+impl AnySwapRewardData {
+    pub fn is_enabled(&self, platform: SlotPlatform) -> bool {
+        match platform {
+            SlotPlatform::Bttv => self.bttv,
+            SlotPlatform::Ffz => self.ffz,
+            SlotPlatform::SevenTv => self.seventv,
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -115,12 +252,26 @@ pub struct RemEmoteRewardData {
     pub reply: bool,
 }
 
+/// Used for polls and predictions
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PollRewardData {
+    /// How long the poll runs or for how long predictions can be made
+    pub duration: String,
+    /// Only controls the "ok" case
+    /// Errors are always printed
+    #[serde(default = "always_true")]
+    pub reply: bool,
+}
+
 impl Default for SwapRewardData {
     fn default() -> Self {
         Self {
             limit: Default::default(),
             allow_unlisted: true,
+            max_per_redemption: None,
+            search: None,
             reply: true,
+            require_approval: false,
         }
     }
 }
@@ -132,6 +283,39 @@ const fn always_true() -> bool {
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct SpotifyPlayOptions {
     pub allow_explicit: bool,
+    /// Maximum length of a track in seconds
+    #[serde(default)]
+    pub max_duration: Option<u32>,
+    /// Minimum popularity of a track (0-100)
+    #[serde(default)]
+    pub min_popularity: Option<u32>,
+    /// If set, only tracks from these playlists (ids or links) can be
+    /// requested
+    #[serde(default)]
+    pub playlists: Vec<String>,
+    /// If set, at least one artist of a track has to have one of these
+    /// genres
+    #[serde(default)]
+    pub genres: Vec<String>,
+}
+
+/// Requests are saved and sent to Spotify one at a time.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SpotifyQueueOptions {
+    #[serde(flatten)]
+    pub play: SpotifyPlayOptions,
+    /// Open requests a user can have at once
+    #[serde(default)]
+    pub max_per_user: Option<u32>,
+    /// A track can't be requested again within this many hours
+    #[serde(default)]
+    pub repeat_cooldown: Option<u32>,
+    /// Artists, track names or track ids that can't be requested
+    #[serde(default)]
+    pub blocklist: Vec<String>,
+    /// Requests have to be approved by a moderator before they're queued
+    #[serde(default)]
+    pub require_approval: bool,
 }
 
 #[derive(FromRow, Serialize, Deserialize)]
@@ -145,6 +329,8 @@ impl Reward {
         data: RewardData,
         live_delay: Option<String>,
         auto_accept: bool,
+        refund_on_error: RefundPolicy,
+        reply_templates: ReplyTemplates,
     ) -> Self {
         Self {
             user_id: res.broadcaster_id.clone().take(),
@@ -152,6 +338,8 @@ impl Reward {
             id: res.id.clone().take(),
             live_delay,
             auto_accept,
+            refund_on_error,
+            reply_templates: Json(reply_templates),
         }
     }
 
@@ -160,7 +348,8 @@ impl Reward {
         let reward: Self = sqlx::query_as!(
             Reward,
             r#"
-            SELECT id, user_id, data as "data: Json<RewardData>", live_delay, auto_accept
+            SELECT id, user_id, data as "data: Json<RewardData>", live_delay, auto_accept, refund_on_error as "refund_on_error: _",
+                   reply_templates as "reply_templates: Json<ReplyTemplates>"
             FROM rewards
             WHERE id = $1
             "#,
@@ -180,7 +369,8 @@ impl Reward {
         let rewards: Vec<Self> = sqlx::query_as!(
             Reward,
             r#"
-            SELECT id, user_id, data as "data: Json<RewardData>", live_delay, auto_accept
+            SELECT id, user_id, data as "data: Json<RewardData>", live_delay, auto_accept, refund_on_error as "refund_on_error: _",
+                   reply_templates as "reply_templates: Json<ReplyTemplates>"
             FROM rewards
             WHERE user_id = $1
             "#,
@@ -210,8 +400,9 @@ impl Reward {
             r#"
             SELECT data as "data: Json<RewardData>", count(s.id) as "count!", r.id as "reward_id"
             FROM rewards r
-                    LEFT JOIN (select id, reward_id from swap_emotes where platform = $2) s on s.reward_id = r.id
-                WHERE r.data->>'type' = $3 AND r.user_id = $1
+                    LEFT JOIN swap_emotes s
+                        on s.reward_id = r.id AND (s.platform = $2 OR r.data->>'type' = 'AnySwap')
+                WHERE r.data->>'type' IN ($3, 'AnySwap') AND r.user_id = $1
             GROUP BY r.id, r.data
             "#,
             user_id,
@@ -228,6 +419,9 @@ impl Reward {
                     RewardData::FfzSwap(d) => d.limit,
                     RewardData::BttvSwap(d) => d.limit,
                     RewardData::SevenTvSwap(d) => d.limit,
+                    RewardData::AnySwap(d) if d.is_enabled(platform) => {
+                        d.swap.limit
+                    }
                     _ => return None,
                 };
                 Some(SwapEmoteStat {
@@ -302,12 +496,14 @@ impl Reward {
         let mut tx = pool.begin().await?;
         // language=PostgreSQL
         let _ = sqlx::query!(
-            "INSERT INTO rewards (id, user_id, data, live_delay, auto_accept) VALUES ($1, $2, $3, $4, $5)",
+            "INSERT INTO rewards (id, user_id, data, live_delay, auto_accept, refund_on_error, reply_templates) VALUES ($1, $2, $3, $4, $5, $6, $7)",
             self.id,
             self.user_id,
             Json(&self.data) as _,
             self.live_delay,
             self.auto_accept,
+            self.refund_on_error as _,
+            Json(&self.reply_templates) as _,
         )
         .execute(&mut *tx)
         .await?;
@@ -320,11 +516,13 @@ impl Reward {
         let mut tx = pool.begin().await?;
         // language=PostgreSQL
         let _ = sqlx::query!(
-            "UPDATE rewards SET data=$2, live_delay = $3, auto_accept = $4 WHERE id=$1",
+            "UPDATE rewards SET data=$2, live_delay = $3, auto_accept = $4, refund_on_error = $5, reply_templates = $6 WHERE id=$1",
             self.id,
             Json(self.data.clone()) as _,
             self.live_delay,
             self.auto_accept,
+            self.refund_on_error as _,
+            Json(&self.reply_templates) as _,
         )
         .execute(&mut *tx)
         .await?;
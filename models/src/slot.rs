@@ -126,6 +126,7 @@ impl Slot {
         let pending = sqlx::query_as!(Self, r#"
             SELECT id, user_id, emote_id, expires, reward_id, platform as "platform: _", name, added_at, added_by FROM slots
             WHERE emote_id is not null AND expires is not null AND expires < (now() + '1 minute'::interval)
+                AND user_id NOT IN (SELECT id FROM users WHERE revoked)
         "#).fetch_all(pool).await?;
 
         Ok(pending)
@@ -0,0 +1,402 @@
+use chrono::{DateTime, Utc};
+use errors::sql::SqlResult;
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, PgPool};
+
+#[derive(
+    sqlx::Type,
+    derive_more::Display,
+    Debug,
+    Serialize,
+    Deserialize,
+    Clone,
+    Copy,
+    Eq,
+    PartialEq,
+)]
+#[sqlx(type_name = "song_request_status", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum SongRequestStatus {
+    /// Waiting for a moderator to approve it
+    #[display("pending")]
+    Pending,
+    /// Waiting to be sent to Spotify
+    #[display("queued")]
+    Queued,
+    /// In Spotify's queue
+    #[display("sent")]
+    Sent,
+    /// Removed while in Spotify's queue, skipped once it plays
+    #[display("skipping")]
+    Skipping,
+    #[display("played")]
+    Played,
+    #[display("removed")]
+    Removed,
+    #[display("rejected")]
+    Rejected,
+}
+
+#[derive(FromRow, Serialize, Deserialize, Debug)]
+pub struct SongRequest {
+    pub id: i32,
+    pub user_id: String,
+    pub reward_id: String,
+    pub redemption_id: String,
+    pub requested_by: String,
+    pub track_uri: String,
+    pub track_name: String,
+    pub artists: String,
+    pub duration_ms: i32,
+    pub status: SongRequestStatus,
+    pub created_at: DateTime<Utc>,
+    pub sent_at: Option<DateTime<Utc>>,
+    pub resolved_by: Option<String>,
+}
+
+/// A request that's about to be saved.
+pub struct NewSongRequest<'a> {
+    pub user_id: &'a str,
+    pub reward_id: &'a str,
+    pub redemption_id: &'a str,
+    pub requested_by: &'a str,
+    pub track_uri: &'a str,
+    pub track_name: &'a str,
+    pub artists: &'a str,
+    pub duration_ms: i32,
+    pub status: SongRequestStatus,
+}
+
+impl NewSongRequest<'_> {
+    pub async fn insert(self, pool: &PgPool) -> SqlResult<SongRequest> {
+        // language=PostgreSQL
+        let request = sqlx::query_as!(
+            SongRequest,
+            r#"
+            INSERT INTO song_requests (user_id, reward_id, redemption_id, requested_by, track_uri, track_name, artists, duration_ms, status)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            RETURNING id, user_id, reward_id, redemption_id, requested_by, track_uri, track_name, artists, duration_ms,
+                      status as "status: _", created_at, sent_at, resolved_by
+            "#,
+            self.user_id,
+            self.reward_id,
+            self.redemption_id,
+            self.requested_by,
+            self.track_uri,
+            self.track_name,
+            self.artists,
+            self.duration_ms,
+            self.status as _
+        )
+        .fetch_one(pool)
+        .await?;
+
+        Ok(request)
+    }
+}
+
+impl SongRequest {
+    /// All requests that haven't been played or removed yet, in order.
+    pub async fn get_open(
+        user_id: &str,
+        pool: &PgPool,
+    ) -> SqlResult<Vec<Self>> {
+        // language=PostgreSQL
+        let requests = sqlx::query_as!(
+            Self,
+            r#"
+            SELECT id, user_id, reward_id, redemption_id, requested_by, track_uri, track_name, artists, duration_ms,
+                   status as "status: _", created_at, sent_at, resolved_by
+            FROM song_requests
+            WHERE user_id = $1 AND status IN ('pending', 'queued', 'sent')
+            ORDER BY id
+            "#,
+            user_id
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(requests)
+    }
+
+    /// The number of open requests of a user on a reward.
+    pub async fn count_open_by(
+        user_id: &str,
+        reward_id: &str,
+        requested_by: &str,
+        pool: &PgPool,
+    ) -> SqlResult<i64> {
+        // language=PostgreSQL
+        let count = sqlx::query_scalar!(
+            r#"
+            SELECT count(*) as "count!"
+            FROM song_requests
+            WHERE user_id = $1 AND reward_id = $2 AND requested_by = $3
+              AND status IN ('pending', 'queued', 'sent')
+            "#,
+            user_id,
+            reward_id,
+            requested_by
+        )
+        .fetch_one(pool)
+        .await?;
+
+        Ok(count)
+    }
+
+    /// Whether the track was requested (and not removed) since `since`.
+    pub async fn was_requested_since(
+        user_id: &str,
+        track_uri: &str,
+        since: DateTime<Utc>,
+        pool: &PgPool,
+    ) -> SqlResult<bool> {
+        // language=PostgreSQL
+        let requested = sqlx::query_scalar!(
+            r#"
+            SELECT exists(
+                SELECT 1
+                FROM song_requests
+                WHERE user_id = $1 AND track_uri = $2 AND created_at > $3
+                  AND status IN ('pending', 'queued', 'sent', 'played')
+            ) as "exists!"
+            "#,
+            user_id,
+            track_uri,
+            since
+        )
+        .fetch_one(pool)
+        .await?;
+
+        Ok(requested)
+    }
+
+    /// The number of open requests before this one.
+    pub async fn position(&self, pool: &PgPool) -> SqlResult<i64> {
+        // language=PostgreSQL
+        let position = sqlx::query_scalar!(
+            r#"
+            SELECT count(*) as "count!"
+            FROM song_requests
+            WHERE user_id = $1 AND id < $2
+              AND status IN ('pending', 'queued', 'sent')
+            "#,
+            self.user_id,
+            self.id
+        )
+        .fetch_one(pool)
+        .await?;
+
+        Ok(position)
+    }
+
+    /// Channels that have requests waiting for or in Spotify's queue.
+    pub async fn get_channels_to_feed(pool: &PgPool) -> SqlResult<Vec<String>> {
+        // language=PostgreSQL
+        let channels = sqlx::query_scalar!(
+            "SELECT DISTINCT user_id FROM song_requests WHERE status IN ('queued', 'sent', 'skipping')"
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(channels)
+    }
+
+    /// The oldest request in Spotify's queue.
+    pub async fn get_in_flight(
+        user_id: &str,
+        pool: &PgPool,
+    ) -> SqlResult<Option<Self>> {
+        // language=PostgreSQL
+        let request = sqlx::query_as!(
+            Self,
+            r#"
+            SELECT id, user_id, reward_id, redemption_id, requested_by, track_uri, track_name, artists, duration_ms,
+                   status as "status: _", created_at, sent_at, resolved_by
+            FROM song_requests
+            WHERE user_id = $1 AND status IN ('sent', 'skipping')
+            ORDER BY id
+            LIMIT 1
+            "#,
+            user_id
+        )
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(request)
+    }
+
+    /// The next request to send to Spotify.
+    pub async fn get_next_queued(
+        user_id: &str,
+        pool: &PgPool,
+    ) -> SqlResult<Option<Self>> {
+        // language=PostgreSQL
+        let request = sqlx::query_as!(
+            Self,
+            r#"
+            SELECT id, user_id, reward_id, redemption_id, requested_by, track_uri, track_name, artists, duration_ms,
+                   status as "status: _", created_at, sent_at, resolved_by
+            FROM song_requests
+            WHERE user_id = $1 AND status = 'queued'
+            ORDER BY id
+            LIMIT 1
+            "#,
+            user_id
+        )
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(request)
+    }
+
+    pub async fn mark_sent(id: i32, pool: &PgPool) -> SqlResult<()> {
+        // language=PostgreSQL
+        sqlx::query!(
+            "UPDATE song_requests SET status = 'sent', sent_at = now() WHERE id = $1 AND status = 'queued'",
+            id
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Returns `false` if the request wasn't in Spotify's queue anymore.
+    pub async fn mark_played(id: i32, pool: &PgPool) -> SqlResult<bool> {
+        // language=PostgreSQL
+        let res = sqlx::query!(
+            "UPDATE song_requests SET status = 'played' WHERE id = $1 AND status = 'sent'",
+            id
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(res.rows_affected() != 0)
+    }
+
+    /// Returns `false` if the request wasn't skipping anymore.
+    pub async fn mark_removed(id: i32, pool: &PgPool) -> SqlResult<bool> {
+        // language=PostgreSQL
+        let res = sqlx::query!(
+            "UPDATE song_requests SET status = 'removed' WHERE id = $1 AND status = 'skipping'",
+            id
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(res.rows_affected() != 0)
+    }
+
+    /// The requester of a track that was sent to Spotify within the last
+    /// three hours.
+    pub async fn get_recent_requester(
+        user_id: &str,
+        track_uri: &str,
+        pool: &PgPool,
+    ) -> SqlResult<Option<String>> {
+        // language=PostgreSQL
+        let requester = sqlx::query_scalar!(
+            "
+            SELECT requested_by FROM song_requests
+            WHERE user_id = $1
+              AND track_uri = $2
+              AND status IN ('sent', 'played')
+              AND sent_at > now() - interval '3 hours'
+            ORDER BY id DESC
+            LIMIT 1
+            ",
+            user_id,
+            track_uri
+        )
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(requester)
+    }
+
+    /// Queues a pending request.
+    pub async fn approve(
+        id: i32,
+        user_id: &str,
+        resolved_by: &str,
+        pool: &PgPool,
+    ) -> SqlResult<Option<Self>> {
+        // language=PostgreSQL
+        let request = sqlx::query_as!(
+            Self,
+            r#"
+            UPDATE song_requests
+            SET status = 'queued', resolved_by = $3
+            WHERE id = $1 AND user_id = $2 AND status = 'pending'
+            RETURNING id, user_id, reward_id, redemption_id, requested_by, track_uri, track_name, artists, duration_ms,
+                      status as "status: _", created_at, sent_at, resolved_by
+            "#,
+            id,
+            user_id,
+            resolved_by
+        )
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(request)
+    }
+
+    pub async fn reject(
+        id: i32,
+        user_id: &str,
+        resolved_by: &str,
+        pool: &PgPool,
+    ) -> SqlResult<Option<Self>> {
+        // language=PostgreSQL
+        let request = sqlx::query_as!(
+            Self,
+            r#"
+            UPDATE song_requests
+            SET status = 'rejected', resolved_by = $3
+            WHERE id = $1 AND user_id = $2 AND status = 'pending'
+            RETURNING id, user_id, reward_id, redemption_id, requested_by, track_uri, track_name, artists, duration_ms,
+                      status as "status: _", created_at, sent_at, resolved_by
+            "#,
+            id,
+            user_id,
+            resolved_by
+        )
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(request)
+    }
+
+    /// Removes an open request. Requests in Spotify's queue have to be
+    /// skipped, so they're marked as [SongRequestStatus::Skipping].
+    pub async fn remove(
+        id: i32,
+        user_id: &str,
+        resolved_by: &str,
+        pool: &PgPool,
+    ) -> SqlResult<Option<Self>> {
+        // language=PostgreSQL
+        let request = sqlx::query_as!(
+            Self,
+            r#"
+            UPDATE song_requests
+            SET status = CASE
+                    WHEN status = 'sent' THEN 'skipping'::song_request_status
+                    ELSE 'removed'::song_request_status
+                END,
+                resolved_by = $3
+            WHERE id = $1 AND user_id = $2 AND status IN ('pending', 'queued', 'sent')
+            RETURNING id, user_id, reward_id, redemption_id, requested_by, track_uri, track_name, artists, duration_ms,
+                      status as "status: _", created_at, sent_at, resolved_by
+            "#,
+            id,
+            user_id,
+            resolved_by
+        )
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(request)
+    }
+}
@@ -9,12 +9,16 @@ pub struct SpotifyData {
     pub refresh_token: String,
     pub only_while_live: bool,
     pub allow_commands: bool,
+    pub overlay_token: Option<String>,
 }
 
 #[derive(FromRow, Serialize, Deserialize)]
 pub struct SpotifySettings {
     pub only_while_live: bool,
     pub allow_commands: bool,
+    /// Secret of the now-playing overlay, it's changed through its own route
+    #[serde(default, skip_deserializing)]
+    pub overlay_token: Option<String>,
 }
 
 impl SpotifyData {
@@ -75,6 +79,39 @@ impl SpotifyData {
         Ok(())
     }
 
+    /// Sets the secret of the now-playing overlay, `None` disables it.
+    pub async fn set_overlay_token(
+        id: &str,
+        token: Option<&str>,
+        pool: &PgPool,
+    ) -> SqlResult<()> {
+        // language=PostgreSQL
+        sqlx::query!(
+            "UPDATE spotify SET overlay_token = $2 WHERE user_id = $1",
+            id,
+            token
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn get_id_by_overlay_token(
+        token: &str,
+        pool: &PgPool,
+    ) -> SqlResult<Option<String>> {
+        // language=PostgreSQL
+        let id = sqlx::query_scalar!(
+            "SELECT user_id FROM spotify WHERE overlay_token = $1",
+            token
+        )
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(id)
+    }
+
     pub async fn remove_for_id(id: &str, pool: &PgPool) -> SqlResult<()> {
         // language=PostgreSQL
         sqlx::query!("DELETE FROM spotify WHERE user_id = $1", id,)
@@ -90,7 +127,7 @@ impl SpotifySettings {
         // language=PostgreSQL
         let data = sqlx::query_as!(
             Self,
-            "SELECT only_while_live, allow_commands FROM spotify WHERE user_id=$1",
+            "SELECT only_while_live, allow_commands, overlay_token FROM spotify WHERE user_id=$1",
             id
         )
         .fetch_optional(pool)
@@ -39,6 +39,51 @@ impl SwapEmote {
         Ok(emote)
     }
 
+    /// Like [Self::oldest] but only considers emotes on `platform`.
+    pub async fn oldest_on_platform(
+        user_id: &str,
+        reward_id: &str,
+        platform: SlotPlatform,
+        pool: &PgPool,
+    ) -> SqlResult<Option<Self>> {
+        // language=PostgreSQL
+        let emote = sqlx::query_as!(
+            Self,
+            r#"
+            SELECT id, user_id, emote_id, platform as "platform: _", name, added_by, added_at, reward_id
+            FROM swap_emotes
+            WHERE user_id = $1 AND reward_id = $2 AND platform = $3
+            ORDER BY added_at
+            LIMIT 1"#,
+            user_id,
+            reward_id,
+            platform as _
+        )
+        .fetch_optional(pool)
+        .await?;
+        Ok(emote)
+    }
+
+    pub async fn newest(
+        user_id: &str,
+        pool: &PgPool,
+    ) -> SqlResult<Option<Self>> {
+        // language=PostgreSQL
+        let emote = sqlx::query_as!(
+            Self,
+            r#"
+            SELECT id, user_id, emote_id, platform as "platform: _", name, added_by, added_at, reward_id
+            FROM swap_emotes
+            WHERE user_id = $1
+            ORDER BY added_at DESC
+            LIMIT 1"#,
+            user_id
+        )
+        .fetch_optional(pool)
+        .await?;
+        Ok(emote)
+    }
+
     pub async fn add(
         user_id: &str,
         emote_id: &str,
@@ -102,6 +147,29 @@ impl SwapEmote {
         Ok(())
     }
 
+    /// Adds a removed emote again, keeping its position in the history.
+    pub async fn restore(&self, pool: &PgPool) -> SqlResult<()> {
+        // language=PostgreSQL
+        sqlx::query!(
+            r#"
+        INSERT INTO swap_emotes
+            (user_id, emote_id, platform, name, added_by, added_at, reward_id)
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        ON CONFLICT(user_id, platform, name) DO NOTHING
+        "#,
+            self.user_id,
+            self.emote_id,
+            self.platform as _,
+            self.name,
+            self.added_by,
+            self.added_at,
+            self.reward_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
     pub async fn remove(id: i64, pool: &PgPool) -> SqlResult<()> {
         // language=PostgreSQL
         sqlx::query!("DELETE FROM swap_emotes WHERE id = $1", id)
@@ -110,25 +178,28 @@ impl SwapEmote {
         Ok(())
     }
 
+    /// Returns the removed emote, if there was one.
     pub async fn remove_on_reward(
         id: i64,
         user_id: &str,
         reward_id: &str,
         pool: &PgPool,
-    ) -> SqlResult<()> {
+    ) -> SqlResult<Option<Self>> {
         // language=PostgreSQL
-        sqlx::query!(
+        let emote = sqlx::query_as!(
+            Self,
             r#"
             DELETE FROM swap_emotes 
             WHERE id = $1 AND user_id = $2 AND reward_id = $3
+            RETURNING id, user_id, emote_id, platform as "platform: _", name, added_by, added_at, reward_id
             "#,
             id,
             user_id,
             reward_id
         )
-        .execute(pool)
+        .fetch_optional(pool)
         .await?;
-        Ok(())
+        Ok(emote)
     }
 
     pub async fn emote_count(
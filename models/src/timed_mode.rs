@@ -25,6 +25,10 @@ pub enum Mode {
     Emoteonly,
     #[display("subscribers")]
     Subonly,
+    #[display("slow")]
+    Slow,
+    #[display("followers")]
+    Followers,
 }
 
 impl TimedMode {
@@ -1,6 +1,7 @@
+use crate::reward::ReplyTemplates;
 use config::CONFIG;
 use errors::sql::SqlResult;
-use sqlx::{FromRow, PgPool};
+use sqlx::{types::Json, FromRow, PgPool};
 use std::time::Duration;
 use twitch_api::{
     twitch_oauth2::{
@@ -45,7 +46,7 @@ impl User {
         // language=PostgreSQL
         let users = sqlx::query_as!(
             User,
-            "SELECT id, access_token, refresh_token, scopes, name FROM users"
+            "SELECT id, access_token, refresh_token, scopes, name FROM users WHERE NOT revoked"
         )
         .fetch_all(pool)
         .await?;
@@ -56,7 +57,7 @@ impl User {
     pub async fn get_all_names(pool: &PgPool) -> SqlResult<Vec<String>> {
         let names = sqlx::query_scalar!(
             // language=PostgreSQL
-            "SELECT name FROM users"
+            "SELECT name FROM users WHERE NOT revoked"
         )
         .fetch_all(pool)
         .await?;
@@ -74,7 +75,7 @@ impl User {
                 SELECT users.id FROM users
                 LEFT JOIN eventsubs e
                     ON users.id = e.user_id AND e.name = $1
-                WHERE e.user_id is null
+                WHERE e.user_id is null AND NOT users.revoked
             ",
             name
         )
@@ -116,6 +117,67 @@ impl User {
         Ok(data)
     }
 
+    pub async fn get_reply_templates(
+        user_id: &str,
+        pool: &PgPool,
+    ) -> SqlResult<ReplyTemplates> {
+        // language=PostgreSQL
+        let templates = sqlx::query_scalar!(
+            r#"SELECT reply_templates as "reply_templates: Json<ReplyTemplates>" FROM users WHERE id = $1"#,
+            user_id
+        )
+        .fetch_one(pool)
+        .await?;
+
+        Ok(templates.0)
+    }
+
+    pub async fn set_reply_templates(
+        user_id: &str,
+        templates: &ReplyTemplates,
+        pool: &PgPool,
+    ) -> SqlResult<()> {
+        // language=PostgreSQL
+        sqlx::query!(
+            "UPDATE users SET reply_templates = $2 WHERE id = $1",
+            user_id,
+            Json(templates) as _
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn get_locale(user_id: &str, pool: &PgPool) -> SqlResult<String> {
+        // language=PostgreSQL
+        let locale = sqlx::query_scalar!(
+            "SELECT locale FROM users WHERE id = $1",
+            user_id
+        )
+        .fetch_one(pool)
+        .await?;
+
+        Ok(locale)
+    }
+
+    pub async fn set_locale(
+        user_id: &str,
+        locale: &str,
+        pool: &PgPool,
+    ) -> SqlResult<()> {
+        // language=PostgreSQL
+        sqlx::query!(
+            "UPDATE users SET locale = $2 WHERE id = $1",
+            user_id,
+            locale
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
     pub async fn create(&self, pool: &PgPool) -> SqlResult<()> {
         let mut tx = pool.begin().await?;
         // language=PostgreSQL
@@ -125,7 +187,7 @@ impl User {
             INTO users (id, access_token, refresh_token, scopes, name)
             VALUES ($1, $2, $3, $4, $5)
             ON CONFLICT(id)
-                DO UPDATE SET access_token= $2, refresh_token=$3
+                DO UPDATE SET access_token= $2, refresh_token=$3, revoked=false
                 ",
             self.id,
             self.access_token,
@@ -195,6 +257,18 @@ impl User {
         Ok(())
     }
 
+    /// Revoked users keep their data but aren't refreshed or joined anymore.
+    /// Logging in again clears the flag.
+    pub async fn set_revoked(id: &str, pool: &PgPool) -> SqlResult<()> {
+        // language=PostgreSQL
+        let _ =
+            sqlx::query!("UPDATE users SET revoked = true WHERE id = $1", id)
+                .execute(pool)
+                .await?;
+
+        Ok(())
+    }
+
     pub async fn delete(id: &str, pool: &PgPool) -> SqlResult<()> {
         let mut tx = pool.begin().await?;
         // language=PostgreSQL
@@ -0,0 +1,12 @@
+use actix::Message;
+use models::pending_approval::PendingApproval;
+
+#[derive(Message)]
+#[rtype(result = "anyhow::Result<PendingApproval>")]
+pub struct ResolveApprovalMessage {
+    pub broadcaster_id: String,
+    pub id: i32,
+    pub approve: bool,
+    /// Login of the moderator or editor, used in logs
+    pub resolved_by: String,
+}
@@ -0,0 +1,88 @@
+mod messages;
+
+use crate::{
+    actors::rewards::RewardsActor, log_err, services::rewards::approval,
+};
+use actix::{
+    Actor, Addr, AsyncContext, Context, Handler, ResponseFuture, Supervised,
+    SystemService, WrapFuture,
+};
+use anyhow::Result as AnyResult;
+pub use messages::*;
+use models::pending_approval::PendingApproval;
+use sqlx::PgPool;
+use std::time::Duration;
+
+/// Resolves redemptions that need to be approved and denies them once
+/// they expire.
+pub struct ApprovalActor {
+    pool: PgPool,
+    executor: Addr<RewardsActor>,
+}
+
+impl ApprovalActor {
+    pub fn new(pool: PgPool, executor: Addr<RewardsActor>) -> Self {
+        Self { pool, executor }
+    }
+}
+
+impl Actor for ApprovalActor {
+    type Context = Context<Self>;
+
+    fn started(&mut self, ctx: &mut Self::Context) {
+        ctx.run_interval(Duration::from_secs(60), |this, ctx| {
+            let pool = this.pool.clone();
+            ctx.spawn(
+                async move {
+                    log_err!(
+                        approval::deny_expired(&pool).await,
+                        "Could not deny expired redemptions"
+                    );
+                }
+                .into_actor(this),
+            );
+        });
+    }
+}
+
+impl Handler<ResolveApprovalMessage> for ApprovalActor {
+    type Result = ResponseFuture<AnyResult<PendingApproval>>;
+
+    fn handle(
+        &mut self,
+        msg: ResolveApprovalMessage,
+        _ctx: &mut Self::Context,
+    ) -> Self::Result {
+        let pool = self.pool.clone();
+        let executor = self.executor.clone();
+        Box::pin(async move {
+            if msg.approve {
+                approval::approve(
+                    msg.id,
+                    &msg.broadcaster_id,
+                    &msg.resolved_by,
+                    &pool,
+                    &executor,
+                )
+                .await
+            } else {
+                approval::deny(
+                    msg.id,
+                    &msg.broadcaster_id,
+                    &msg.resolved_by,
+                    &pool,
+                )
+                .await
+            }
+        })
+    }
+}
+
+impl SystemService for ApprovalActor {}
+impl Supervised for ApprovalActor {}
+
+impl Default for ApprovalActor {
+    fn default() -> Self {
+        unreachable!();
+    }
+}
@@ -1,4 +1,5 @@
 use actix::Message;
+use models::command_settings::CommandOverride;
 use twitch_irc::message::PrivmsgMessage;
 
 use crate::chat::command::ChatCommand;
@@ -8,4 +9,9 @@ use crate::chat::command::ChatCommand;
 pub struct ExecuteCommandMessage {
     pub raw: PrivmsgMessage,
     pub executor: Box<dyn ChatCommand + Send>,
+    /// Identifies the command in cooldowns
+    pub command: String,
+    pub options: CommandOverride,
+    /// The locale of the channel
+    pub locale: String,
 }
@@ -1,10 +1,15 @@
 use crate::{
+    chat::permission::{describe_level, has_permission},
     log_err,
-    services::twitch::{self, requests::send_chat_message},
-    AppAccessToken, RedisConn, RedisPool,
+    services::{
+        rewards::error::render_error,
+        twitch::{self, requests::send_chat_message},
+    },
+    text, AppAccessToken, RedisConn, RedisPool,
 };
 use actix::{Actor, Context, ContextFutureSpawner, Handler, WrapFuture};
 use deadpool_redis::{redis, redis::AsyncCommands};
+use models::command_settings::CommandOverride;
 use sqlx::PgPool;
 use std::sync::Arc;
 use tokio::sync::RwLock;
@@ -58,29 +63,45 @@ impl Handler<ExecuteCommandMessage> for ChatActor {
     }
 }
 
+/// Used if the channel didn't configure a cooldown for the command.
+const DEFAULT_USER_COOLDOWN: u32 = 5;
+const DEFAULT_CHANNEL_COOLDOWN: u32 = 1;
+
 async fn check_update_cooldown(
     conn: &mut RedisConn,
     channel: &str,
     user: &str,
+    command: &str,
+    options: &CommandOverride,
 ) -> anyhow::Result<bool> {
-    let user_key = format!("rewards:cooldown:{}:{}", channel, user);
-    let channel_key = format!("rewards:channel-limit:{}", channel);
+    let user_key = format!("rewards:cooldown:{channel}:{command}:{user}");
+    let channel_key = format!("rewards:channel-limit:{channel}:{command}");
     let existing: i8 = conn.exists(&[&user_key, &channel_key]).await?;
     if existing != 0 {
         return Ok(false);
     }
 
-    redis::pipe()
-        .cmd("SETEX")
-        .arg(&user_key)
-        .arg(5)
-        .arg(1)
-        .cmd("SETEX")
-        .arg(&channel_key)
-        .arg(1)
-        .arg(1)
-        .query_async::<()>(conn)
-        .await?;
+    let mut pipe = redis::pipe();
+    let mut any_cooldown = false;
+    for (key, cooldown) in [
+        (
+            &user_key,
+            options.user_cooldown.unwrap_or(DEFAULT_USER_COOLDOWN),
+        ),
+        (
+            &channel_key,
+            options.channel_cooldown.unwrap_or(DEFAULT_CHANNEL_COOLDOWN),
+        ),
+    ] {
+        // SETEX doesn't accept a duration of zero
+        if cooldown > 0 {
+            pipe.cmd("SETEX").arg(key).arg(cooldown).arg(1);
+            any_cooldown = true;
+        }
+    }
+    if any_cooldown {
+        pipe.query_async::<()>(conn).await?;
+    }
     Ok(true)
 }
 
@@ -95,23 +116,37 @@ async fn try_handle_command(
         &mut conn,
         &msg.raw.channel_id,
         &msg.raw.sender.id,
+        &msg.command,
+        &msg.options,
     )
     .await?
     {
         // nothing went wrong we're just on cooldown
         return Ok(());
     }
-    if !msg
+
+    let required = msg.executor.permission();
+    let required = msg.options.permission.map_or(required, |p| p.max(required));
+    let sender = msg.raw.sender.login.clone();
+    let denied = if !has_permission(&msg.raw, required, &db).await {
+        Some(text!(
+            "chat.permission.level",
+            user = sender.as_str(),
+            level = describe_level(required)
+        ))
+    } else if !msg
         .executor
         .check_permission(&msg.raw, &db, &mut conn)
         .await
     {
+        Some(text!("chat.permission.denied", user = sender.as_str()))
+    } else {
+        None
+    };
+    if let Some(denied) = denied {
         send_chat_message(
             &msg.raw.channel_id,
-            &format!(
-                "@{}, ⛔ You don't have permission to run this command!",
-                msg.raw.sender.login
-            ),
+            &denied.render(&msg.locale),
             &twitch::get_token(),
         )
         .await?;
@@ -119,19 +154,24 @@ async fn try_handle_command(
     }
 
     let channel_id = msg.raw.channel_id.clone();
-    let sender = msg.raw.sender.login.clone();
+    let locale = msg.locale;
     match msg
         .executor
-        .execute(msg.raw, &db, redis, app_access_token)
+        .execute(msg.raw, &db, redis, app_access_token, &locale)
         .await
     {
         Ok(res) => {
             send_chat_message(&channel_id, &res, &twitch::get_token()).await
         }
         Err(e) => {
+            let error = text!(
+                "chat.error",
+                user = sender,
+                error = render_error(&e, &locale)
+            );
             send_chat_message(
                 &channel_id,
-                &format!("@{}, ⚠ {}", sender, e),
+                &error.render(&locale),
                 &twitch::get_token(),
             )
             .await
@@ -0,0 +1,22 @@
+use actix::Message;
+
+/// Twitch accepted the connection and assigned a session.
+#[derive(Message)]
+#[rtype(result = "()")]
+pub struct SessionWelcomeMessage {
+    pub connection: usize,
+    pub session_id: String,
+}
+
+/// Twitch asked us to connect to a different URL.
+#[derive(Message)]
+#[rtype(result = "()")]
+pub struct ReconnectMessage {
+    pub url: String,
+}
+
+#[derive(Message)]
+#[rtype(result = "()")]
+pub struct DisconnectedMessage {
+    pub connection: usize,
+}
@@ -0,0 +1,230 @@
+use actix::{
+    Actor, Addr, AsyncContext, Context, ContextFutureSpawner, Handler,
+    WrapFuture,
+};
+use anyhow::{bail, Result as AnyResult};
+use futures::StreamExt;
+use sqlx::PgPool;
+use std::{sync::Arc, time::Duration};
+use tokio::sync::RwLock;
+use tokio_tungstenite::{connect_async, tungstenite::Message as WsMessage};
+use twitch_api::{
+    eventsub::{Event, EventsubWebsocketData},
+    twitch_oauth2::AppAccessToken,
+};
+
+use crate::{
+    log_err,
+    services::eventsub::{
+        handle_event, register_eventsub_for_all, set_websocket_session,
+        EventCtx,
+    },
+};
+use config::CONFIG;
+
+mod messages;
+pub use messages::*;
+
+/// Time to wait for the welcome message (before the keepalive is known).
+const WELCOME_TIMEOUT: Duration = Duration::from_secs(15);
+/// Added to the keepalive timeout to account for latency.
+const KEEPALIVE_GRACE: Duration = Duration::from_secs(5);
+const RECONNECT_DELAY: Duration = Duration::from_secs(5);
+
+pub struct EventSubWsActor {
+    pool: PgPool,
+    app_access_token: Arc<RwLock<AppAccessToken>>,
+    event_ctx: Arc<EventCtx>,
+
+    /// Incremented on every new connection,
+    /// used to ignore messages from old connections.
+    connection: usize,
+    /// Subscriptions are carried over to the new session when Twitch
+    /// asks us to reconnect, so they don't need to be registered again.
+    reconnecting: bool,
+}
+
+impl EventSubWsActor {
+    pub fn new(
+        pool: PgPool,
+        app_access_token: Arc<RwLock<AppAccessToken>>,
+        event_ctx: EventCtx,
+    ) -> Self {
+        Self {
+            pool,
+            app_access_token,
+            event_ctx: Arc::new(event_ctx),
+            connection: 0,
+            reconnecting: false,
+        }
+    }
+
+    fn connect(&mut self, url: String, ctx: &mut Context<Self>) {
+        self.connection += 1;
+        let connection = self.connection;
+        let addr = ctx.address();
+        let event_ctx = self.event_ctx.clone();
+
+        log::info!("Connecting to EventSub websocket (#{connection})");
+        async move {
+            log_err!(
+                Self::run(connection, &url, &addr, &event_ctx).await,
+                "EventSub websocket disconnected"
+            );
+            addr.do_send(DisconnectedMessage { connection });
+        }
+        .into_actor(self)
+        .spawn(ctx);
+    }
+
+    async fn run(
+        connection: usize,
+        url: &str,
+        addr: &Addr<Self>,
+        event_ctx: &Arc<EventCtx>,
+    ) -> AnyResult<()> {
+        let (mut stream, _) = connect_async(url).await?;
+        let mut timeout = WELCOME_TIMEOUT;
+
+        loop {
+            let msg = match tokio::time::timeout(timeout, stream.next()).await {
+                Ok(Some(msg)) => msg?,
+                Ok(None) => bail!("Connection closed"),
+                Err(_) => bail!("No message received in {timeout:?}"),
+            };
+            let text = match msg {
+                WsMessage::Text(text) => text,
+                WsMessage::Close(frame) => bail!("Closed: {frame:?}"),
+                _ => continue,
+            };
+
+            match Event::parse_websocket(text.as_str())? {
+                EventsubWebsocketData::Welcome { payload, .. } => {
+                    if let Some(secs) =
+                        payload.session.keepalive_timeout_seconds
+                    {
+                        timeout = keepalive_timeout(secs);
+                    }
+                    addr.do_send(SessionWelcomeMessage {
+                        connection,
+                        session_id: payload.session.id.to_string(),
+                    });
+                }
+                EventsubWebsocketData::Keepalive { .. } => (),
+                EventsubWebsocketData::Notification { payload, .. } => {
+                    let event_ctx = event_ctx.clone();
+                    actix::spawn(async move {
+                        log_err!(
+                            handle_event(payload, &event_ctx).await,
+                            "Could not handle event"
+                        );
+                    });
+                }
+                EventsubWebsocketData::Reconnect { payload, .. } => {
+                    if let Some(url) = payload.session.reconnect_url {
+                        addr.do_send(ReconnectMessage {
+                            url: url.to_string(),
+                        });
+                    }
+                }
+                EventsubWebsocketData::Revocation { metadata, .. } => {
+                    log::warn!("EventSub revocation: {:?}", metadata);
+                }
+                _ => (),
+            }
+        }
+    }
+}
+
+/// How long to wait for the next message given the keepalive Twitch sent
+/// in the welcome message.
+fn keepalive_timeout(secs: i64) -> Duration {
+    Duration::from_secs(secs.max(0) as u64) + KEEPALIVE_GRACE
+}
+
+impl Actor for EventSubWsActor {
+    type Context = Context<Self>;
+
+    fn started(&mut self, ctx: &mut Self::Context) {
+        self.connect(CONFIG.urls.twitch_eventsub_ws.clone(), ctx);
+    }
+}
+
+impl Handler<SessionWelcomeMessage> for EventSubWsActor {
+    type Result = ();
+
+    fn handle(
+        &mut self,
+        msg: SessionWelcomeMessage,
+        ctx: &mut Self::Context,
+    ) -> Self::Result {
+        if msg.connection != self.connection {
+            return;
+        }
+        log::info!("EventSub session: {}", msg.session_id);
+        set_websocket_session(Some(msg.session_id));
+
+        if std::mem::take(&mut self.reconnecting) {
+            return;
+        }
+
+        let pool = self.pool.clone();
+        let token = self.app_access_token.clone();
+        async move {
+            log_err!(
+                register_eventsub_for_all(&token, &pool).await,
+                "Could not register eventsub"
+            );
+        }
+        .into_actor(self)
+        .spawn(ctx);
+    }
+}
+
+impl Handler<ReconnectMessage> for EventSubWsActor {
+    type Result = ();
+
+    fn handle(
+        &mut self,
+        msg: ReconnectMessage,
+        ctx: &mut Self::Context,
+    ) -> Self::Result {
+        // the old connection stays open until the new one is welcomed
+        self.reconnecting = true;
+        self.connect(msg.url, ctx);
+    }
+}
+
+impl Handler<DisconnectedMessage> for EventSubWsActor {
+    type Result = ();
+
+    fn handle(
+        &mut self,
+        msg: DisconnectedMessage,
+        ctx: &mut Self::Context,
+    ) -> Self::Result {
+        if msg.connection != self.connection {
+            return;
+        }
+        // all subscriptions of the session are gone now
+        set_websocket_session(None);
+        self.reconnecting = false;
+
+        ctx.run_later(RECONNECT_DELAY, |this, ctx| {
+            this.connect(CONFIG.urls.twitch_eventsub_ws.clone(), ctx);
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keepalive_timeout_has_grace() {
+        assert_eq!(keepalive_timeout(10), Duration::from_secs(15));
+        assert_eq!(keepalive_timeout(600), Duration::from_secs(605));
+        assert_eq!(keepalive_timeout(0), KEEPALIVE_GRACE);
+        assert_eq!(keepalive_timeout(-1), KEEPALIVE_GRACE);
+    }
+}
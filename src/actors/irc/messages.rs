@@ -16,3 +16,11 @@ pub struct PartMessage(pub String);
 #[derive(Message)]
 #[rtype(result = "()")]
 pub struct JoinAllMessage(pub Vec<String>);
+
+/// Updates the prefix of a channel. `None` resets it to the default prefix.
+#[derive(Message)]
+#[rtype(result = "()")]
+pub struct SetPrefixMessage {
+    pub channel_id: String,
+    pub prefix: Option<String>,
+}
@@ -1,9 +1,12 @@
 use std::{collections::HashMap, time::Duration};
 
 use actix::{
-    Actor, ActorFutureExt, Addr, AsyncContext, Context, ContextFutureSpawner,
-    Handler, Recipient, WrapFuture,
+    Actor, ActorFutureExt, Addr, AsyncContext, Context, Handler, Recipient,
+    WrapFuture,
 };
+use config::CONFIG;
+use models::command_settings::CommandSettings;
+use sqlx::PgPool;
 use tokio::sync::mpsc::UnboundedReceiver;
 use twitch_irc::{
     login::StaticLoginCredentials,
@@ -13,11 +16,21 @@ use twitch_irc::{
 
 use crate::{
     actors::timeout::TimeoutActor,
-    chat::{parse::opt_next_space, try_parse_command},
+    chat::{
+        parse::opt_next_space,
+        settings::{
+            get_command_settings, get_custom_commands, get_locale, strip_prefix,
+        },
+        try_parse_command, ParsedCommand,
+    },
     log_err,
-    services::twitch::{self, requests::send_chat_message},
+    services::{
+        i18n::DEFAULT_LOCALE,
+        rewards::error::render_error,
+        twitch::{self, requests::send_chat_message},
+    },
+    text, RedisPool,
 };
-use config::CONFIG;
 
 mod messages;
 use crate::actors::{
@@ -36,13 +49,22 @@ pub struct IrcActor {
 
     last_messages: HashMap<String, String>,
 
+    /// Custom prefixes by channel id, so messages that can't be commands
+    /// don't have to hit redis.
+    prefixes: HashMap<String, String>,
+
     timeout_handler: Addr<TimeoutActor>,
+
+    pool: PgPool,
+    redis: RedisPool,
 }
 
 impl IrcActor {
     pub fn new(
         executor: Recipient<ExecuteCommandMessage>,
         timeout_handler: Addr<TimeoutActor>,
+        pool: PgPool,
+        redis: RedisPool,
     ) -> Self {
         let config = ClientConfig {
             metrics_config: MetricsConfig::default(),
@@ -63,8 +85,12 @@ impl IrcActor {
 
             executor,
             last_messages: HashMap::new(),
+            prefixes: HashMap::new(),
 
             timeout_handler,
+
+            pool,
+            redis,
         }
     }
 }
@@ -108,6 +134,39 @@ impl Actor for IrcActor {
             }
             .into_actor(self),
         );
+
+        let pool = self.pool.clone();
+        ctx.spawn(
+            async move { CommandSettings::all_prefixes(&pool).await }
+                .into_actor(self)
+                .map(|res, this, _ctx| match res {
+                    Ok(prefixes) => this.prefixes.extend(prefixes),
+                    Err(e) => log::warn!("Could not load prefixes: {e}"),
+                }),
+        );
+    }
+}
+
+impl IrcActor {
+    fn prefix(&self, channel_id: &str) -> &str {
+        self.prefixes
+            .get(channel_id)
+            .map_or(CONFIG.bot.prefix.as_str(), String::as_str)
+    }
+}
+
+impl Handler<SetPrefixMessage> for IrcActor {
+    type Result = ();
+
+    fn handle(
+        &mut self,
+        msg: SetPrefixMessage,
+        _ctx: &mut Self::Context,
+    ) -> Self::Result {
+        match msg.prefix {
+            Some(prefix) => self.prefixes.insert(msg.channel_id, prefix),
+            None => self.prefixes.remove(&msg.channel_id),
+        };
     }
 }
 
@@ -158,44 +217,82 @@ impl Handler<ChatMessage> for IrcActor {
         msg: ChatMessage,
         ctx: &mut Self::Context,
     ) -> Self::Result {
-        if !msg.0.message_text.starts_with(&CONFIG.bot.prefix)
-            || msg.0.message_text.len() < CONFIG.bot.prefix.len()
+        if !msg
+            .0
+            .message_text
+            .starts_with(self.prefix(&msg.0.channel_id))
         {
             return;
         }
-        let (command, args) = opt_next_space(
-            msg.0.message_text[CONFIG.bot.prefix.len()..].trim(),
-        );
-        match try_parse_command(command, args) {
-            Some(Ok(ex)) => {
-                self.executor
-                    .send(ExecuteCommandMessage {
-                        executor: ex,
-                        raw: msg.0,
-                    })
-                    .into_actor(self)
-                    .map(|res, _, _| {
-                        log_err!(res, "Could not deliver");
-                    })
-                    .spawn(ctx);
-            }
-            Some(Err(e)) => {
-                ctx.spawn(
-                    async move {
-                        log_err!(
-                            send_chat_message(
-                                &msg.0.channel_id,
-                                &format!("@{}, ⚠ {}", msg.0.sender.login, e),
-                                &twitch::get_token(),
-                            )
-                            .await,
-                            "Failed to send chat"
-                        );
-                    }
-                    .into_actor(self),
+
+        let pool = self.pool.clone();
+        let redis = self.redis.clone();
+        let executor = self.executor.clone();
+        ctx.spawn(
+            async move {
+                log_err!(
+                    try_dispatch_command(msg, pool, redis, executor).await,
+                    "Could not dispatch command"
                 );
             }
-            None => (),
+            .into_actor(self),
+        );
+    }
+}
+
+async fn try_dispatch_command(
+    msg: ChatMessage,
+    pool: PgPool,
+    redis: RedisPool,
+    executor: Recipient<ExecuteCommandMessage>,
+) -> anyhow::Result<()> {
+    let mut conn = redis.get().await?;
+    let settings =
+        get_command_settings(&msg.0.channel_id, &pool, &mut conn).await?;
+    let Some(text) = strip_prefix(&msg.0.message_text, &settings) else {
+        return Ok(());
+    };
+    let (command, args) = opt_next_space(text.trim());
+
+    let custom =
+        get_custom_commands(&msg.0.channel_id, &pool, &mut conn).await?;
+    let locale = get_locale(&msg.0.channel_id, &pool, &mut conn)
+        .await
+        .unwrap_or_else(|_| DEFAULT_LOCALE.to_string());
+
+    match try_parse_command(command, args, &settings, &custom) {
+        Some(ParsedCommand {
+            name,
+            options,
+            executor: Ok(ex),
+        }) => {
+            executor
+                .send(ExecuteCommandMessage {
+                    executor: ex,
+                    raw: msg.0,
+                    command: name,
+                    options,
+                    locale,
+                })
+                .await?;
         }
+        Some(ParsedCommand {
+            executor: Err(e), ..
+        }) => {
+            let error = text!(
+                "chat.error",
+                user = msg.0.sender.login.as_str(),
+                error = render_error(&e, &locale)
+            );
+            send_chat_message(
+                &msg.0.channel_id,
+                &error.render(&locale),
+                &twitch::get_token(),
+            )
+            .await?;
+        }
+        None => (),
     }
+
+    Ok(())
 }
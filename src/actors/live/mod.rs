@@ -13,7 +13,7 @@ use twitch_api::{
 use crate::{
     log_discord, log_err,
     services::twitch::{
-        self,
+        self, live,
         requests::{
             get_reward_for_broadcaster_by_id, send_chat_message, update_reward,
         },
@@ -203,6 +203,7 @@ impl Handler<LiveMessage> for LiveActor {
         msg: LiveMessage,
         ctx: &mut Self::Context,
     ) -> Self::Result {
+        live::set_live(&msg.0, true);
         let pool = self.pool.clone();
         async move { Self::on_live(&msg.0, &pool).await }
             .into_actor(self)
@@ -255,6 +256,7 @@ impl Handler<OfflineMessage> for LiveActor {
         msg: OfflineMessage,
         ctx: &mut Self::Context,
     ) -> Self::Result {
+        live::set_live(&msg.0, false);
         let pool = self.pool.clone();
         ctx.spawn(
             async move {
@@ -1,10 +1,15 @@
+pub mod approval;
 pub mod chat;
 pub mod db;
 pub mod discord;
+pub mod eventsub_ws;
 pub mod irc;
 pub mod live;
+pub mod now_playing;
 pub mod rewards;
 pub mod slot;
+pub mod song_queue;
 pub mod supinic;
+pub mod timed_mode;
 pub mod timeout;
 pub mod token_refresher;
@@ -0,0 +1,17 @@
+use crate::services::spotify::overlay::OverlayState;
+use actix::Message;
+use tokio::sync::broadcast::Receiver;
+
+/// Subscribes to the now-playing state of a channel.
+#[derive(Message)]
+#[rtype(result = "NowPlayingSubscription")]
+pub struct SubscribeNowPlaying {
+    pub broadcaster_id: String,
+}
+
+pub struct NowPlayingSubscription {
+    /// `None` if the player wasn't requested yet
+    pub current: Option<OverlayState>,
+    /// Receives the state whenever it changes
+    pub receiver: Receiver<OverlayState>,
+}
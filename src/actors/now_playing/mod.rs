@@ -0,0 +1,128 @@
+mod messages;
+
+use crate::services::spotify::overlay::{self, OverlayState};
+use actix::{
+    Actor, ActorFutureExt, AsyncContext, Context, ContextFutureSpawner,
+    Handler, MessageResult, SpawnHandle, Supervised, SystemService, WrapFuture,
+};
+use config::CONFIG;
+pub use messages::*;
+use sqlx::PgPool;
+use std::{
+    collections::{hash_map::Entry, HashMap},
+    time::Duration,
+};
+use tokio::sync::broadcast::{self, Sender};
+
+struct Channel {
+    sender: Sender<OverlayState>,
+    /// `None` until the player was requested
+    current: Option<OverlayState>,
+    poller: SpawnHandle,
+}
+
+/// Polls the Spotify player of channels with an open overlay.
+/// A channel isn't polled anymore once all its subscribers are gone.
+pub struct NowPlayingActor {
+    pool: PgPool,
+    channels: HashMap<String, Channel>,
+}
+
+impl NowPlayingActor {
+    pub fn new(pool: PgPool) -> Self {
+        Self {
+            pool,
+            channels: HashMap::new(),
+        }
+    }
+
+    fn poll(&mut self, broadcaster_id: String, ctx: &mut Context<Self>) {
+        let Some(channel) = self.channels.get(&broadcaster_id) else {
+            return;
+        };
+        if channel.sender.receiver_count() == 0 {
+            if let Some(channel) = self.channels.remove(&broadcaster_id) {
+                ctx.cancel_future(channel.poller);
+            }
+            return;
+        }
+
+        let previous = channel.current.clone();
+        let pool = self.pool.clone();
+        async move {
+            let state =
+                overlay::get_state(&broadcaster_id, previous.as_ref(), &pool)
+                    .await;
+            (broadcaster_id, state)
+        }
+        .into_actor(self)
+        .map(|(broadcaster_id, state), this, _ctx| {
+            let state = match state {
+                Ok(state) => state,
+                Err(e) => {
+                    log::warn!("Could not get now playing: {}", e);
+                    return;
+                }
+            };
+            let Some(channel) = this.channels.get_mut(&broadcaster_id) else {
+                return;
+            };
+            if channel.current.as_ref() != Some(&state) {
+                // fails if everyone unsubscribed in the meantime
+                channel.sender.send(state.clone()).ok();
+                channel.current = Some(state);
+            }
+        })
+        .spawn(ctx);
+    }
+}
+
+impl Actor for NowPlayingActor {
+    type Context = Context<Self>;
+}
+
+impl Handler<SubscribeNowPlaying> for NowPlayingActor {
+    type Result = MessageResult<SubscribeNowPlaying>;
+
+    fn handle(
+        &mut self,
+        msg: SubscribeNowPlaying,
+        ctx: &mut Self::Context,
+    ) -> Self::Result {
+        let (channel, is_new) =
+            match self.channels.entry(msg.broadcaster_id.clone()) {
+                Entry::Occupied(entry) => (entry.into_mut(), false),
+                Entry::Vacant(entry) => {
+                    let broadcaster_id = msg.broadcaster_id.clone();
+                    let poller = ctx.run_interval(
+                        Duration::from_secs(CONFIG.spotify.overlay_interval),
+                        move |this, ctx| this.poll(broadcaster_id.clone(), ctx),
+                    );
+                    let channel = Channel {
+                        sender: broadcast::channel(8).0,
+                        current: None,
+                        poller,
+                    };
+                    (entry.insert(channel), true)
+                }
+            };
+        let subscription = NowPlayingSubscription {
+            current: channel.current.clone(),
+            receiver: channel.sender.subscribe(),
+        };
+        if is_new {
+            self.poll(msg.broadcaster_id, ctx);
+        }
+
+        MessageResult(subscription)
+    }
+}
+
+impl SystemService for NowPlayingActor {}
+impl Supervised for NowPlayingActor {}
+
+impl Default for NowPlayingActor {
+    fn default() -> Self {
+        unreachable!();
+    }
+}
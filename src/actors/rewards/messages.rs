@@ -1,3 +1,4 @@
+use crate::services::rewards::reply::RedemptionReply;
 use actix::Message;
 use models::{reward::Reward, user::User};
 use twitch_api::eventsub::channel::ChannelPointsCustomRewardRedemptionAddV1Payload;
@@ -9,5 +10,5 @@ pub struct ExecuteRewardMessage {
 }
 
 impl Message for ExecuteRewardMessage {
-    type Result = anyhow::Result<()>;
+    type Result = anyhow::Result<Option<RedemptionReply>>;
 }
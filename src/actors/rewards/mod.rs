@@ -1,9 +1,12 @@
 mod messages;
 use crate::{
-    actors::{discord::DiscordActor, timeout::TimeoutActor},
+    actors::{
+        discord::DiscordActor, timed_mode::TimedModeActor,
+        timeout::TimeoutActor,
+    },
     services::{
         emotes::{bttv::BttvEmotes, ffz::FfzEmotes, seven_tv::SevenTvEmotes},
-        rewards::{execute, extract},
+        rewards::{execute, extract, reply::RedemptionReply},
     },
     RedisPool,
 };
@@ -22,6 +25,7 @@ pub struct RewardsActor {
     pub redis: RedisPool,
     pub app_access_token: Arc<RwLock<AppAccessToken>>,
     pub timeout: Addr<TimeoutActor>,
+    pub timed_mode: Addr<TimedModeActor>,
     pub discord: Addr<DiscordActor>,
 }
 
@@ -32,7 +36,7 @@ impl Actor for RewardsActor {
 }
 
 impl Handler<ExecuteRewardMessage> for RewardsActor {
-    type Result = ResponseFuture<AnyResult<()>>;
+    type Result = ResponseFuture<AnyResult<Option<RedemptionReply>>>;
 
     fn handle(
         &mut self,
@@ -50,15 +54,37 @@ impl Handler<ExecuteRewardMessage> for RewardsActor {
             RewardData::SubOnly(duration) => execute::timed_mode(
                 timed_mode::Mode::Subonly,
                 duration,
+                None,
                 msg.broadcaster,
                 msg.redemption,
+                self.timed_mode.clone(),
             )
             .boxed(),
             RewardData::EmoteOnly(duration) => execute::timed_mode(
                 timed_mode::Mode::Emoteonly,
                 duration,
+                None,
+                msg.broadcaster,
+                msg.redemption,
+                self.timed_mode.clone(),
+            )
+            .boxed(),
+            RewardData::SlowMode(data) => execute::timed_mode(
+                timed_mode::Mode::Slow,
+                data.duration,
+                Some(data.wait_time),
+                msg.broadcaster,
+                msg.redemption,
+                self.timed_mode.clone(),
+            )
+            .boxed(),
+            RewardData::FollowerOnly(data) => execute::timed_mode(
+                timed_mode::Mode::Followers,
+                data.duration,
+                Some(data.follow_age),
                 msg.broadcaster,
                 msg.redemption,
+                self.timed_mode.clone(),
             )
             .boxed(),
             RewardData::BttvSwap(data) => execute::swap::<BttvEmotes>(
@@ -82,6 +108,12 @@ impl Handler<ExecuteRewardMessage> for RewardsActor {
                 (self.db.clone(), self.redis.clone(), self.discord.clone()),
             )
             .boxed(),
+            RewardData::AnySwap(data) => execute::any_swap(
+                msg.redemption,
+                data,
+                (self.db.clone(), self.redis.clone(), self.discord.clone()),
+            )
+            .boxed(),
             RewardData::BttvSlot(slot) => execute::slot::<BttvEmotes>(
                 extract::bttv,
                 msg.redemption,
@@ -114,6 +146,13 @@ impl Handler<ExecuteRewardMessage> for RewardsActor {
                 execute::spotify_play(opts, msg.redemption, self.db.clone())
                     .boxed()
             }
+            RewardData::Poll(data) => {
+                execute::poll(data, msg.redemption, msg.broadcaster).boxed()
+            }
+            RewardData::Prediction(data) => {
+                execute::prediction(data, msg.redemption, msg.broadcaster)
+                    .boxed()
+            }
             RewardData::RemEmote(opts) => {
                 let args =
                     (self.db.clone(), self.redis.clone(), self.discord.clone());
@@ -5,7 +5,8 @@ use crate::{
     embed_builder, log_discord, log_err, send_discord,
     services::{
         emotes::{
-            bttv::BttvEmotes, ffz::FfzEmotes, seven_tv::SevenTvEmotes, EmoteRW,
+            bttv::BttvEmotes, events, ffz::FfzEmotes, seven_tv::SevenTvEmotes,
+            EmoteRW,
         },
         twitch::requests::update_reward,
     },
@@ -19,8 +20,13 @@ use anyhow::Result as AnyResult;
 use deadpool_redis::redis::AsyncCommands;
 pub use messages::*;
 use models::{
-    emote::SlotPlatform, log_entry::LogEntry, slot::Slot, user::User,
+    emote::SlotPlatform,
+    emote_event::EmoteEventReason,
+    log_entry::{LogEntry, LogEntryType},
+    slot::Slot,
+    user::User,
 };
+use serde_json::json;
 use sqlx::PgPool;
 use std::time::Duration;
 use twitch_api::{
@@ -130,17 +136,29 @@ impl SlotActor {
                 "Could not clear pending slot {:?} error={}",
                 p
             );
+            if let Some(event) =
+                events::slot_removal(&p, EmoteEventReason::Expired)
+            {
+                events::record(event, &pool).await;
+            }
             match emote {
                 Ok(emote) => {
                     log_err!(
-                        LogEntry::create(
+                        LogEntry::new(
                             &p.user_id,
-                            &format!(
-                                "[slots::{:?}] Deleted {}",
-                                p.platform, emote
-                            ),
-                            &pool
+                            LogEntryType::EmoteRemoved,
+                            format!("Removed {emote} ({:?})", p.platform),
                         )
+                        .target(&emote)
+                        .reward(&p.reward_id)
+                        .payload(json!({
+                            "kind": "slot",
+                            "platform": p.platform,
+                            "emote_id": emote_id,
+                            "slot_id": p.id,
+                            "added_by": p.added_by,
+                        }))
+                        .insert(&pool)
                         .await,
                         "Could not save logs"
                     );
@@ -170,11 +188,24 @@ impl SlotActor {
                         e
                     );
                     log_err!(
-                        LogEntry::create(
+                        LogEntry::new(
                             &p.user_id,
-                            &format!("[slots::{:?}] Deleted {} - no emote data available or could not delete on platform", p.platform, emote_id),
-                            &pool
+                            LogEntryType::EmoteRemoved,
+                            format!(
+                                "Removed {emote_id} ({:?}) - no emote data available or could not delete on platform",
+                                p.platform
+                            ),
                         )
+                        .reward(&p.reward_id)
+                        .payload(json!({
+                            "kind": "slot",
+                            "platform": p.platform,
+                            "emote_id": emote_id,
+                            "slot_id": p.id,
+                            "added_by": p.added_by,
+                            "error": e.to_string(),
+                        }))
+                        .insert(&pool)
                         .await,
                         "Could not save logs"
                     );
@@ -0,0 +1,9 @@
+use actix::Message;
+
+/// Feeds the channel's song queue right away instead of waiting for the next
+/// interval.
+#[derive(Message)]
+#[rtype(result = "()")]
+pub struct FeedQueueMessage {
+    pub broadcaster_id: String,
+}
@@ -0,0 +1,81 @@
+mod messages;
+
+use crate::{log_err, services::spotify::queue};
+use actix::{
+    Actor, AsyncContext, Context, Handler, Supervised, SystemService,
+    WrapFuture,
+};
+pub use messages::*;
+use models::song_request::SongRequest;
+use sqlx::PgPool;
+use std::time::Duration;
+
+/// Sends song requests to Spotify in order.
+pub struct SongQueueActor {
+    pool: PgPool,
+}
+
+impl SongQueueActor {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+impl Actor for SongQueueActor {
+    type Context = Context<Self>;
+
+    fn started(&mut self, ctx: &mut Self::Context) {
+        ctx.run_interval(Duration::from_secs(15), |this, ctx| {
+            let pool = this.pool.clone();
+            ctx.spawn(
+                async move {
+                    let channels =
+                        match SongRequest::get_channels_to_feed(&pool).await {
+                            Ok(channels) => channels,
+                            Err(e) => {
+                                log::warn!("Could not get song queues: {}", e);
+                                return;
+                            }
+                        };
+                    for channel in channels {
+                        log_err!(
+                            queue::feed(&channel, &pool).await,
+                            "Could not feed song queue"
+                        );
+                    }
+                }
+                .into_actor(this),
+            );
+        });
+    }
+}
+
+impl Handler<FeedQueueMessage> for SongQueueActor {
+    type Result = ();
+
+    fn handle(
+        &mut self,
+        msg: FeedQueueMessage,
+        ctx: &mut Self::Context,
+    ) -> Self::Result {
+        let pool = self.pool.clone();
+        ctx.spawn(
+            async move {
+                log_err!(
+                    queue::feed(&msg.broadcaster_id, &pool).await,
+                    "Could not feed song queue"
+                );
+            }
+            .into_actor(self),
+        );
+    }
+}
+
+impl SystemService for SongQueueActor {}
+impl Supervised for SongQueueActor {}
+
+impl Default for SongQueueActor {
+    fn default() -> Self {
+        unreachable!();
+    }
+}
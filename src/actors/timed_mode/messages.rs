@@ -0,0 +1,11 @@
+use actix::Message;
+use models::timed_mode::Mode;
+use std::time::Duration;
+
+#[derive(Message)]
+#[rtype(result = "()")]
+pub struct TimedModeMessage {
+    pub broadcaster_id: String,
+    pub mode: Mode,
+    pub duration: Duration,
+}
@@ -0,0 +1,183 @@
+use actix::{
+    Actor, ActorFutureExt, AsyncContext, Context, ContextFutureSpawner,
+    Handler, WrapFuture,
+};
+use anyhow::Result as AnyResult;
+use chrono::Utc;
+use sqlx::PgPool;
+use std::time::Duration;
+
+use crate::services::twitch::{self, requests::update_chat_settings};
+use config::CONFIG;
+use models::timed_mode::{Mode, TimedMode};
+
+mod messages;
+pub use messages::*;
+
+/// Delay before a failed revert is retried, doubled for every attempt.
+const RETRY_DELAY: Duration = Duration::from_secs(30);
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(60 * 60);
+
+fn retry_delay(attempt: u32) -> Duration {
+    RETRY_DELAY
+        .saturating_mul(2u32.saturating_pow(attempt))
+        .min(MAX_RETRY_DELAY)
+}
+
+pub struct TimedModeActor {
+    pool: PgPool,
+}
+
+impl TimedModeActor {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Disables `mode` in the broadcaster's chat and removes the entry.
+    async fn revert(
+        id: Option<i32>,
+        broadcaster_id: &str,
+        mode: Mode,
+        pool: &PgPool,
+    ) -> AnyResult<()> {
+        log::info!("Reverting {mode:?} in {broadcaster_id}");
+        update_chat_settings(
+            broadcaster_id,
+            CONFIG.twitch.user_id.clone(),
+            mode,
+            false,
+            None,
+            &twitch::get_token(),
+        )
+        .await?;
+        if let Some(id) = id {
+            TimedMode::delete_mode(id, pool).await?;
+        }
+        Ok(())
+    }
+
+    /// Reverts the mode after `run_in`. Failed reverts are retried with a
+    /// backoff, otherwise the mode would stay until the next restart.
+    fn schedule_revert(
+        &self,
+        id: Option<i32>,
+        broadcaster_id: String,
+        mode: Mode,
+        run_in: Duration,
+        attempt: u32,
+        ctx: &mut Context<Self>,
+    ) {
+        ctx.run_later(run_in, move |this, ctx| {
+            let pool = this.pool.clone();
+            let target = broadcaster_id.clone();
+            async move { Self::revert(id, &target, mode, &pool).await }
+                .into_actor(this)
+                .map(move |res, this, ctx| {
+                    if let Err(e) = res {
+                        let delay = retry_delay(attempt);
+                        log::warn!(
+                            "Could not revert timed mode, retrying in {}s: {}",
+                            delay.as_secs(),
+                            e
+                        );
+                        this.schedule_revert(
+                            id,
+                            broadcaster_id,
+                            mode,
+                            delay,
+                            attempt + 1,
+                            ctx,
+                        );
+                    }
+                })
+                .spawn(ctx);
+        });
+    }
+}
+
+impl Actor for TimedModeActor {
+    type Context = Context<Self>;
+
+    fn started(&mut self, ctx: &mut Self::Context) {
+        let pool = self.pool.clone();
+        async move { TimedMode::get_all(&pool).await }
+            .into_actor(self)
+            .map(|res, this, ctx| match res {
+                Ok(modes) => {
+                    log::info!("Restoring {} timed modes", modes.len());
+                    let now = Utc::now();
+                    for mode in modes {
+                        // overdue modes are reverted immediately
+                        let run_in =
+                            (mode.end_ts - now).to_std().unwrap_or_default();
+                        this.schedule_revert(
+                            Some(mode.id),
+                            mode.user_id,
+                            mode.mode,
+                            run_in,
+                            0,
+                            ctx,
+                        );
+                    }
+                }
+                Err(e) => log::warn!("Could not get timed modes: {}", e),
+            })
+            .spawn(ctx);
+    }
+}
+
+impl Handler<TimedModeMessage> for TimedModeActor {
+    type Result = ();
+
+    fn handle(
+        &mut self,
+        msg: TimedModeMessage,
+        ctx: &mut Self::Context,
+    ) -> Self::Result {
+        let pool = self.pool.clone();
+        let broadcaster_id = msg.broadcaster_id.clone();
+        async move {
+            TimedMode::create_mode(
+                &broadcaster_id,
+                msg.mode,
+                msg.duration,
+                &pool,
+            )
+            .await
+        }
+        .into_actor(self)
+        .map(move |res, this, ctx| {
+            // even if the mode couldn't be saved, it should still be reverted
+            let id = match res {
+                Ok(id) => Some(id),
+                Err(e) => {
+                    log::warn!("Could not save timed mode: {}", e);
+                    None
+                }
+            };
+            this.schedule_revert(
+                id,
+                msg.broadcaster_id,
+                msg.mode,
+                msg.duration,
+                0,
+                ctx,
+            );
+        })
+        .spawn(ctx);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn retry_delay_backs_off() {
+        assert_eq!(retry_delay(0), Duration::from_secs(30));
+        assert_eq!(retry_delay(1), Duration::from_secs(60));
+        assert_eq!(retry_delay(3), Duration::from_secs(240));
+        assert_eq!(retry_delay(7), MAX_RETRY_DELAY);
+        assert_eq!(retry_delay(u32::MAX), MAX_RETRY_DELAY);
+    }
+}
@@ -1,6 +1,7 @@
 use crate::{AppAccessToken, RedisConn, RedisPool};
 use anyhow::Result as AnyResult;
 use async_trait::async_trait;
+use models::command_settings::PermissionLevel;
 use sqlx::PgPool;
 use std::sync::Arc;
 use tokio::sync::RwLock;
@@ -14,6 +15,7 @@ pub trait ChatCommand: Send {
         pool: &PgPool,
         redis: RedisPool,
         app_access_token: Arc<RwLock<AppAccessToken>>,
+        locale: &str,
     ) -> AnyResult<String>;
     fn parse(
         cmd: &str,
@@ -22,6 +24,12 @@ pub trait ChatCommand: Send {
     where
         Self: Sized + Send;
 
+    /// The level required to run this command unless the channel requires a higher one.
+    fn permission(&self) -> PermissionLevel {
+        PermissionLevel::Everyone
+    }
+
+    /// Additional checks that can't be expressed with a [PermissionLevel].
     async fn check_permission(
         &mut self,
         _msg: &PrivmsgMessage,
@@ -1,4 +1,4 @@
-use crate::{chat::command::ChatCommand, AppAccessToken, RedisPool};
+use crate::{chat::command::ChatCommand, text, AppAccessToken, RedisPool};
 use anyhow::Result as AnyResult;
 use async_trait::async_trait;
 use sqlx::PgPool;
@@ -16,12 +16,15 @@ impl ChatCommand for About {
         _pool: &PgPool,
         _: RedisPool,
         _: Arc<RwLock<AppAccessToken>>,
+        locale: &str,
     ) -> AnyResult<String> {
-        Ok(format!("@{}, I'm a bot made for rewards.nerixyz.de by @nerixyz in Rust {rustc_info} ({build_profile}) 📝 github.com/Nerixyz/rewards",
-                   msg.sender.login,
-                   rustc_info = env!("RW_RUSTC_INFO"),
-                   build_profile = env!("RW_BUILD_PROFILE")
-        ))
+        Ok(text!(
+            "chat.about",
+            user = msg.sender.login,
+            rustc = env!("RW_RUSTC_INFO"),
+            profile = env!("RW_BUILD_PROFILE")
+        )
+        .render(locale))
     }
 
     fn parse(
@@ -0,0 +1,87 @@
+use crate::{
+    chat::command::ChatCommand,
+    services::{
+        rewards::error::render_error, spotify::rewards::format_current_track,
+        template,
+    },
+    AppAccessToken, RedisPool,
+};
+use anyhow::{anyhow, Result as AnyResult};
+use async_trait::async_trait;
+use models::{slot::Slot, swap_emote::SwapEmote};
+use sqlx::PgPool;
+use std::{collections::HashMap, sync::Arc};
+use tokio::sync::RwLock;
+use twitch_irc::message::PrivmsgMessage;
+
+/// The variables available in the response of custom commands
+pub const CUSTOM_COMMAND_VARIABLES: &[&str] = &[
+    "sender",
+    "args",
+    "channel",
+    "slots.free",
+    "spotify.current",
+    "swap.last",
+];
+
+/// A command defined by the broadcaster that responds with a template
+pub struct CustomTextCommand {
+    pub response: String,
+    pub args: Option<String>,
+}
+
+#[async_trait]
+impl ChatCommand for CustomTextCommand {
+    async fn execute(
+        &mut self,
+        msg: PrivmsgMessage,
+        pool: &PgPool,
+        _: RedisPool,
+        _: Arc<RwLock<AppAccessToken>>,
+        locale: &str,
+    ) -> AnyResult<String> {
+        let vars = template::variables(&self.response).collect::<Vec<_>>();
+        let mut values = HashMap::new();
+        for var in vars {
+            if values.contains_key(var) {
+                continue;
+            }
+            let value = match var {
+                "sender" => msg.sender.login.clone(),
+                "args" => self.args.clone().unwrap_or_default(),
+                "channel" => msg.channel_login.clone(),
+                "slots.free" => Slot::get_occupation(&msg.channel_id, pool)
+                    .await?
+                    .available
+                    .unwrap_or(0)
+                    .to_string(),
+                "spotify.current" => {
+                    match format_current_track(&msg.channel_id, pool).await {
+                        Ok(track) => track.render(locale),
+                        Err(e) => render_error(&e, locale),
+                    }
+                }
+                "swap.last" => SwapEmote::newest(&msg.channel_id, pool)
+                    .await?
+                    .map(|e| e.name)
+                    .unwrap_or_else(|| "-".to_string()),
+                _ => continue,
+            };
+            values.insert(var, value);
+        }
+
+        Ok(template::render(&self.response, |var| {
+            values.get(var).cloned()
+        }))
+    }
+
+    fn parse(
+        _cmd: &str,
+        _args: Option<&str>,
+    ) -> AnyResult<Box<dyn ChatCommand + Send>>
+    where
+        Self: Sized + Send,
+    {
+        Err(anyhow!("Custom commands are created from their definition"))
+    }
+}
@@ -0,0 +1,59 @@
+use crate::{
+    chat::commands::emote_management::extract::extract_emote_by_url,
+    services::emotes::ban_rules::get_emote_properties, PgPool,
+};
+use anyhow::{anyhow, Result as AnyResult};
+use models::blocklist::{BlocklistEntry, BlocklistKind};
+
+/// Blocks the emote behind the url or its owner on all channels.
+pub async fn block(
+    kind: BlocklistKind,
+    emote_url: &str,
+    reason: Option<&str>,
+    pool: &PgPool,
+) -> AnyResult<BlocklistEntry> {
+    let (emote_id, platform) = extract_emote_by_url(emote_url)
+        .ok_or_else(|| anyhow!("expected an emote url"))?;
+    let value = match kind {
+        BlocklistKind::Emote => emote_id.into_owned(),
+        BlocklistKind::Owner => get_emote_properties(platform, &emote_id)
+            .await?
+            .owner_id
+            .ok_or_else(|| anyhow!("the emote has no known owner"))?,
+    };
+    let entry =
+        BlocklistEntry::add(platform, kind, &value, reason, pool).await?;
+
+    Ok(entry)
+}
+
+pub async fn unblock(id: &str, pool: &PgPool) -> AnyResult<()> {
+    let id = id
+        .trim_start_matches('#')
+        .parse::<i32>()
+        .map_err(|_| anyhow!("expected an id"))?;
+    if !BlocklistEntry::remove(id, pool).await? {
+        return Err(anyhow!("there's no entry #{id}"));
+    }
+
+    Ok(())
+}
+
+pub async fn summary(pool: &PgPool) -> AnyResult<String> {
+    let entries = BlocklistEntry::get_all(pool).await?;
+    if entries.is_empty() {
+        return Ok("the blocklist is empty".to_string());
+    }
+    let newest = entries
+        .iter()
+        .rev()
+        .take(5)
+        .map(|e| format!("#{} {} {} {}", e.id, e.platform, e.kind, e.value))
+        .collect::<Vec<_>>();
+
+    Ok(format!(
+        "{} entries, newest: {}",
+        entries.len(),
+        newest.join(", ")
+    ))
+}
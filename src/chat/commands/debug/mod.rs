@@ -1,3 +1,4 @@
+mod blocklist;
 mod channel;
 mod emotes;
 mod platforms;
@@ -12,7 +13,7 @@ use anyhow::{anyhow, Error as AnyError, Result as AnyResult};
 use async_trait::async_trait;
 use channel::ChannelData;
 use config::CONFIG;
-use models::editor;
+use models::{blocklist::BlocklistKind, editor};
 use platforms::Platforms;
 use std::sync::Arc;
 use tokio::sync::RwLock;
@@ -25,6 +26,13 @@ pub enum DebugCommand {
     Editor(String),
     RmEditor(String),
     SyncRewards(Option<String>),
+    Block {
+        kind: BlocklistKind,
+        emote: String,
+        reason: Option<String>,
+    },
+    Unblock(String),
+    Blocklist,
 }
 
 #[async_trait]
@@ -35,6 +43,7 @@ impl ChatCommand for DebugCommand {
         pool: &PgPool,
         _redis: RedisPool,
         app_access_token: Arc<RwLock<AppAccessToken>>,
+        _: &str,
     ) -> AnyResult<String> {
         match &mut self {
             DebugCommand::Channel(chan) => {
@@ -108,6 +117,31 @@ impl ChatCommand for DebugCommand {
                 };
                 Ok(format!("@{}, removed {data} internal rewards not present on Twitch.", msg.sender.login))
             }
+            DebugCommand::Block {
+                kind,
+                emote,
+                reason,
+            } => {
+                let entry =
+                    blocklist::block(*kind, emote, reason.as_deref(), pool)
+                        .await?;
+                Ok(format!(
+                    "@{}, blocked {} {} on {} (#{}).",
+                    msg.sender.login,
+                    entry.kind,
+                    entry.value,
+                    entry.platform,
+                    entry.id
+                ))
+            }
+            DebugCommand::Unblock(id) => {
+                blocklist::unblock(id, pool).await?;
+                Ok(format!("@{}, done.", msg.sender.login))
+            }
+            DebugCommand::Blocklist => {
+                let summary = blocklist::summary(pool).await?;
+                Ok(format!("@{}, {summary}", msg.sender.login))
+            }
         }
     }
 
@@ -141,6 +175,25 @@ impl ChatCommand for DebugCommand {
             "sync" => Ok(Box::new(Self::SyncRewards(
                 cmd.map(|c| opt_next_space(c).0.to_string()),
             ))),
+            "block" | "blockowner" => {
+                let (emote, reason) = cmd
+                    .map(opt_next_space)
+                    .ok_or_else(|| anyhow!("expected emote url"))?;
+                Ok(Box::new(Self::Block {
+                    kind: if subcmd == "block" {
+                        BlocklistKind::Emote
+                    } else {
+                        BlocklistKind::Owner
+                    },
+                    emote: emote.to_string(),
+                    reason: reason.map(ToOwned::to_owned),
+                }))
+            }
+            "unblock" => Ok(Box::new(Self::Unblock(
+                cmd.map(|c| opt_next_space(c).0.to_string())
+                    .ok_or_else(|| anyhow!("expected id"))?,
+            ))),
+            "blocklist" => Ok(Box::new(Self::Blocklist)),
             _ => Err(anyhow!(
                 "Expected subcommand (one of 'channel', 'platforms')"
             )),
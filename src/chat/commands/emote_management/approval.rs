@@ -0,0 +1,33 @@
+use crate::{
+    actors::approval::{ApprovalActor, ResolveApprovalMessage},
+    text,
+};
+use actix::SystemService;
+use anyhow::Result as AnyResult;
+use twitch_irc::message::PrivmsgMessage;
+
+pub async fn execute_resolve(
+    msg: &PrivmsgMessage,
+    id: i32,
+    approve: bool,
+    locale: &str,
+) -> AnyResult<String> {
+    let pending = ApprovalActor::from_registry()
+        .send(ResolveApprovalMessage {
+            broadcaster_id: msg.channel_id.clone(),
+            id,
+            approve,
+            resolved_by: msg.sender.login.clone(),
+        })
+        .await??;
+    let message = if approve {
+        text!("chat.emote.approved")
+    } else {
+        text!("chat.emote.denied")
+    };
+    Ok(message
+        .arg("user", msg.sender.login.as_str())
+        .arg("id", pending.id.to_string())
+        .arg("input", pending.user_input)
+        .render(locale))
+}
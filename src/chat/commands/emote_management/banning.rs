@@ -1,10 +1,18 @@
 use super::extract::extract_emote_data;
 use crate::{
-    services::emotes::{format::format_emote_url, remove::remove_emote},
-    PgPool, RedisPool,
+    services::emotes::{
+        ban_rules::{describe_rule, rule_value_from_emote, verify_rule},
+        format::format_emote_url,
+        remove::remove_emote,
+    },
+    text, PgPool, RedisPool,
+};
+use anyhow::Result as AnyResult;
+use models::{
+    ban_rule::{BanRule, BanRuleKind},
+    banned_emote,
+    emote_event::EmoteEventReason,
 };
-use anyhow::{anyhow, Result as AnyResult};
-use models::banned_emote;
 use twitch_irc::message::PrivmsgMessage;
 
 pub async fn execute_ban(
@@ -12,42 +20,105 @@ pub async fn execute_ban(
     emote: &str,
     pool: &PgPool,
     redis_pool: &RedisPool,
+    locale: &str,
 ) -> AnyResult<String> {
     let (emote_id, platform) = extract_emote_data(emote, &msg.channel_id, pool)
         .await
-        .ok_or_else(|| {
-            anyhow!("Could not find emote. Try to specify the emote url!")
-        })?;
+        .ok_or_else(|| text!("chat.emote.not_found"))?;
     banned_emote::add(&msg.channel_id, &emote_id, platform, pool)
         .await
-        .map_err(|_| {
-            anyhow!("Couldn't add ban, the emote might be banned already")
-        })?;
+        .map_err(|_| text!("chat.emote.already_banned"))?;
     // .ok because it may not be added
-    remove_emote(&msg.channel_id, &emote_id, platform, pool, redis_pool)
-        .await
-        .ok();
-    Ok(format!(
-        "@{}, 🚫 Banned {}",
-        msg.sender.login,
-        format_emote_url(platform, &emote_id)
-    ))
+    remove_emote(
+        &msg.channel_id,
+        &emote_id,
+        platform,
+        EmoteEventReason::Banned,
+        &msg.sender.login,
+        pool,
+        redis_pool,
+    )
+    .await
+    .ok();
+    Ok(text!(
+        "chat.emote.banned",
+        user = msg.sender.login.as_str(),
+        emote = format_emote_url(platform, &emote_id)
+    )
+    .render(locale))
 }
 
 pub async fn execute_unban(
     msg: &PrivmsgMessage,
     emote: &str,
     pool: &PgPool,
+    locale: &str,
 ) -> AnyResult<String> {
     let (emote_id, platform) = extract_emote_data(emote, &msg.channel_id, pool)
         .await
-        .ok_or_else(|| {
-            anyhow!("Could not find emote. Try to specify the emote url!")
-        })?;
+        .ok_or_else(|| text!("chat.emote.not_found"))?;
     banned_emote::remove(&msg.channel_id, &emote_id, platform, pool).await?;
-    Ok(format!(
-        "@{}, ✅ Unbanned {}",
-        msg.sender.login,
-        format_emote_url(platform, &emote_id)
-    ))
+    Ok(text!(
+        "chat.emote.unbanned",
+        user = msg.sender.login.as_str(),
+        emote = format_emote_url(platform, &emote_id)
+    )
+    .render(locale))
+}
+
+/// Adds a ban rule. Owner and hash rules take an emote and use its uploader
+/// or image, restricted to the emote's platform.
+pub async fn execute_ban_rule(
+    msg: &PrivmsgMessage,
+    kind: BanRuleKind,
+    value: Option<&str>,
+    pool: &PgPool,
+    locale: &str,
+) -> AnyResult<String> {
+    let (platform, value) = match (kind, value) {
+        (BanRuleKind::Owner | BanRuleKind::Hash, Some(emote)) => {
+            let (emote_id, platform) =
+                extract_emote_data(emote, &msg.channel_id, pool)
+                    .await
+                    .ok_or_else(|| text!("chat.emote.not_found"))?;
+            let value =
+                rule_value_from_emote(kind, platform, &emote_id).await?;
+            (Some(platform), Some(value))
+        }
+        (_, value) => (None, value.map(ToOwned::to_owned)),
+    };
+    verify_rule(kind, value.as_deref())?;
+    let rule = BanRule::create(
+        &msg.channel_id,
+        platform,
+        kind,
+        value.as_deref(),
+        pool,
+    )
+    .await?;
+    Ok(text!(
+        "chat.emote.rule_added",
+        user = msg.sender.login.as_str(),
+        rule = describe_rule(&rule)
+    )
+    .render(locale))
+}
+
+pub async fn execute_unban_rule(
+    msg: &PrivmsgMessage,
+    id: i32,
+    pool: &PgPool,
+    locale: &str,
+) -> AnyResult<String> {
+    if !BanRule::delete(id, &msg.channel_id, pool).await? {
+        return Err(
+            text!("chat.emote.rule_not_found", id = id.to_string()).into()
+        );
+    }
+    Ok(text!(
+        "chat.emote.rule_removed",
+        user = msg.sender.login.as_str(),
+        id = id.to_string()
+    )
+    .render(locale))
 }
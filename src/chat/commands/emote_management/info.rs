@@ -1,10 +1,11 @@
 use crate::{
     services::{
-        emotes::search::search_emote_by_name, formatting::human_format_duration,
+        emotes::search::search_emote_by_name,
+        formatting::human_format_duration, i18n::Text,
     },
-    PgPool, RedisConn,
+    text, PgPool, RedisConn,
 };
-use anyhow::{Error as AnyError, Result as AnyResult};
+use anyhow::Result as AnyResult;
 use chrono::Utc;
 use deadpool_redis::redis::AsyncCommands;
 use either::Either;
@@ -16,10 +17,12 @@ pub async fn execute_info(
     emote: &str,
     pool: &PgPool,
     redis: &mut RedisConn,
+    locale: &str,
 ) -> AnyResult<String> {
-    match search_emote_by_name(emote, &msg.channel_id, pool).await? {
-        Some(Either::Left(slot)) => format_slot(&msg.sender.login, slot),
-        Some(Either::Right(swap)) => format_swap(&msg.sender.login, swap),
+    let sender = msg.sender.login.as_str();
+    let info = match search_emote_by_name(emote, &msg.channel_id, pool).await? {
+        Some(Either::Left(slot)) => format_slot(sender, slot)?,
+        Some(Either::Right(swap)) => format_swap(sender, swap),
         None => {
             if let Some(slot) = redis
                 .get::<_, String>(format!(
@@ -31,44 +34,55 @@ pub async fn execute_info(
                 .ok()
                 .and_then(|s| serde_json::from_str::<Slot>(&s).ok())
             {
-                format_slot(&msg.sender.login, slot)
+                format_slot(sender, slot)?
             } else {
-                Err(AnyError::msg("This emote is unknown to me."))
+                return Err(text!("chat.emote.unknown").into());
             }
         }
-    }
+    };
+    Ok(info.render(locale))
 }
 
-fn format_slot(sender: &str, slot: Slot) -> AnyResult<String> {
+fn format_slot(sender: &str, slot: Slot) -> AnyResult<Text> {
     match (slot.name, slot.added_at, slot.added_by, slot.expires) {
         (Some(name), Some(added_at), Some(added_by), Some(expired)) => {
             let now = Utc::now();
             let added_duration = now - added_at;
             let expired_duration = now - expired;
-            let in_past = now > expired;
-            Ok(format!(
-                "@{}, {} was added {} by @{} and {} removed {}",
-                sender,
-                name,
-                human_format_duration(&added_duration),
-                added_by,
-                if in_past { "was" } else { "will be" },
-                human_format_duration(&expired_duration)
-            ))
+            let info = if now > expired {
+                text!(
+                    "chat.emote.slot_info_expired",
+                    user = sender,
+                    emote = name,
+                    added = human_format_duration(&added_duration),
+                    added_by = added_by,
+                    expires = human_format_duration(&expired_duration)
+                )
+            } else {
+                text!(
+                    "chat.emote.slot_info",
+                    user = sender,
+                    emote = name,
+                    added = human_format_duration(&added_duration),
+                    added_by = added_by,
+                    expires = human_format_duration(&expired_duration)
+                )
+            };
+            Ok(info)
         }
-        _ => Err(AnyError::msg("Not enough information")),
+        _ => Err(text!("chat.emote.no_info").into()),
     }
 }
 
-fn format_swap(sender: &str, emote: SwapEmote) -> AnyResult<String> {
+fn format_swap(sender: &str, emote: SwapEmote) -> Text {
     let now = Utc::now();
     let added_duration = now - emote.added_at;
-    Ok(format!(
-        "@{}, {} was added {} by @{} [{}]",
-        sender,
-        emote.name,
-        human_format_duration(&added_duration),
-        emote.added_by,
-        emote.platform
-    ))
+    text!(
+        "chat.emote.swap_info",
+        user = sender,
+        emote = emote.name,
+        added = human_format_duration(&added_duration),
+        added_by = emote.added_by,
+        platform = emote.platform.to_string()
+    )
 }
@@ -1,9 +1,9 @@
 use crate::{
     chat::commands::emote_management::extract::extract_emote_by_url,
     services::emotes::{add::track_emote, search::EmoteCache},
-    PgPool, RedisConn,
+    text, PgPool, RedisConn,
 };
-use anyhow::{anyhow, Result as AnyResult};
+use anyhow::Result as AnyResult;
 use models::emote::SlotPlatform;
 use twitch_irc::message::PrivmsgMessage;
 
@@ -18,12 +18,11 @@ pub async fn execute_inject(
     emote: &str,
     redis: &mut RedisConn,
     pool: &PgPool,
+    locale: &str,
 ) -> AnyResult<String> {
     let emote = find_emote(emote, &msg.channel_id, redis, pool)
         .await
-        .ok_or_else(|| {
-            anyhow!("Could not find emote. Try to specify the emote url!")
-        })?;
+        .ok_or_else(|| text!("chat.emote.not_found"))?;
     track_emote(
         &msg.channel_id,
         &msg.sender.login,
@@ -33,10 +32,13 @@ pub async fn execute_inject(
         pool,
     )
     .await?;
-    Ok(format!(
-        "@{}, injected {} from {}.",
-        msg.sender.login, emote.name, emote.platform
-    ))
+    Ok(text!(
+        "chat.emote.injected",
+        user = msg.sender.login.as_str(),
+        emote = emote.name,
+        platform = emote.platform.to_string()
+    )
+    .render(locale))
 }
 
 async fn find_emote(
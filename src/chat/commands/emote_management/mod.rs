@@ -1,21 +1,25 @@
+mod approval;
 mod banning;
 mod eject;
-mod extract;
+pub mod extract;
 mod info;
 mod inject;
 mod reload;
 
 use crate::{
     chat::{command::ChatCommand, parse::opt_next_space},
-    AppAccessToken, PgPool, RedisConn, RedisPool,
+    text, AppAccessToken, PgPool, RedisPool,
 };
-use anyhow::{anyhow, Result as AnyResult};
+use anyhow::Result as AnyResult;
+use approval::execute_resolve;
 use async_trait::async_trait;
-use banning::{execute_ban, execute_unban};
+use banning::{
+    execute_ban, execute_ban_rule, execute_unban, execute_unban_rule,
+};
 use eject::execute_eject;
 use info::execute_info;
 use inject::execute_inject;
-use models::editor::Editor;
+use models::{ban_rule::BanRuleKind, command_settings::PermissionLevel};
 use reload::execute_reload;
 use std::sync::Arc;
 use tokio::sync::RwLock;
@@ -24,10 +28,14 @@ use twitch_irc::message::PrivmsgMessage;
 pub enum EmoteManagement {
     Info(String),
     Ban(String),
+    BanRule(BanRuleKind, Option<String>),
     Unban(String),
+    UnbanRule(i32),
     Eject(String),
     Inject(String),
     Reload,
+    Approve(i32),
+    Deny(i32),
 }
 
 #[async_trait]
@@ -38,20 +46,39 @@ impl ChatCommand for EmoteManagement {
         pool: &PgPool,
         redis: RedisPool,
         _: Arc<RwLock<AppAccessToken>>,
+        locale: &str,
     ) -> AnyResult<String> {
         match &self {
             Self::Info(emote) => {
-                execute_info(&msg, emote, pool, &mut redis.get().await?).await
+                let mut redis = redis.get().await?;
+                execute_info(&msg, emote, pool, &mut redis, locale).await
+            }
+            Self::Ban(emote) => {
+                execute_ban(&msg, emote, pool, &redis, locale).await
+            }
+            Self::BanRule(kind, value) => {
+                execute_ban_rule(&msg, *kind, value.as_deref(), pool, locale)
+                    .await
+            }
+            Self::Unban(emote) => {
+                execute_unban(&msg, emote, pool, locale).await
+            }
+            Self::UnbanRule(id) => {
+                execute_unban_rule(&msg, *id, pool, locale).await
+            }
+            Self::Eject(emote) => {
+                execute_eject(&msg, emote, pool, locale).await
             }
-            Self::Ban(emote) => execute_ban(&msg, emote, pool, &redis).await,
-            Self::Unban(emote) => execute_unban(&msg, emote, pool).await,
-            Self::Eject(emote) => execute_eject(&msg, emote, pool).await,
             Self::Inject(emote) => {
-                execute_inject(&msg, emote, &mut redis.get().await?, pool).await
+                let mut redis = redis.get().await?;
+                execute_inject(&msg, emote, &mut redis, pool, locale).await
             }
             Self::Reload => {
-                execute_reload(&msg, &mut redis.get().await?, pool).await
+                execute_reload(&msg, &mut redis.get().await?, pool, locale)
+                    .await
             }
+            Self::Approve(id) => execute_resolve(&msg, *id, true, locale).await,
+            Self::Deny(id) => execute_resolve(&msg, *id, false, locale).await,
         }
     }
 
@@ -63,64 +90,88 @@ impl ChatCommand for EmoteManagement {
         Self: Sized + Send,
     {
         Ok(Box::new(match cmd {
-            "ei" | "emoteinfo" => Self::Info(
-                args.ok_or_else(|| anyhow!("No emote specified"))
+            "emoteinfo" => Self::Info(
+                args.ok_or_else(|| text!("chat.emote.no_emote"))
                     .map(opt_next_space)?
                     .0
                     .to_string(),
             ),
             _ => {
                 let (target, args) = args
-                    .ok_or_else(|| {
-                        anyhow!(
-                            "No option specified (emote <ban/unban/info/eject/inject/reload/{{emote}}>"
-                        )
-                    })
+                    .ok_or_else(|| text!("chat.emote.no_option"))
                     .map(opt_next_space)?;
                 let target = target.to_lowercase();
                 match target.as_str() {
                     "ban" | "unban" | "eject" | "inject" => {
-                        let emote = args
-                            .ok_or_else(|| anyhow!("No emote url specified"))
-                            .map(opt_next_space)?
-                            .0
-                            .to_string();
-                        match target.as_str() {
-                            "ban" => Self::Ban(emote),
-                            "unban" => Self::Unban(emote),
-                            "eject" => Self::Eject(emote),
-                            _ => Self::Inject(emote),
+                        let (emote, rest) = args
+                            .ok_or_else(|| text!("chat.emote.no_emote_url"))
+                            .map(opt_next_space)?;
+                        match (target.as_str(), parse_rule_kind(emote)) {
+                            ("ban", Some(kind)) => {
+                                Self::BanRule(kind, rest.map(ToOwned::to_owned))
+                            }
+                            ("unban", _)
+                                if emote.eq_ignore_ascii_case("rule") =>
+                            {
+                                Self::UnbanRule(parse_id(rest)?)
+                            }
+                            ("ban", _) => Self::Ban(emote.to_string()),
+                            ("unban", _) => Self::Unban(emote.to_string()),
+                            ("eject", _) => Self::Eject(emote.to_string()),
+                            _ => Self::Inject(emote.to_string()),
                         }
                     }
                     "info" => Self::Info(
-                        args.ok_or_else(|| anyhow!("No emote specified"))
+                        args.ok_or_else(|| text!("chat.emote.no_emote"))
                             .map(opt_next_space)?
                             .0
                             .to_string(),
                     ),
                     "reload" => Self::Reload,
+                    "approve" | "deny" => {
+                        let id = parse_id(args)?;
+                        if target == "approve" {
+                            Self::Approve(id)
+                        } else {
+                            Self::Deny(id)
+                        }
+                    }
                     _ => Self::Info(target),
                 }
             }
         }))
     }
 
-    async fn check_permission(
-        &mut self,
-        msg: &PrivmsgMessage,
-        pool: &PgPool,
-        _redis: &mut RedisConn,
-    ) -> bool {
-        if matches!(self, Self::Info(_)) || msg.sender.id == msg.channel_id {
-            true
-        } else {
-            Editor::get_broadcaster_for_editor(
-                &msg.sender.id,
-                &msg.channel_id,
-                pool,
-            )
-            .await
-            .is_ok()
+    fn permission(&self) -> PermissionLevel {
+        match self {
+            Self::Info(_) => PermissionLevel::Everyone,
+            Self::Approve(_) | Self::Deny(_) => PermissionLevel::Moderator,
+            _ => PermissionLevel::Editor,
         }
     }
 }
+
+/// Parses an id like `#42` from the first word.
+fn parse_id(args: Option<&str>) -> AnyResult<i32> {
+    Ok(args
+        .ok_or_else(|| text!("chat.emote.no_id"))
+        .map(opt_next_space)?
+        .0
+        .trim_start_matches('#')
+        .parse::<i32>()
+        .map_err(|_| text!("chat.emote.invalid_id"))?)
+}
+
+fn parse_rule_kind(kind: &str) -> Option<BanRuleKind> {
+    Some(match kind.to_lowercase().as_str() {
+        "name" => BanRuleKind::Name,
+        "owner" => BanRuleKind::Owner,
+        "tag" => BanRuleKind::Tag,
+        "zerowidth" | "zero-width" => BanRuleKind::ZeroWidth,
+        "nsfw" => BanRuleKind::Nsfw,
+        "animated" => BanRuleKind::Animated,
+        "static" => BanRuleKind::Static,
+        "hash" => BanRuleKind::Hash,
+        _ => return None,
+    })
+}
@@ -1,4 +1,6 @@
-use crate::{services::emotes::refresh::refresh_emotes, PgPool, RedisConn};
+use crate::{
+    services::emotes::refresh::refresh_emotes, text, PgPool, RedisConn,
+};
 use anyhow::Result as AnyResult;
 use twitch_irc::message::PrivmsgMessage;
 
@@ -6,11 +8,14 @@ pub async fn execute_reload(
     msg: &PrivmsgMessage,
     redis: &mut RedisConn,
     pg: &PgPool,
+    locale: &str,
 ) -> AnyResult<String> {
     let removed = refresh_emotes(&msg.channel_id, redis, pg).await?;
 
-    Ok(format!(
-        "@{}, removed {} emotes!",
-        msg.sender.login, removed
-    ))
+    Ok(text!(
+        "chat.emote.reloaded",
+        user = msg.sender.login.as_str(),
+        count = removed.to_string()
+    )
+    .render(locale))
 }
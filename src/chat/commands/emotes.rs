@@ -1,6 +1,6 @@
 use crate::{
     chat::{command::ChatCommand, parse::opt_next_space},
-    AppAccessToken, RedisPool,
+    text, AppAccessToken, RedisPool,
 };
 use anyhow::Result as AnyResult;
 use async_trait::async_trait;
@@ -31,6 +31,7 @@ impl ChatCommand for Emotes {
         pool: &PgPool,
         _: RedisPool,
         _: Arc<RwLock<AppAccessToken>>,
+        locale: &str,
     ) -> AnyResult<String> {
         let resp = match self.requested {
             None => future::try_join(
@@ -71,13 +72,15 @@ impl ChatCommand for Emotes {
             }
         }?;
         Ok(if resp.is_empty() {
-            format!(
-                "@{}, no managed emotes found for this channel",
-                msg.sender.login
-            )
+            text!("chat.emotes.none", user = msg.sender.login)
         } else {
-            format!("@{}, {}", msg.sender.login, resp.iter().join(" "))
-        })
+            text!(
+                "chat.emotes.list",
+                user = msg.sender.login,
+                emotes = resp.iter().join(" ")
+            )
+        }
+        .render(locale))
     }
 
     fn parse(
@@ -1,4 +1,5 @@
 pub mod about;
+pub mod custom;
 pub mod debug;
 pub mod emote_management;
 pub mod emotes;
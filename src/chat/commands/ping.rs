@@ -1,4 +1,4 @@
-use crate::{chat::command::ChatCommand, AppAccessToken, RedisPool};
+use crate::{chat::command::ChatCommand, text, AppAccessToken, RedisPool};
 use anyhow::Result as AnyResult;
 use async_trait::async_trait;
 use sqlx::PgPool;
@@ -20,15 +20,19 @@ impl ChatCommand for Ping {
         _pool: &PgPool,
         _: RedisPool,
         _: Arc<RwLock<AppAccessToken>>,
+        locale: &str,
     ) -> AnyResult<String> {
-        Ok(format!("@{}, 🤖 Pong! ⏱ Uptime: {uptime} 📜 Git: {git_info} 🛠 Compiled with Rust {rustc_info} on {build_info}@{build_profile}",
-                   msg.sender.login,
-                   uptime = humantime::format_duration(clean_duration(uptimer::get().unwrap_or_default())),
-                   git_info = env!("RW_GIT_INFO"),
-                   rustc_info = env!("RW_RUSTC_INFO"),
-                   build_info = env!("RW_BUILD_INFO"),
-                   build_profile = env!("RW_BUILD_PROFILE")
-        ))
+        let uptime = clean_duration(uptimer::get().unwrap_or_default());
+        Ok(text!(
+            "chat.ping",
+            user = msg.sender.login,
+            uptime = humantime::format_duration(uptime).to_string(),
+            git = env!("RW_GIT_INFO"),
+            rustc = env!("RW_RUSTC_INFO"),
+            build = env!("RW_BUILD_INFO"),
+            profile = env!("RW_BUILD_PROFILE")
+        )
+        .render(locale))
     }
 
     fn parse(
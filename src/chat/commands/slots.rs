@@ -1,5 +1,5 @@
-use crate::{chat::command::ChatCommand, AppAccessToken, RedisPool};
-use anyhow::{Error as AnyError, Result as AnyResult};
+use crate::{chat::command::ChatCommand, text, AppAccessToken, RedisPool};
+use anyhow::Result as AnyResult;
 use async_trait::async_trait;
 use models::slot::Slot;
 use sqlx::PgPool;
@@ -17,16 +17,18 @@ impl ChatCommand for SlotsCommand {
         pool: &PgPool,
         _: RedisPool,
         _: Arc<RwLock<AppAccessToken>>,
+        locale: &str,
     ) -> AnyResult<String> {
         let occupation = Slot::get_occupation(&msg.channel_id, pool)
             .await
-            .map_err(|_| AnyError::msg("Some kind of internal error"))?;
-        Ok(format!(
-            "@{}, There are {} of {} slots free in this channel",
-            msg.sender.login,
-            occupation.available.unwrap_or(0),
-            occupation.total.unwrap_or(0)
-        ))
+            .map_err(|_| text!("chat.internal_error"))?;
+        Ok(text!(
+            "chat.slots",
+            user = msg.sender.login,
+            available = occupation.available.unwrap_or(0).to_string(),
+            total = occupation.total.unwrap_or(0).to_string()
+        )
+        .render(locale))
     }
 
     fn parse(
@@ -1,20 +1,31 @@
 mod info;
+mod queue;
 mod skip;
 
 use crate::{
     chat::{command::ChatCommand, parse::opt_next_space},
-    AppAccessToken, PgPool, RedisConn, RedisPool,
+    text, AppAccessToken, PgPool, RedisPool,
 };
-use anyhow::{anyhow, Result as AnyResult};
+use anyhow::Result as AnyResult;
 use async_trait::async_trait;
-use models::editor::Editor;
+use models::command_settings::PermissionLevel;
+use queue::Resolution;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use twitch_irc::message::PrivmsgMessage;
 
 pub enum SpotifyAction {
-    Skip(Option<String>),
+    Skip,
     Info,
+    Queue,
+    Approve(i32),
+    Reject(i32),
+    /// Removes a request from the song queue
+    Remove(i32),
+    /// Removes one of the sender's own requests
+    RemoveOwn(Option<i32>),
+    /// Lists the sender's requests
+    Mine,
 }
 
 #[async_trait]
@@ -25,13 +36,48 @@ impl ChatCommand for SpotifyAction {
         pool: &PgPool,
         _redis: RedisPool,
         _: Arc<RwLock<AppAccessToken>>,
+        locale: &str,
     ) -> AnyResult<String> {
         match &self {
-            SpotifyAction::Skip(Some(_)) => {
-                Err(anyhow!("Removing a specific song from the queue is not supported because of missing functionality in Spotify's API."))
-            },
-            SpotifyAction::Skip(None) => skip::execute(msg, pool).await,
-            SpotifyAction::Info => info::execute(msg, pool).await,
+            SpotifyAction::Skip => skip::execute(msg, pool, locale).await,
+            SpotifyAction::Info => info::execute(msg, pool, locale).await,
+            SpotifyAction::Queue => {
+                queue::execute_list(msg, pool, locale).await
+            }
+            SpotifyAction::Approve(id) => {
+                queue::execute_resolve(
+                    msg,
+                    *id,
+                    Resolution::Approve,
+                    pool,
+                    locale,
+                )
+                .await
+            }
+            SpotifyAction::Reject(id) => {
+                queue::execute_resolve(
+                    msg,
+                    *id,
+                    Resolution::Reject,
+                    pool,
+                    locale,
+                )
+                .await
+            }
+            SpotifyAction::Remove(id) => {
+                queue::execute_resolve(
+                    msg,
+                    *id,
+                    Resolution::Remove,
+                    pool,
+                    locale,
+                )
+                .await
+            }
+            SpotifyAction::RemoveOwn(id) => {
+                queue::execute_remove_own(msg, *id, pool, locale).await
+            }
+            SpotifyAction::Mine => queue::execute_mine(msg, pool, locale).await,
         }
     }
 
@@ -42,36 +88,112 @@ impl ChatCommand for SpotifyAction {
     where
         Self: Sized + Send,
     {
-        const OPTIONS: &str = "spotify <(i)nfo/(s)kip>";
+        const OPTIONS: &str = "spotify <(i)nfo/(s)kip [id]/(q)ueue/(m)ine/\
+                               (r)emove [id]/approve <id>/reject <id>>";
 
         let (target, args) = args
-            .ok_or_else(|| anyhow!("No option specified ({OPTIONS})"))
+            .ok_or_else(|| text!("chat.spotify.no_option", options = OPTIONS))
             .map(opt_next_space)?;
         let target = target.to_lowercase();
         let cmd = match target.as_str() {
             "i" | "info" => Self::Info,
-            "s" | "skip" => Self::Skip(args.map(|a| a.to_owned())),
-            _ => return Err(anyhow!("Unknown subcommand ({OPTIONS})")),
+            "s" | "skip" if args.is_some() => Self::Remove(parse_id(args)?),
+            "s" | "skip" => Self::Skip,
+            "q" | "queue" => Self::Queue,
+            "m" | "mine" => Self::Mine,
+            "r" | "remove" if args.is_some() => {
+                Self::RemoveOwn(Some(parse_id(args)?))
+            }
+            "r" | "remove" => Self::RemoveOwn(None),
+            "approve" => Self::Approve(parse_id(args)?),
+            "reject" => Self::Reject(parse_id(args)?),
+            _ => {
+                return Err(text!(
+                    "chat.spotify.unknown_option",
+                    options = OPTIONS
+                )
+                .into())
+            }
         };
         Ok(Box::new(cmd))
     }
 
-    async fn check_permission(
-        &mut self,
-        msg: &PrivmsgMessage,
-        pool: &PgPool,
-        _redis: &mut RedisConn,
-    ) -> bool {
-        if matches!(self, Self::Info) || msg.sender.id == msg.channel_id {
-            true
-        } else {
-            Editor::get_broadcaster_for_editor(
-                &msg.sender.id,
-                &msg.channel_id,
-                pool,
-            )
-            .await
-            .is_ok()
+    fn permission(&self) -> PermissionLevel {
+        match self {
+            Self::Info | Self::Queue | Self::Mine | Self::RemoveOwn(_) => {
+                PermissionLevel::Everyone
+            }
+            Self::Approve(_) | Self::Reject(_) | Self::Remove(_) => {
+                PermissionLevel::Moderator
+            }
+            Self::Skip => PermissionLevel::Editor,
+        }
+    }
+}
+
+/// Parses a song request id like `#42` from the first word.
+fn parse_id(args: Option<&str>) -> AnyResult<i32> {
+    Ok(args
+        .ok_or_else(|| text!("chat.spotify.no_id"))
+        .map(opt_next_space)?
+        .0
+        .trim_start_matches('#')
+        .parse::<i32>()
+        .map_err(|_| text!("chat.spotify.invalid_id"))?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::i18n::Text;
+
+    fn error_key(error: anyhow::Error) -> Option<&'static str> {
+        match error.downcast_ref::<Text>() {
+            Some(Text::Message { key, .. }) => Some(*key),
+            _ => None,
         }
     }
+
+    fn permission(args: &str) -> PermissionLevel {
+        SpotifyAction::parse("spotify", Some(args))
+            .unwrap()
+            .permission()
+    }
+
+    #[test]
+    fn parses_request_ids() {
+        assert_eq!(parse_id(Some("#42")).unwrap(), 42);
+        assert_eq!(parse_id(Some("7 and more")).unwrap(), 7);
+        assert_eq!(
+            error_key(parse_id(None).unwrap_err()),
+            Some("chat.spotify.no_id")
+        );
+        assert_eq!(
+            error_key(parse_id(Some("#abc")).unwrap_err()),
+            Some("chat.spotify.invalid_id")
+        );
+    }
+
+    #[test]
+    fn removing_own_requests_is_allowed_for_everyone() {
+        assert_eq!(permission("mine"), PermissionLevel::Everyone);
+        assert_eq!(permission("r"), PermissionLevel::Everyone);
+        assert_eq!(permission("remove #3"), PermissionLevel::Everyone);
+        // removing any request needs a moderator
+        assert_eq!(permission("skip #3"), PermissionLevel::Moderator);
+        assert_eq!(permission("skip"), PermissionLevel::Editor);
+    }
+
+    #[test]
+    fn rejects_invalid_options() {
+        let error = |args| {
+            SpotifyAction::parse("spotify", args)
+                .err()
+                .and_then(error_key)
+        };
+        assert_eq!(error(None), Some("chat.spotify.no_option"));
+        assert_eq!(error(Some("dance")), Some("chat.spotify.unknown_option"));
+        assert_eq!(error(Some("approve")), Some("chat.spotify.no_id"));
+        assert_eq!(error(Some("r me")), Some("chat.spotify.invalid_id"));
+    }
 }
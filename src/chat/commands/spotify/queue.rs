@@ -0,0 +1,125 @@
+use crate::{
+    services::{i18n::Text, spotify::queue, text::trim_to},
+    text,
+};
+use anyhow::Result as AnyResult;
+use itertools::Itertools;
+use models::song_request::{SongRequest, SongRequestStatus};
+use sqlx::PgPool;
+use twitch_irc::message::PrivmsgMessage;
+
+pub async fn execute_list(
+    msg: PrivmsgMessage,
+    pool: &PgPool,
+    locale: &str,
+) -> AnyResult<String> {
+    let requests = SongRequest::get_open(&msg.channel_id, pool).await?;
+    if requests.is_empty() {
+        return Ok(text!("chat.spotify.queue_empty").render(locale));
+    }
+    let queue = requests
+        .iter()
+        .map(|r| {
+            let pending = if r.status == SongRequestStatus::Pending {
+                " ⏳"
+            } else {
+                ""
+            };
+            format!(
+                "#{} {} ({}){pending}",
+                r.id,
+                queue::describe(r),
+                r.requested_by
+            )
+        })
+        .join(" | ");
+
+    Ok(text!("chat.spotify.queue", queue = trim_to(queue, 400)).render(locale))
+}
+
+pub async fn execute_mine(
+    msg: PrivmsgMessage,
+    pool: &PgPool,
+    locale: &str,
+) -> AnyResult<String> {
+    let requests =
+        queue::get_open_by(&msg.channel_id, &msg.sender.login, pool).await?;
+    if requests.is_empty() {
+        return Ok(text!("spotify.queue.none_by_you").render(locale));
+    }
+    let requests = requests
+        .iter()
+        .map(|(r, position)| {
+            let state = match r.status {
+                SongRequestStatus::Pending => " ⏳".to_owned(),
+                SongRequestStatus::Sent => " 🎵".to_owned(),
+                _ => format!(" (#{})", position + 1),
+            };
+            format!("#{} {}{state}", r.id, queue::describe(r))
+        })
+        .join(" | ");
+
+    Ok(
+        text!("chat.spotify.mine", requests = trim_to(requests, 400))
+            .render(locale),
+    )
+}
+
+pub async fn execute_remove_own(
+    msg: PrivmsgMessage,
+    id: Option<i32>,
+    pool: &PgPool,
+    locale: &str,
+) -> AnyResult<String> {
+    let (request, refunded) =
+        queue::remove_own(&msg.channel_id, &msg.sender.login, id, pool).await?;
+
+    Ok(removed_message(refunded)
+        .arg("id", request.id.to_string())
+        .arg("track", queue::describe(&request))
+        .render(locale))
+}
+
+fn removed_message(refunded: bool) -> Text {
+    if refunded {
+        text!("chat.spotify.removed")
+    } else {
+        text!("chat.spotify.removed_not_refunded")
+    }
+}
+
+pub enum Resolution {
+    Approve,
+    Reject,
+    Remove,
+}
+
+pub async fn execute_resolve(
+    msg: PrivmsgMessage,
+    id: i32,
+    resolution: Resolution,
+    pool: &PgPool,
+    locale: &str,
+) -> AnyResult<String> {
+    let (channel, by) = (msg.channel_id.as_str(), msg.sender.login.as_str());
+    let (request, message) = match resolution {
+        Resolution::Approve => (
+            queue::approve(channel, id, by, pool).await?,
+            text!("chat.spotify.approved"),
+        ),
+        Resolution::Reject => (
+            queue::reject(channel, id, by, pool).await?,
+            text!("chat.spotify.rejected"),
+        ),
+        Resolution::Remove => {
+            let (request, refunded) =
+                queue::remove(channel, id, by, pool).await?;
+            (request, removed_message(refunded))
+        }
+    };
+
+    Ok(message
+        .arg("id", request.id.to_string())
+        .arg("track", queue::describe(&request))
+        .render(locale))
+}
@@ -1,9 +1,13 @@
-use crate::services::spotify::rewards::skip_track;
+use crate::{services::spotify::rewards::skip_track, text};
 use anyhow::Result as AnyResult;
 use sqlx::PgPool;
 use twitch_irc::message::PrivmsgMessage;
 
-pub async fn execute(msg: PrivmsgMessage, pool: &PgPool) -> AnyResult<String> {
+pub async fn execute(
+    msg: PrivmsgMessage,
+    pool: &PgPool,
+    locale: &str,
+) -> AnyResult<String> {
     let name = skip_track(&msg.channel_id, true, pool).await?;
-    Ok(format!("Skipped {name}"))
+    Ok(text!("chat.spotify.skipped", track = name).render(locale))
 }
@@ -1,30 +1,68 @@
 pub mod command;
 pub mod commands;
 pub mod parse;
+pub mod permission;
+pub mod settings;
 
 use crate::chat::commands::{debug::DebugCommand, spotify::SpotifyAction};
 use anyhow::Result as AnyResult;
 use command::ChatCommand;
 use commands::{
-    about::About, emote_management::EmoteManagement, emotes::Emotes,
-    ping::Ping, slots::SlotsCommand,
+    about::About, custom::CustomTextCommand, emote_management::EmoteManagement,
+    emotes::Emotes, ping::Ping, slots::SlotsCommand,
 };
+use models::{
+    command_settings::{CommandOverride, CommandSettings},
+    custom_command::CustomCommand,
+};
+
+pub struct ParsedCommand {
+    /// Identifies the command in cooldowns
+    pub name: String,
+    pub options: CommandOverride,
+    pub executor: AnyResult<Box<dyn ChatCommand + Send>>,
+}
 
+/// Parses a builtin command and falls back to the channel's custom commands.
 pub fn try_parse_command(
     command: &str,
     args: Option<&str>,
-) -> Option<AnyResult<Box<dyn ChatCommand + Send>>> {
+    settings: &CommandSettings,
+    custom: &[CustomCommand],
+) -> Option<ParsedCommand> {
     let command = command.to_lowercase();
-    let cmd = match command.as_str() {
-        "ping" | "bing" => Ping::parse(&command, args),
-        "about" | "rewardmore" | "who" | "bot" => About::parse(&command, args),
-        "emote" | "emoteinfo" | "ei" => EmoteManagement::parse(&command, args),
-        "slots" | "emoteslots" => SlotsCommand::parse(&command, args),
-        "emotes" | "currentemotes" | "ce" => Emotes::parse(&command, args),
-        "debug" | "dbg" => DebugCommand::parse(&command, args),
-        "spotify" | "sp" => SpotifyAction::parse(&command, args),
+    let Some(name) = settings::resolve_command(&command, settings) else {
+        return custom.iter().find(|c| c.name == command).map(|c| {
+            ParsedCommand {
+                name: format!("custom:{}", c.name),
+                options: CommandOverride {
+                    user_cooldown: Some(c.user_cooldown as u32),
+                    channel_cooldown: Some(c.channel_cooldown as u32),
+                    ..Default::default()
+                },
+                executor: Ok(Box::new(CustomTextCommand {
+                    response: c.response.clone(),
+                    args: args.map(ToOwned::to_owned),
+                })),
+            }
+        });
+    };
+
+    let executor = match name {
+        "ping" => Ping::parse(&command, args),
+        "about" => About::parse(&command, args),
+        // aliases can be anything, so the subcommand is chosen by the name
+        "emote" | "emoteinfo" => EmoteManagement::parse(name, args),
+        "slots" => SlotsCommand::parse(&command, args),
+        "emotes" => Emotes::parse(&command, args),
+        "debug" => DebugCommand::parse(&command, args),
+        "spotify" => SpotifyAction::parse(&command, args),
         _ => return None,
     };
 
-    Some(cmd)
+    Some(ParsedCommand {
+        name: name.to_string(),
+        options: settings.commands.get(name).cloned().unwrap_or_default(),
+        executor,
+    })
 }
@@ -0,0 +1,95 @@
+use crate::{services::i18n::Text, text};
+use models::{command_settings::PermissionLevel, editor::Editor};
+use sqlx::PgPool;
+use twitch_irc::message::PrivmsgMessage;
+
+/// Gets the level of the sender based on their badges.
+/// This doesn't include editors since they're only known to the bot.
+fn badge_level(msg: &PrivmsgMessage) -> PermissionLevel {
+    if msg.sender.id == msg.channel_id {
+        return PermissionLevel::Broadcaster;
+    }
+
+    msg.badges
+        .iter()
+        .map(|badge| match badge.name.as_str() {
+            "broadcaster" => PermissionLevel::Broadcaster,
+            "moderator" => PermissionLevel::Moderator,
+            "vip" => PermissionLevel::Vip,
+            "subscriber" | "founder" => PermissionLevel::Subscriber,
+            _ => PermissionLevel::Everyone,
+        })
+        .max()
+        .unwrap_or(PermissionLevel::Everyone)
+}
+
+/// Checks if the sender has at least the `required` level.
+pub async fn has_permission(
+    msg: &PrivmsgMessage,
+    required: PermissionLevel,
+    pool: &PgPool,
+) -> bool {
+    let level = badge_level(msg);
+    if level >= required {
+        return true;
+    }
+    if required > PermissionLevel::Editor {
+        return false;
+    }
+
+    Editor::get_broadcaster_for_editor(&msg.sender.id, &msg.channel_id, pool)
+        .await
+        .is_ok()
+}
+
+/// Describes who can run a command with the `required` level.
+pub fn describe_level(required: PermissionLevel) -> Text {
+    match required {
+        PermissionLevel::Everyone => text!("permission.everyone"),
+        PermissionLevel::Subscriber => text!("permission.subscriber"),
+        PermissionLevel::Vip => text!("permission.vip"),
+        PermissionLevel::Moderator => text!("permission.moderator"),
+        PermissionLevel::Editor => text!("permission.editor"),
+        PermissionLevel::Broadcaster => text!("permission.broadcaster"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use twitch_irc::message::IRCMessage;
+
+    fn message(user_id: &str, badges: &str) -> PrivmsgMessage {
+        let raw = format!(
+            "@badge-info=;badges={badges};color=#0000FF;display-name=Viewer;\
+             emotes=;flags=;id=e9d998c3-36f1-430f-89ec-6b887c28af36;mod=0;\
+             room-id=1001;subscriber=0;tmi-sent-ts=1594545155039;turbo=0;\
+             user-id={user_id};user-type= \
+             :viewer!viewer@viewer.tmi.twitch.tv PRIVMSG #broadcaster :!ping"
+        );
+        PrivmsgMessage::try_from(IRCMessage::parse(&raw).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn levels_from_badges() {
+        let level = |badges| badge_level(&message("2002", badges));
+        assert_eq!(level(""), PermissionLevel::Everyone);
+        assert_eq!(level("premium/1"), PermissionLevel::Everyone);
+        assert_eq!(level("founder/0"), PermissionLevel::Subscriber);
+        assert_eq!(level("subscriber/12"), PermissionLevel::Subscriber);
+        assert_eq!(level("vip/1"), PermissionLevel::Vip);
+        // the highest badge counts
+        assert_eq!(
+            level("moderator/1,subscriber/12"),
+            PermissionLevel::Moderator
+        );
+    }
+
+    #[test]
+    fn the_channel_owner_is_the_broadcaster() {
+        assert_eq!(
+            badge_level(&message("1001", "")),
+            PermissionLevel::Broadcaster
+        );
+    }
+}
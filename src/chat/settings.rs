@@ -0,0 +1,396 @@
+use crate::{
+    chat::commands::custom::CUSTOM_COMMAND_VARIABLES,
+    services::template::verify_template, RedisConn,
+};
+use anyhow::{bail, Result as AnyResult};
+use config::CONFIG;
+use deadpool_redis::redis::AsyncCommands;
+use errors::sql::SqlResult;
+use models::{
+    command_settings::{CommandOverride, CommandSettings},
+    custom_command::CustomCommand,
+    user::User,
+};
+use serde::{de::DeserializeOwned, Serialize};
+use sqlx::PgPool;
+use std::{collections::HashSet, future::Future};
+
+/// The builtin commands and their default names.
+/// The first name is used to refer to the command in the settings.
+pub const COMMANDS: &[&[&str]] = &[
+    &["ping", "bing"],
+    &["about", "rewardmore", "who", "bot"],
+    &["emote"],
+    &["emoteinfo", "ei"],
+    &["slots", "emoteslots"],
+    &["emotes", "currentemotes", "ce"],
+    &["debug", "dbg"],
+    &["spotify", "sp"],
+];
+
+/// Commands that can't be configured by broadcasters
+const PROTECTED_COMMANDS: &[&str] = &["debug"];
+
+fn settings_key(user_id: &str) -> String {
+    format!("rewards:command-settings:{}", user_id)
+}
+
+fn custom_commands_key(user_id: &str) -> String {
+    format!("rewards:custom-commands:{}", user_id)
+}
+
+fn locale_key(user_id: &str) -> String {
+    format!("rewards:locale:{}", user_id)
+}
+
+/// Gets a value from redis or loads it and caches it for 10 minutes.
+async fn get_cached<T, F>(
+    key: String,
+    redis: &mut RedisConn,
+    load: F,
+) -> AnyResult<T>
+where
+    T: Serialize + DeserializeOwned,
+    F: Future<Output = SqlResult<T>>,
+{
+    let cached: Option<String> = redis.get(&key).await?;
+    if let Some(Ok(value)) = cached.map(|c| serde_json::from_str(&c)) {
+        return Ok(value);
+    }
+
+    let value = load.await?;
+    redis
+        .set_ex::<_, _, ()>(key, serde_json::to_string(&value)?, 10 * 60)
+        .await
+        .ok();
+
+    Ok(value)
+}
+
+/// Gets the settings for a channel, cached in redis.
+pub async fn get_command_settings(
+    user_id: &str,
+    pool: &PgPool,
+    redis: &mut RedisConn,
+) -> AnyResult<CommandSettings> {
+    get_cached(
+        settings_key(user_id),
+        redis,
+        CommandSettings::get(user_id, pool),
+    )
+    .await
+}
+
+/// Gets the custom commands of a channel, cached in redis.
+pub async fn get_custom_commands(
+    user_id: &str,
+    pool: &PgPool,
+    redis: &mut RedisConn,
+) -> AnyResult<Vec<CustomCommand>> {
+    get_cached(
+        custom_commands_key(user_id),
+        redis,
+        CustomCommand::get_all_for_user(user_id, pool),
+    )
+    .await
+}
+
+/// Gets the locale of a channel, cached in redis.
+pub async fn get_locale(
+    user_id: &str,
+    pool: &PgPool,
+    redis: &mut RedisConn,
+) -> AnyResult<String> {
+    get_cached(locale_key(user_id), redis, User::get_locale(user_id, pool))
+        .await
+}
+
+pub async fn invalidate_command_settings(
+    user_id: &str,
+    redis: &mut RedisConn,
+) -> AnyResult<()> {
+    redis.del::<_, ()>(settings_key(user_id)).await?;
+    Ok(())
+}
+
+pub async fn invalidate_custom_commands(
+    user_id: &str,
+    redis: &mut RedisConn,
+) -> AnyResult<()> {
+    redis.del::<_, ()>(custom_commands_key(user_id)).await?;
+    Ok(())
+}
+
+pub async fn invalidate_locale(
+    user_id: &str,
+    redis: &mut RedisConn,
+) -> AnyResult<()> {
+    redis.del::<_, ()>(locale_key(user_id)).await?;
+    Ok(())
+}
+
+/// Removes the channel's prefix from the message.
+/// Returns `None` if the message isn't a command.
+pub fn strip_prefix<'a>(
+    message: &'a str,
+    settings: &CommandSettings,
+) -> Option<&'a str> {
+    let prefix = settings.prefix.as_deref().unwrap_or(&CONFIG.bot.prefix);
+    message.strip_prefix(prefix)
+}
+
+/// Resolves the (lowercase) name of a command to the name of the builtin command.
+pub fn resolve_command(
+    name: &str,
+    settings: &CommandSettings,
+) -> Option<&'static str> {
+    let default = CommandOverride::default();
+    let mut builtin = None;
+    for names in COMMANDS {
+        let cmd = names[0];
+        if PROTECTED_COMMANDS.contains(&cmd) {
+            if names.contains(&name) {
+                return Some(cmd);
+            }
+            continue;
+        }
+
+        let opts = settings.commands.get(cmd).unwrap_or(&default);
+        if !opts.enabled {
+            continue;
+        }
+        // aliases take precedence over the default names of other commands
+        if opts.aliases.iter().any(|a| a == name) {
+            return Some(cmd);
+        }
+        if opts.default_names && names.contains(&name) {
+            builtin = Some(cmd);
+        }
+    }
+
+    builtin
+}
+
+fn is_protected_name(name: &str) -> bool {
+    COMMANDS.iter().any(|names| {
+        PROTECTED_COMMANDS.contains(&names[0]) && names.contains(&name)
+    })
+}
+
+pub fn verify_command_settings(settings: &CommandSettings) -> AnyResult<()> {
+    if let Some(prefix) = &settings.prefix {
+        if prefix.is_empty() || prefix.chars().count() > 8 {
+            bail!("The prefix must be between 1 and 8 characters long");
+        }
+        if prefix.contains(char::is_whitespace) {
+            bail!("The prefix can't contain whitespace");
+        }
+    }
+
+    let mut seen = HashSet::new();
+    for (name, opts) in &settings.commands {
+        if PROTECTED_COMMANDS.contains(&name.as_str()) {
+            bail!("The command {name} can't be configured");
+        }
+        if !COMMANDS.iter().any(|names| names[0] == name.as_str()) {
+            bail!("There's no command named {name}");
+        }
+        if [opts.user_cooldown, opts.channel_cooldown]
+            .into_iter()
+            .flatten()
+            .any(|c| c > 60 * 60)
+        {
+            bail!("The cooldowns of {name} can't be longer than an hour");
+        }
+        if opts.aliases.len() > 10 {
+            bail!("The command {name} can't have more than 10 aliases");
+        }
+        for alias in &opts.aliases {
+            if alias.is_empty() || alias.chars().count() > 25 {
+                bail!("Aliases must be between 1 and 25 characters long");
+            }
+            if alias.contains(char::is_whitespace) {
+                bail!("The alias {alias} can't contain whitespace");
+            }
+            if alias.to_lowercase() != *alias {
+                bail!("The alias {alias} must be lowercase");
+            }
+            if is_protected_name(alias) {
+                bail!("The alias {alias} is reserved");
+            }
+            if !seen.insert(alias.as_str()) {
+                bail!("The alias {alias} is used more than once");
+            }
+        }
+    }
+
+    Ok(())
+}
+
+pub fn verify_custom_command(
+    name: &str,
+    response: &str,
+    cooldowns: [i32; 2],
+) -> AnyResult<()> {
+    if name.is_empty() || name.chars().count() > 25 {
+        bail!("The name must be between 1 and 25 characters long");
+    }
+    if name.contains(char::is_whitespace) {
+        bail!("The name can't contain whitespace");
+    }
+    if name.to_lowercase() != name {
+        bail!("The name must be lowercase");
+    }
+    if COMMANDS.iter().any(|names| names.contains(&name)) {
+        bail!("There's already a builtin command named {name}");
+    }
+    if cooldowns.iter().any(|c| !(0..=60 * 60).contains(c)) {
+        bail!("The cooldowns must be between 0 seconds and an hour");
+    }
+    verify_template(response, CUSTOM_COMMAND_VARIABLES, 500)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use models::command_settings::PermissionLevel;
+    use std::collections::HashMap;
+
+    fn settings(commands: &[(&str, CommandOverride)]) -> CommandSettings {
+        CommandSettings {
+            prefix: None,
+            commands: commands
+                .iter()
+                .map(|(name, opts)| (name.to_string(), opts.clone()))
+                .collect(),
+        }
+    }
+
+    fn aliases(aliases: &[&str]) -> CommandOverride {
+        CommandOverride {
+            aliases: aliases.iter().map(|a| a.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn resolves_default_names() {
+        let settings = settings(&[]);
+        assert_eq!(resolve_command("ping", &settings), Some("ping"));
+        assert_eq!(resolve_command("ei", &settings), Some("emoteinfo"));
+        assert_eq!(resolve_command("dbg", &settings), Some("debug"));
+        assert_eq!(resolve_command("nope", &settings), None);
+    }
+
+    #[test]
+    fn aliases_take_precedence() {
+        let settings = settings(&[("about", aliases(&["ping"]))]);
+        assert_eq!(resolve_command("ping", &settings), Some("about"));
+        assert_eq!(resolve_command("bing", &settings), Some("ping"));
+    }
+
+    #[test]
+    fn disabled_commands_and_default_names() {
+        let disabled = CommandOverride {
+            enabled: false,
+            ..aliases(&["pong"])
+        };
+        let no_defaults = CommandOverride {
+            default_names: false,
+            ..aliases(&["cur"])
+        };
+        let settings = settings(&[("ping", disabled), ("emotes", no_defaults)]);
+        assert_eq!(resolve_command("ping", &settings), None);
+        assert_eq!(resolve_command("pong", &settings), None);
+        assert_eq!(resolve_command("ce", &settings), None);
+        assert_eq!(resolve_command("cur", &settings), Some("emotes"));
+    }
+
+    #[test]
+    fn emote_info_is_separate() {
+        let disabled = CommandOverride {
+            enabled: false,
+            ..Default::default()
+        };
+        let settings =
+            settings(&[("emote", disabled), ("emoteinfo", aliases(&["info"]))]);
+        assert_eq!(resolve_command("emote", &settings), None);
+        assert_eq!(resolve_command("info", &settings), Some("emoteinfo"));
+        assert_eq!(resolve_command("ei", &settings), Some("emoteinfo"));
+    }
+
+    #[test]
+    fn protected_commands_ignore_settings() {
+        let settings = settings(&[(
+            "debug",
+            CommandOverride {
+                enabled: false,
+                ..Default::default()
+            },
+        )]);
+        assert_eq!(resolve_command("debug", &settings), Some("debug"));
+    }
+
+    #[test]
+    fn verifies_prefixes() {
+        let with_prefix = |prefix: &str| CommandSettings {
+            prefix: Some(prefix.to_owned()),
+            commands: HashMap::new(),
+        };
+        assert!(verify_command_settings(&with_prefix("!")).is_ok());
+        assert!(verify_command_settings(&with_prefix("ßßßßßßßß")).is_ok());
+        assert!(verify_command_settings(&with_prefix("")).is_err());
+        assert!(verify_command_settings(&with_prefix("123456789")).is_err());
+        assert!(verify_command_settings(&with_prefix("! ")).is_err());
+    }
+
+    #[test]
+    fn verifies_command_overrides() {
+        let ok = |opts: CommandOverride| {
+            verify_command_settings(&settings(&[("ping", opts)])).is_ok()
+        };
+        assert!(ok(aliases(&["p", "pong"])));
+        assert!(ok(CommandOverride {
+            permission: Some(PermissionLevel::Moderator),
+            user_cooldown: Some(60 * 60),
+            channel_cooldown: Some(0),
+            ..Default::default()
+        }));
+        assert!(!ok(CommandOverride {
+            user_cooldown: Some(60 * 60 + 1),
+            ..Default::default()
+        }));
+        assert!(!ok(aliases(&["Pong"])));
+        assert!(!ok(aliases(&["po ng"])));
+        assert!(!ok(aliases(&[""])));
+        assert!(!ok(aliases(&["pong", "pong"])));
+        assert!(!ok(aliases(&["dbg"])));
+        assert!(!ok(aliases(&["a"; 11])));
+
+        assert!(verify_command_settings(&settings(&[(
+            "debug",
+            Default::default()
+        )]))
+        .is_err());
+        assert!(verify_command_settings(&settings(&[(
+            "nope",
+            Default::default()
+        )]))
+        .is_err());
+    }
+
+    #[test]
+    fn verifies_custom_commands() {
+        let ok = |name, response, cooldowns| {
+            verify_custom_command(name, response, cooldowns).is_ok()
+        };
+        assert!(ok("hi", "Hello {sender} ({slots.free})", [0, 5]));
+        assert!(!ok("Hi", "Hello", [0, 0]));
+        assert!(!ok("h i", "Hello", [0, 0]));
+        assert!(!ok("ping", "Hello", [0, 0]));
+        assert!(!ok("hi", "Hello", [-1, 0]));
+        assert!(!ok("hi", "Hello", [0, 60 * 60 + 1]));
+        assert!(!ok("hi", "Hello {unknown}", [0, 0]));
+        assert!(!ok("hi", " ", [0, 0]));
+    }
+}
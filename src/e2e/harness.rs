@@ -0,0 +1,334 @@
+use crate::{
+    actors::{
+        discord::DiscordActor, rewards::RewardsActor,
+        timed_mode::TimedModeActor, timeout::TimeoutActor,
+    },
+    e2e::mock::MockServer,
+    get_app_access_token,
+    services::{i18n::CATALOG, twitch},
+    RedisPool,
+};
+use actix::{Actor, Addr};
+use actix_web::http::Method;
+use config::CONFIG;
+use models::user::User;
+use serde_json::json;
+use sqlx::{
+    postgres::PgConnectOptions, Connection, Executor, PgConnection, PgPool,
+};
+use std::{
+    net::TcpListener,
+    process::{Child, Command, Stdio},
+    sync::{Arc, Once},
+    time::Duration,
+};
+use tokio::sync::{Mutex, MutexGuard, RwLock};
+
+/// Mocks for all external APIs. The config of the test process points to
+/// these servers.
+pub struct Mocks {
+    pub twitch: MockServer,
+    pub bttv: MockServer,
+    pub ffz: MockServer,
+    pub seven_tv: MockServer,
+    pub spotify: MockServer,
+    pub ivr: MockServer,
+    pub supinic: MockServer,
+}
+
+lazy_static::lazy_static! {
+    pub static ref MOCKS: Mocks = Mocks {
+        twitch: MockServer::start(),
+        bttv: MockServer::start(),
+        ffz: MockServer::start(),
+        seven_tv: MockServer::start(),
+        spotify: MockServer::start(),
+        ivr: MockServer::start(),
+        supinic: MockServer::start(),
+    };
+    /// The mocks are shared, so only one test can run at a time.
+    static ref SERIAL: Mutex<()> = Mutex::new(());
+}
+
+impl Mocks {
+    fn reset(&self) {
+        for server in [
+            &self.twitch,
+            &self.bttv,
+            &self.ffz,
+            &self.seven_tv,
+            &self.spotify,
+            &self.ivr,
+            &self.supinic,
+        ] {
+            server.reset();
+        }
+    }
+
+    fn config(&self) -> String {
+        let twitch = self.twitch.url();
+        let bttv = self.bttv.url();
+        let ffz = self.ffz.url();
+        let seven_tv = self.seven_tv.url();
+        let spotify = self.spotify.url();
+        let ivr = self.ivr.url();
+        let supinic = self.supinic.url();
+        format!(
+            r#"
+[db]
+url = "postgres://unused"
+
+[server]
+url = "http://localhost:8082"
+
+[redis]
+url = "redis://unused"
+
+[auth]
+jwt-secret = "secret"
+
+[twitch]
+client-id = "client-id"
+client-secret = "client-secret"
+login = "rewardmore"
+user-id = "1"
+
+[twitch.eventsub]
+secret = "secret"
+
+[emotes.bttv]
+jwt = "bttv"
+
+[emotes.ffz]
+session = "session"
+remember = "remember"
+
+[emotes.seven-tv]
+jwt = "seven-tv"
+user-id = "seven-tv"
+
+[spotify]
+client-id = "client-id"
+client-secret = "client-secret"
+
+[bot]
+
+[owner]
+id = "1"
+username = "rewardmore"
+
+[urls]
+twitch-helix = "{twitch}/helix"
+twitch-oauth2 = "{twitch}/oauth2"
+twitch-eventsub-ws = "ws://127.0.0.1:1"
+bttv = "{bttv}"
+bttv-cdn = "{bttv}/cdn"
+ffz-api = "{ffz}/v1"
+ffz = "{ffz}"
+ffz-cdn = "{ffz}/cdn"
+seven-tv = "{seven_tv}"
+seven-tv-cdn = "{seven_tv}/cdn"
+spotify-api = "{spotify}/v1"
+spotify-accounts = "{spotify}/accounts"
+ivr = "{ivr}/v2"
+supinic = "{supinic}/api"
+"#
+        )
+    }
+}
+
+/// Writes the config for the mocks and sets the global state `main` would
+/// set up.
+fn init() {
+    static INIT: Once = Once::new();
+    INIT.call_once(|| {
+        dotenv::dotenv().ok();
+        let _ = env_logger::builder().is_test(true).try_init();
+
+        let path = std::env::temp_dir()
+            .join(format!("rewards-test-{}.toml", std::process::id()));
+        std::fs::write(&path, MOCKS.config())
+            .expect("Could not write test config");
+        std::env::set_var("REWARDS_CONFIG", &path);
+
+        lazy_static::initialize(&CONFIG);
+        CONFIG.urls.export_twitch_urls();
+        lazy_static::initialize(&CATALOG);
+
+        twitch::set_token(
+            serde_json::from_value(json!({
+                "access_token": "bot-token",
+                "refresh_token": "bot-refresh",
+                "created_at": "2026-01-01T00:00:00Z",
+                "expires_at": null,
+            }))
+            .unwrap(),
+        );
+    });
+}
+
+/// Everything needed to execute a redemption: a throwaway database with all
+/// migrations, a throwaway redis server and the [RewardsActor].
+///
+/// Needs `DATABASE_URL` (a user that can create databases) and
+/// `redis-server` in the `PATH`.
+pub struct TestEnv {
+    pub pool: PgPool,
+    pub redis: RedisPool,
+    pub rewards: Addr<RewardsActor>,
+    db_name: String,
+    redis_server: Child,
+    _serial: MutexGuard<'static, ()>,
+}
+
+impl TestEnv {
+    pub async fn start() -> Self {
+        init();
+        let serial = SERIAL.lock().await;
+        MOCKS.reset();
+        MOCKS.twitch.mock_ok(
+            Method::POST,
+            "/oauth2/token",
+            json!({
+                "access_token": "app-token",
+                "expires_in": 3600,
+                "token_type": "bearer",
+            }),
+        );
+        MOCKS.twitch.mock_ok(
+            Method::GET,
+            "/oauth2/validate",
+            json!({
+                "client_id": "client-id",
+                "scopes": [],
+                "expires_in": 3600,
+            }),
+        );
+
+        let (pool, db_name) = create_database().await;
+        let (redis_server, redis) = start_redis().await;
+
+        let app_access_token = get_app_access_token()
+            .await
+            .expect("Could not get app access token");
+        let rewards = RewardsActor {
+            db: pool.clone(),
+            redis: redis.clone(),
+            app_access_token: Arc::new(RwLock::new(app_access_token)),
+            timeout: TimeoutActor::new(redis.clone()).start(),
+            timed_mode: TimedModeActor::new(pool.clone()).start(),
+            discord: DiscordActor::new(pool.clone()).start(),
+        }
+        .start();
+
+        Self {
+            pool,
+            redis,
+            rewards,
+            db_name,
+            redis_server,
+            _serial: serial,
+        }
+    }
+
+    pub async fn create_user(&self, id: &str, name: &str) -> User {
+        let user = User {
+            id: id.to_owned(),
+            access_token: format!("{name}-token"),
+            refresh_token: format!("{name}-refresh"),
+            scopes: String::new(),
+            name: name.to_owned(),
+        };
+        user.create(&self.pool)
+            .await
+            .expect("Could not create user");
+        user
+    }
+
+    /// Drops the database. If a test fails, its database is kept for
+    /// inspection.
+    pub async fn finish(self) {
+        self.pool.close().await;
+        let mut admin = connect_admin().await;
+        admin
+            .execute(
+                format!("DROP DATABASE {} WITH (FORCE)", self.db_name).as_str(),
+            )
+            .await
+            .expect("Could not drop test database");
+    }
+}
+
+impl Drop for TestEnv {
+    fn drop(&mut self) {
+        let _ = self.redis_server.kill();
+        let _ = self.redis_server.wait();
+    }
+}
+
+fn admin_options() -> PgConnectOptions {
+    std::env::var("DATABASE_URL")
+        .expect("DATABASE_URL must be set")
+        .parse()
+        .expect("Invalid DATABASE_URL")
+}
+
+async fn connect_admin() -> PgConnection {
+    PgConnection::connect_with(&admin_options())
+        .await
+        .expect("Could not connect to postgres")
+}
+
+async fn create_database() -> (PgPool, String) {
+    let name =
+        format!("rewards_test_{}", hex::encode(rand::random::<[u8; 8]>()));
+    let mut admin = connect_admin().await;
+    admin
+        .execute(format!("CREATE DATABASE {name}").as_str())
+        .await
+        .expect("Could not create test database");
+
+    let pool = PgPool::connect_with(admin_options().database(&name))
+        .await
+        .expect("Could not connect to test database");
+    sqlx::migrate!()
+        .run(&pool)
+        .await
+        .expect("Could not run migrations");
+    (pool, name)
+}
+
+async fn start_redis() -> (Child, RedisPool) {
+    let port = TcpListener::bind("127.0.0.1:0")
+        .and_then(|l| l.local_addr())
+        .expect("Could not find a free port")
+        .port();
+    let server = Command::new("redis-server")
+        .args([
+            "--port",
+            &port.to_string(),
+            "--save",
+            "",
+            "--appendonly",
+            "no",
+        ])
+        .stdout(Stdio::null())
+        .spawn()
+        .expect("Could not start redis-server");
+
+    let pool = deadpool_redis::Config {
+        url: Some(format!("redis://127.0.0.1:{port}")),
+        connection: None,
+        ..Default::default()
+    }
+    .create_pool(Some(deadpool_redis::Runtime::Tokio1))
+    .expect("Could not create redis pool");
+
+    for _ in 0..50 {
+        if pool.get().await.is_ok() {
+            return (server, pool);
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    panic!("redis-server didn't start");
+}
@@ -0,0 +1,130 @@
+use actix_web::{
+    http::{Method, StatusCode},
+    web, App, HttpRequest, HttpResponse, HttpServer,
+};
+use serde_json::{json, Value};
+use std::{
+    collections::HashMap,
+    net::TcpListener,
+    sync::{Mutex, MutexGuard, PoisonError},
+};
+
+/// A request received by a [MockServer].
+#[derive(Debug, Clone)]
+pub struct MockRequest {
+    pub method: Method,
+    pub path: String,
+    pub query: String,
+    pub body: String,
+}
+
+impl MockRequest {
+    pub fn json(&self) -> Value {
+        serde_json::from_str(&self.body).unwrap_or_default()
+    }
+}
+
+#[derive(Default)]
+struct MockState {
+    responses: HashMap<(Method, String), (StatusCode, String)>,
+    requests: Vec<MockRequest>,
+}
+
+/// HTTP server standing in for an external API.
+/// Responses are matched by method and path, everything else is a 404.
+pub struct MockServer {
+    url: String,
+    state: web::Data<Mutex<MockState>>,
+}
+
+impl MockServer {
+    /// Starts the server on its own thread, so it outlives the actix system
+    /// of a single test.
+    pub fn start() -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0")
+            .expect("Could not bind mock server");
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let state = web::Data::new(Mutex::new(MockState::default()));
+
+        let server_state = state.clone();
+        std::thread::spawn(move || {
+            actix_web::rt::System::new().block_on(async move {
+                HttpServer::new(move || {
+                    App::new()
+                        .app_data(server_state.clone())
+                        .default_service(web::to(respond))
+                })
+                .workers(1)
+                .listen(listener)
+                .expect("Could not listen on mock server")
+                .run()
+                .await
+            })
+        });
+
+        Self { url, state }
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    pub fn mock(&self, method: Method, path: &str, status: u16, body: Value) {
+        self.lock().responses.insert(
+            (method, path.to_owned()),
+            (StatusCode::from_u16(status).unwrap(), body.to_string()),
+        );
+    }
+
+    pub fn mock_ok(&self, method: Method, path: &str, body: Value) {
+        self.mock(method, path, 200, body)
+    }
+
+    /// All requests to `path` in the order they were received.
+    pub fn requests(&self, method: Method, path: &str) -> Vec<MockRequest> {
+        self.lock()
+            .requests
+            .iter()
+            .filter(|r| r.method == method && r.path == path)
+            .cloned()
+            .collect()
+    }
+
+    pub fn reset(&self) {
+        let mut state = self.lock();
+        state.responses.clear();
+        state.requests.clear();
+    }
+
+    fn lock(&self) -> MutexGuard<'_, MockState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+async fn respond(
+    req: HttpRequest,
+    body: web::Bytes,
+    state: web::Data<Mutex<MockState>>,
+) -> HttpResponse {
+    let mut state = state.lock().unwrap_or_else(PoisonError::into_inner);
+    state.requests.push(MockRequest {
+        method: req.method().clone(),
+        path: req.path().to_owned(),
+        query: req.query_string().to_owned(),
+        body: String::from_utf8_lossy(&body).into_owned(),
+    });
+
+    match state
+        .responses
+        .get(&(req.method().clone(), req.path().to_owned()))
+    {
+        Some((status, body)) => HttpResponse::build(*status)
+            .content_type("application/json")
+            .body(body.clone()),
+        None => HttpResponse::NotFound().json(json!({
+            "error": "Not Found",
+            "status": 404,
+            "message": format!("{} {} isn't mocked", req.method(), req.path()),
+        })),
+    }
+}
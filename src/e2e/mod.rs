@@ -0,0 +1,8 @@
+//! End-to-end tests: redemptions are executed by the
+//! [RewardsActor](crate::actors::rewards::RewardsActor) against mocked APIs,
+//! a throwaway database and a throwaway redis server.
+
+mod harness;
+mod mock;
+mod redemptions;
+mod revoke;
@@ -0,0 +1,803 @@
+use crate::{
+    e2e::harness::{TestEnv, MOCKS},
+    services::rewards::{approval, redemption::execute_redemption, Redemption},
+};
+use actix_web::http::Method;
+use models::{
+    blocklist::{BlocklistEntry, BlocklistKind},
+    emote::SlotPlatform,
+    log_entry::{LogEntry, LogEntryType, LogFilter},
+    pending_approval::PendingApproval,
+    reward::{
+        EmoteSearchMode, RefundPolicy, Reward, RewardData, SlotRewardData,
+        SwapRewardData, TimeoutRewardData,
+    },
+    slot::Slot,
+    swap_emote::SwapEmote,
+    user::User,
+};
+use serde_json::json;
+use sqlx::types::Json;
+use std::time::Instant;
+
+const BROADCASTER_ID: &str = "1001";
+const BTTV_ID: &str = "5f0000000000000000000001";
+const REWARD_ID: &str = "reward-1";
+const REDEMPTION_ID: &str = "redemption-1";
+
+const NEW_EMOTE: &str = "5f00000000000000000000aa";
+const OLD_EMOTE: &str = "5f00000000000000000000bb";
+
+const REDEMPTIONS_PATH: &str =
+    "/helix/channel_points/custom_rewards/redemptions";
+const CHAT_PATH: &str = "/helix/chat/messages";
+
+fn redemption(input: &str) -> Redemption {
+    serde_json::from_value(json!({
+        "id": REDEMPTION_ID,
+        "broadcaster_user_id": BROADCASTER_ID,
+        "broadcaster_user_login": "broadcaster",
+        "broadcaster_user_name": "Broadcaster",
+        "user_id": "2002",
+        "user_login": "viewer",
+        "user_name": "Viewer",
+        "user_input": input,
+        "status": "unfulfilled",
+        "reward": {
+            "id": REWARD_ID,
+            "title": "Swap an emote",
+            "cost": 100,
+            "prompt": "",
+        },
+        "redeemed_at": "2026-10-18T12:00:00Z",
+    }))
+    .unwrap()
+}
+
+/// Creates the broadcaster (with a BTTV account) and the reward.
+async fn create_reward(env: &TestEnv, data: RewardData) -> (User, Reward) {
+    let user = env.create_user(BROADCASTER_ID, "broadcaster").await;
+    User::set_bttv_id(BROADCASTER_ID, BTTV_ID, &env.pool)
+        .await
+        .unwrap();
+
+    let reward = Reward {
+        id: REWARD_ID.to_owned(),
+        user_id: BROADCASTER_ID.to_owned(),
+        data: Json(data),
+        live_delay: None,
+        auto_accept: true,
+        refund_on_error: RefundPolicy::Always,
+        reply_templates: Json(Default::default()),
+    };
+    reward.create(&env.pool).await.unwrap();
+
+    (user, reward)
+}
+
+async fn create_bttv_swap(env: &TestEnv, limit: Option<u16>) -> (User, Reward) {
+    create_reward(
+        env,
+        RewardData::BttvSwap(SwapRewardData {
+            limit,
+            allow_unlisted: true,
+            max_per_redemption: None,
+            search: None,
+            reply: true,
+            require_approval: false,
+        }),
+    )
+    .await
+}
+
+fn mock_bttv_channel(max_emotes: usize, shared_emotes: serde_json::Value) {
+    MOCKS.bttv.mock_ok(
+        Method::GET,
+        "/account/dashboards",
+        json!([{ "id": BTTV_ID, "limits": { "liveEmotes": max_emotes } }]),
+    );
+    MOCKS.bttv.mock_ok(
+        Method::GET,
+        &format!("/users/{BTTV_ID}"),
+        json!({
+            "id": BTTV_ID,
+            "channelEmotes": [],
+            "sharedEmotes": shared_emotes,
+        }),
+    );
+}
+
+fn mock_twitch_replies() {
+    MOCKS.twitch.mock_ok(
+        Method::POST,
+        CHAT_PATH,
+        json!({ "data": [{ "message_id": "msg", "is_sent": true }] }),
+    );
+    MOCKS.twitch.mock_ok(
+        Method::PATCH,
+        REDEMPTIONS_PATH,
+        json!({ "data": [] }),
+    );
+}
+
+/// The redemption status that was sent to Twitch.
+fn sent_status() -> Option<String> {
+    let updates = MOCKS.twitch.requests(Method::PATCH, REDEMPTIONS_PATH);
+    let update = updates.last()?;
+    assert!(update.query.contains(&format!("id={REDEMPTION_ID}")));
+    update.json()["status"].as_str().map(ToOwned::to_owned)
+}
+
+/// The log entries of the broadcaster with one of `types`, newest first.
+async fn logs(env: &TestEnv, types: Vec<LogEntryType>) -> Vec<LogEntry> {
+    let filter = LogFilter {
+        types: Some(types),
+        since: None,
+        until: None,
+    };
+    LogEntry::query(BROADCASTER_ID, &filter, None, 50, &env.pool)
+        .await
+        .unwrap()
+}
+
+fn sent_messages() -> Vec<String> {
+    MOCKS
+        .twitch
+        .requests(Method::POST, CHAT_PATH)
+        .into_iter()
+        .filter_map(|r| r.json()["message"].as_str().map(ToOwned::to_owned))
+        .collect()
+}
+
+#[actix_web::test]
+async fn bttv_swap_adds_emote() {
+    let env = TestEnv::start().await;
+    let (user, reward) = create_bttv_swap(&env, Some(2)).await;
+    mock_bttv_channel(10, json!([]));
+    MOCKS.bttv.mock_ok(
+        Method::GET,
+        &format!("/emotes/{NEW_EMOTE}"),
+        json!({ "id": NEW_EMOTE, "code": "NewEmote" }),
+    );
+    let add_path = format!("/emotes/{NEW_EMOTE}/shared/{BTTV_ID}/{BTTV_ID}");
+    MOCKS.bttv.mock_ok(Method::PUT, &add_path, json!({}));
+    mock_twitch_replies();
+
+    execute_redemption(
+        &env.pool,
+        &env.rewards,
+        redemption(&format!("https://betterttv.com/emotes/{NEW_EMOTE}")),
+        user,
+        reward,
+        Instant::now(),
+    )
+    .await;
+
+    assert_eq!(MOCKS.bttv.requests(Method::PUT, &add_path).len(), 1);
+    let swapped =
+        SwapEmote::all_for_reward(BROADCASTER_ID, REWARD_ID, &env.pool)
+            .await
+            .unwrap();
+    assert_eq!(swapped.len(), 1);
+    assert_eq!(swapped[0].emote_id, NEW_EMOTE);
+    assert_eq!(swapped[0].name, "NewEmote");
+    assert_eq!(swapped[0].added_by, "viewer");
+
+    let messages = sent_messages();
+    assert_eq!(messages.len(), 1);
+    assert!(messages[0].contains("Added NewEmote"), "{}", messages[0]);
+    assert_eq!(sent_status().as_deref(), Some("FULFILLED"));
+
+    env.finish().await;
+}
+
+#[actix_web::test]
+async fn bttv_swap_removes_oldest_emote_at_limit() {
+    let env = TestEnv::start().await;
+    let (user, reward) = create_bttv_swap(&env, Some(1)).await;
+    SwapEmote::add_or_update(
+        BROADCASTER_ID,
+        OLD_EMOTE,
+        SlotPlatform::Bttv,
+        "OldEmote",
+        "someone",
+        REWARD_ID,
+        &env.pool,
+    )
+    .await
+    .unwrap();
+    mock_bttv_channel(10, json!([{ "id": OLD_EMOTE, "code": "OldEmote" }]));
+    MOCKS.bttv.mock_ok(
+        Method::GET,
+        &format!("/emotes/{NEW_EMOTE}"),
+        json!({ "id": NEW_EMOTE, "code": "NewEmote" }),
+    );
+    let add_path = format!("/emotes/{NEW_EMOTE}/shared/{BTTV_ID}/{BTTV_ID}");
+    let remove_path = format!("/emotes/{OLD_EMOTE}/shared/{BTTV_ID}/{BTTV_ID}");
+    MOCKS.bttv.mock_ok(Method::PUT, &add_path, json!({}));
+    MOCKS.bttv.mock_ok(Method::DELETE, &remove_path, json!({}));
+    mock_twitch_replies();
+
+    execute_redemption(
+        &env.pool,
+        &env.rewards,
+        redemption(NEW_EMOTE),
+        user,
+        reward,
+        Instant::now(),
+    )
+    .await;
+
+    assert_eq!(MOCKS.bttv.requests(Method::PUT, &add_path).len(), 1);
+    assert_eq!(MOCKS.bttv.requests(Method::DELETE, &remove_path).len(), 1);
+    let swapped =
+        SwapEmote::all_for_reward(BROADCASTER_ID, REWARD_ID, &env.pool)
+            .await
+            .unwrap();
+    assert_eq!(swapped.len(), 1);
+    assert_eq!(swapped[0].emote_id, NEW_EMOTE);
+
+    let messages = sent_messages();
+    assert_eq!(messages.len(), 1);
+    assert!(messages[0].contains("Removed OldEmote"), "{}", messages[0]);
+    assert_eq!(sent_status().as_deref(), Some("FULFILLED"));
+
+    env.finish().await;
+}
+
+#[actix_web::test]
+async fn failed_swap_is_refunded() {
+    let env = TestEnv::start().await;
+    let (user, reward) = create_bttv_swap(&env, Some(2)).await;
+    mock_bttv_channel(10, json!([]));
+    // the emote isn't mocked, so BTTV responds with 404
+    mock_twitch_replies();
+
+    execute_redemption(
+        &env.pool,
+        &env.rewards,
+        redemption(NEW_EMOTE),
+        user,
+        reward,
+        Instant::now(),
+    )
+    .await;
+
+    assert!(MOCKS
+        .bttv
+        .requests(
+            Method::PUT,
+            &format!("/emotes/{NEW_EMOTE}/shared/{BTTV_ID}/{BTTV_ID}")
+        )
+        .is_empty());
+    assert!(
+        SwapEmote::all_for_reward(BROADCASTER_ID, REWARD_ID, &env.pool)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+    assert_eq!(sent_messages().len(), 1);
+    assert_eq!(sent_status().as_deref(), Some("CANCELED"));
+
+    env.finish().await;
+}
+
+#[actix_web::test]
+async fn failed_swap_restores_removed_emote() {
+    let env = TestEnv::start().await;
+    let (user, reward) = create_bttv_swap(&env, Some(2)).await;
+    SwapEmote::add_or_update(
+        BROADCASTER_ID,
+        OLD_EMOTE,
+        SlotPlatform::Bttv,
+        "OldEmote",
+        "someone",
+        REWARD_ID,
+        &env.pool,
+    )
+    .await
+    .unwrap();
+    // the channel is full, so the old emote has to be removed first
+    mock_bttv_channel(1, json!([{ "id": OLD_EMOTE, "code": "OldEmote" }]));
+    MOCKS.bttv.mock_ok(
+        Method::GET,
+        &format!("/emotes/{NEW_EMOTE}"),
+        json!({ "id": NEW_EMOTE, "code": "NewEmote" }),
+    );
+    // adding the new emote isn't mocked, so BTTV responds with 404
+    let add_path = format!("/emotes/{NEW_EMOTE}/shared/{BTTV_ID}/{BTTV_ID}");
+    let old_path = format!("/emotes/{OLD_EMOTE}/shared/{BTTV_ID}/{BTTV_ID}");
+    MOCKS.bttv.mock_ok(Method::DELETE, &old_path, json!({}));
+    MOCKS.bttv.mock_ok(Method::PUT, &old_path, json!({}));
+    mock_twitch_replies();
+
+    execute_redemption(
+        &env.pool,
+        &env.rewards,
+        redemption(NEW_EMOTE),
+        user,
+        reward,
+        Instant::now(),
+    )
+    .await;
+
+    assert_eq!(MOCKS.bttv.requests(Method::PUT, &add_path).len(), 1);
+    assert_eq!(MOCKS.bttv.requests(Method::DELETE, &old_path).len(), 1);
+    assert_eq!(MOCKS.bttv.requests(Method::PUT, &old_path).len(), 1);
+    let swapped =
+        SwapEmote::all_for_reward(BROADCASTER_ID, REWARD_ID, &env.pool)
+            .await
+            .unwrap();
+    assert_eq!(swapped.len(), 1);
+    assert_eq!(swapped[0].emote_id, OLD_EMOTE);
+    assert_eq!(swapped[0].added_by, "someone");
+    assert_eq!(sent_status().as_deref(), Some("CANCELED"));
+
+    env.finish().await;
+}
+
+fn mock_new_emote() -> String {
+    MOCKS.bttv.mock_ok(
+        Method::GET,
+        &format!("/emotes/{NEW_EMOTE}"),
+        json!({ "id": NEW_EMOTE, "code": "NewEmote" }),
+    );
+    let add_path = format!("/emotes/{NEW_EMOTE}/shared/{BTTV_ID}/{BTTV_ID}");
+    MOCKS.bttv.mock_ok(Method::PUT, &add_path, json!({}));
+    add_path
+}
+
+#[actix_web::test]
+async fn blocked_emote_is_refunded() {
+    let env = TestEnv::start().await;
+    let (user, reward) = create_bttv_swap(&env, Some(2)).await;
+    BlocklistEntry::add(
+        SlotPlatform::Bttv,
+        BlocklistKind::Emote,
+        NEW_EMOTE,
+        Some("test"),
+        &env.pool,
+    )
+    .await
+    .unwrap();
+    mock_bttv_channel(10, json!([]));
+    let add_path = mock_new_emote();
+    mock_twitch_replies();
+
+    execute_redemption(
+        &env.pool,
+        &env.rewards,
+        redemption(NEW_EMOTE),
+        user,
+        reward,
+        Instant::now(),
+    )
+    .await;
+
+    assert!(MOCKS.bttv.requests(Method::PUT, &add_path).is_empty());
+    assert!(
+        SwapEmote::all_for_reward(BROADCASTER_ID, REWARD_ID, &env.pool)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+    assert_eq!(sent_status().as_deref(), Some("CANCELED"));
+
+    env.finish().await;
+}
+
+#[actix_web::test]
+async fn approved_swap_is_executed() {
+    let env = TestEnv::start().await;
+    let (user, _) = create_bttv_swap(&env, Some(2)).await;
+    mock_bttv_channel(10, json!([]));
+    let add_path = mock_new_emote();
+    mock_twitch_replies();
+
+    approval::request_approval(redemption(NEW_EMOTE), user, &env.pool).await;
+
+    let pending = PendingApproval::get_all_for_user(BROADCASTER_ID, &env.pool)
+        .await
+        .unwrap();
+    assert_eq!(pending.len(), 1);
+    assert_eq!(pending[0].redeemed_by, "viewer");
+    assert!(MOCKS.bttv.requests(Method::PUT, &add_path).is_empty());
+    assert_eq!(sent_status(), None);
+    let messages = sent_messages();
+    assert_eq!(messages.len(), 1);
+    assert!(messages[0].contains("waiting"), "{}", messages[0]);
+
+    approval::approve(
+        pending[0].id,
+        BROADCASTER_ID,
+        "moderator",
+        &env.pool,
+        &env.rewards,
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(MOCKS.bttv.requests(Method::PUT, &add_path).len(), 1);
+    assert_eq!(
+        SwapEmote::all_for_reward(BROADCASTER_ID, REWARD_ID, &env.pool)
+            .await
+            .unwrap()
+            .len(),
+        1
+    );
+    assert!(PendingApproval::get_all_for_user(BROADCASTER_ID, &env.pool)
+        .await
+        .unwrap()
+        .is_empty());
+    assert_eq!(sent_status().as_deref(), Some("FULFILLED"));
+
+    env.finish().await;
+}
+
+#[actix_web::test]
+async fn denied_swap_is_refunded() {
+    let env = TestEnv::start().await;
+    let (user, _) = create_bttv_swap(&env, Some(2)).await;
+    mock_bttv_channel(10, json!([]));
+    let add_path = mock_new_emote();
+    mock_twitch_replies();
+
+    approval::request_approval(redemption(NEW_EMOTE), user, &env.pool).await;
+    let pending = PendingApproval::get_all_for_user(BROADCASTER_ID, &env.pool)
+        .await
+        .unwrap();
+    approval::deny(pending[0].id, BROADCASTER_ID, "moderator", &env.pool)
+        .await
+        .unwrap();
+
+    assert!(MOCKS.bttv.requests(Method::PUT, &add_path).is_empty());
+    assert_eq!(sent_status().as_deref(), Some("CANCELED"));
+    let messages = sent_messages();
+    assert_eq!(messages.len(), 2);
+    assert!(messages[1].contains("denied"), "{}", messages[1]);
+    // it can't be resolved twice
+    assert!(approval::deny(
+        pending[0].id,
+        BROADCASTER_ID,
+        "moderator",
+        &env.pool
+    )
+    .await
+    .is_err());
+
+    env.finish().await;
+}
+
+#[actix_web::test]
+async fn executed_swap_is_logged() {
+    let env = TestEnv::start().await;
+    let (user, reward) = create_bttv_swap(&env, Some(2)).await;
+    mock_bttv_channel(10, json!([]));
+    mock_new_emote();
+    mock_twitch_replies();
+
+    execute_redemption(
+        &env.pool,
+        &env.rewards,
+        redemption(NEW_EMOTE),
+        user,
+        reward,
+        Instant::now(),
+    )
+    .await;
+
+    let entries = logs(
+        &env,
+        vec![LogEntryType::RewardExecuted, LogEntryType::EmoteAdded],
+    )
+    .await;
+    assert_eq!(entries.len(), 2);
+    for entry in &entries {
+        assert_eq!(entry.actor.as_deref(), Some("viewer"));
+        assert_eq!(entry.reward_id.as_deref(), Some(REWARD_ID));
+    }
+    let added = entries
+        .iter()
+        .find(|e| e.kind == LogEntryType::EmoteAdded)
+        .unwrap();
+    assert_eq!(added.target.as_deref(), Some("NewEmote"));
+    let payload = &added.payload.as_ref().unwrap().0;
+    assert_eq!(payload["emote_id"], NEW_EMOTE);
+    assert!(logs(&env, vec![LogEntryType::RewardFailed])
+        .await
+        .is_empty());
+
+    env.finish().await;
+}
+
+#[actix_web::test]
+async fn failed_swap_is_logged() {
+    let env = TestEnv::start().await;
+    let (user, reward) = create_bttv_swap(&env, Some(2)).await;
+    mock_bttv_channel(10, json!([]));
+    mock_twitch_replies();
+
+    execute_redemption(
+        &env.pool,
+        &env.rewards,
+        redemption(NEW_EMOTE),
+        user,
+        reward,
+        Instant::now(),
+    )
+    .await;
+
+    let entries = logs(&env, vec![LogEntryType::RewardFailed]).await;
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].actor.as_deref(), Some("viewer"));
+    assert_eq!(entries[0].reward_id.as_deref(), Some(REWARD_ID));
+    let payload = &entries[0].payload.as_ref().unwrap().0;
+    assert_eq!(payload["refunded"], true);
+    assert!(logs(
+        &env,
+        vec![LogEntryType::RewardExecuted, LogEntryType::EmoteAdded]
+    )
+    .await
+    .is_empty());
+
+    env.finish().await;
+}
+
+/// Lets the reward search BTTV, which finds `NewEmote`.
+fn search_most_used(reward: &mut Reward) {
+    if let RewardData::BttvSwap(data) = &mut reward.data.0 {
+        data.search = Some(EmoteSearchMode::MostUsed);
+    }
+    MOCKS.bttv.mock_ok(
+        Method::GET,
+        "/emotes/shared/search",
+        json!([{ "id": NEW_EMOTE, "code": "NewEmote" }]),
+    );
+}
+
+#[actix_web::test]
+async fn most_used_search_ignores_case() {
+    let env = TestEnv::start().await;
+    let (user, mut reward) = create_bttv_swap(&env, Some(2)).await;
+    search_most_used(&mut reward);
+    mock_bttv_channel(10, json!([]));
+    let add_path = mock_new_emote();
+    mock_twitch_replies();
+
+    execute_redemption(
+        &env.pool,
+        &env.rewards,
+        redemption("newemote"),
+        user,
+        reward,
+        Instant::now(),
+    )
+    .await;
+
+    assert_eq!(MOCKS.bttv.requests(Method::PUT, &add_path).len(), 1);
+    assert_eq!(sent_status().as_deref(), Some("FULFILLED"));
+
+    env.finish().await;
+}
+
+#[actix_web::test]
+async fn most_used_search_rejects_other_names() {
+    let env = TestEnv::start().await;
+    let (user, mut reward) = create_bttv_swap(&env, Some(2)).await;
+    search_most_used(&mut reward);
+    mock_bttv_channel(10, json!([]));
+    let add_path = mock_new_emote();
+    mock_twitch_replies();
+
+    execute_redemption(
+        &env.pool,
+        &env.rewards,
+        redemption("NewEm"),
+        user,
+        reward,
+        Instant::now(),
+    )
+    .await;
+
+    assert!(MOCKS.bttv.requests(Method::PUT, &add_path).is_empty());
+    assert_eq!(sent_status().as_deref(), Some("CANCELED"));
+
+    env.finish().await;
+}
+
+async fn create_bttv_slots(env: &TestEnv, slots: usize) -> (User, Reward) {
+    let created = create_reward(
+        env,
+        RewardData::BttvSlot(SlotRewardData {
+            slots,
+            expiration: "1h".to_owned(),
+            allow_unlisted: true,
+            search: None,
+            reply: true,
+            require_approval: false,
+        }),
+    )
+    .await;
+    for _ in 0..slots {
+        Slot::create(BROADCASTER_ID, REWARD_ID, SlotPlatform::Bttv, &env.pool)
+            .await
+            .unwrap();
+    }
+    created
+}
+
+#[actix_web::test]
+async fn bttv_slot_adds_emote() {
+    let env = TestEnv::start().await;
+    let (user, reward) = create_bttv_slots(&env, 2).await;
+    mock_bttv_channel(10, json!([]));
+    let add_path = mock_new_emote();
+    mock_twitch_replies();
+
+    execute_redemption(
+        &env.pool,
+        &env.rewards,
+        redemption(NEW_EMOTE),
+        user,
+        reward,
+        Instant::now(),
+    )
+    .await;
+
+    assert_eq!(MOCKS.bttv.requests(Method::PUT, &add_path).len(), 1);
+    let available =
+        Slot::get_available_slots(BROADCASTER_ID, REWARD_ID, &env.pool)
+            .await
+            .unwrap();
+    assert_eq!(available.len(), 1);
+    let occupied = Slot::get_occupied(BROADCASTER_ID, &env.pool).await.unwrap();
+    assert_eq!(occupied.len(), 1);
+    assert_eq!(occupied[0].emote_id.as_deref(), Some(NEW_EMOTE));
+    assert_eq!(occupied[0].added_by.as_deref(), Some("viewer"));
+    assert!(occupied[0].expires.is_some());
+
+    let messages = sent_messages();
+    assert_eq!(messages.len(), 1);
+    assert!(messages[0].contains("Added NewEmote"), "{}", messages[0]);
+    assert_eq!(sent_status().as_deref(), Some("FULFILLED"));
+
+    env.finish().await;
+}
+
+#[actix_web::test]
+async fn full_slots_are_refunded() {
+    let env = TestEnv::start().await;
+    let (user, reward) = create_bttv_slots(&env, 0).await;
+    mock_bttv_channel(10, json!([]));
+    let add_path = mock_new_emote();
+    mock_twitch_replies();
+
+    execute_redemption(
+        &env.pool,
+        &env.rewards,
+        redemption(NEW_EMOTE),
+        user,
+        reward,
+        Instant::now(),
+    )
+    .await;
+
+    assert!(MOCKS.bttv.requests(Method::PUT, &add_path).is_empty());
+    assert_eq!(sent_messages().len(), 1);
+    assert_eq!(sent_status().as_deref(), Some("CANCELED"));
+
+    env.finish().await;
+}
+
+const TARGET_ID: &str = "3003";
+const BANS_PATH: &str = "/helix/moderation/bans";
+
+async fn create_timeout(env: &TestEnv, vip: bool) -> (User, Reward) {
+    create_reward(
+        env,
+        RewardData::Timeout(TimeoutRewardData {
+            duration: "10m".to_owned(),
+            vip,
+        }),
+    )
+    .await
+}
+
+fn mock_target(is_vip: bool) {
+    MOCKS.twitch.mock_ok(
+        Method::GET,
+        "/helix/users",
+        json!({ "data": [{
+            "id": TARGET_ID,
+            "login": "target",
+            "display_name": "Target",
+            "type": "",
+            "broadcaster_type": "",
+            "description": "",
+            "profile_image_url": "",
+            "offline_image_url": "",
+            "view_count": 0,
+            "created_at": "2020-01-01T00:00:00Z",
+        }] }),
+    );
+    let vips = if is_vip {
+        json!([{ "id": TARGET_ID, "login": "target" }])
+    } else {
+        json!([])
+    };
+    MOCKS.ivr.mock_ok(
+        Method::GET,
+        "/v2/twitch/modvip/broadcaster",
+        json!({ "mods": [], "vips": vips }),
+    );
+    MOCKS.twitch.mock_ok(
+        Method::POST,
+        BANS_PATH,
+        json!({ "data": [{
+            "broadcaster_id": BROADCASTER_ID,
+            "moderator_id": "1",
+            "user_id": TARGET_ID,
+            "created_at": "2026-10-18T12:00:00Z",
+            "end_time": "2026-10-18T12:10:00Z",
+        }] }),
+    );
+}
+
+#[actix_web::test]
+async fn timeout_bans_user() {
+    let env = TestEnv::start().await;
+    let (user, reward) = create_timeout(&env, true).await;
+    mock_target(false);
+    mock_twitch_replies();
+
+    execute_redemption(
+        &env.pool,
+        &env.rewards,
+        redemption("@Target"),
+        user,
+        reward,
+        Instant::now(),
+    )
+    .await;
+
+    assert_eq!(
+        MOCKS
+            .ivr
+            .requests(Method::GET, "/v2/twitch/modvip/broadcaster")
+            .len(),
+        1
+    );
+    let bans = MOCKS.twitch.requests(Method::POST, BANS_PATH);
+    assert_eq!(bans.len(), 1);
+    let ban = bans[0].json();
+    assert_eq!(ban["data"]["user_id"], TARGET_ID);
+    assert_eq!(ban["data"]["duration"], 600);
+    assert!(sent_messages().is_empty());
+    assert_eq!(sent_status().as_deref(), Some("FULFILLED"));
+
+    env.finish().await;
+}
+
+#[actix_web::test]
+async fn vips_are_not_timed_out() {
+    let env = TestEnv::start().await;
+    let (user, reward) = create_timeout(&env, true).await;
+    mock_target(true);
+    mock_twitch_replies();
+
+    execute_redemption(
+        &env.pool,
+        &env.rewards,
+        redemption("@Target"),
+        user,
+        reward,
+        Instant::now(),
+    )
+    .await;
+
+    assert!(MOCKS.twitch.requests(Method::POST, BANS_PATH).is_empty());
+    assert_eq!(sent_messages().len(), 1);
+    assert_eq!(sent_status().as_deref(), Some("CANCELED"));
+
+    env.finish().await;
+}
@@ -0,0 +1,34 @@
+use crate::e2e::harness::TestEnv;
+use models::user::User;
+
+#[actix_web::test]
+async fn revoked_user_is_skipped_until_login() {
+    let env = TestEnv::start().await;
+    let revoked = env.create_user("1001", "revoked").await;
+    env.create_user("1002", "active").await;
+
+    User::set_revoked(&revoked.id, &env.pool).await.unwrap();
+
+    let names = User::get_all_names(&env.pool).await.unwrap();
+    assert_eq!(names, vec!["active".to_owned()]);
+    let users = User::get_all(&env.pool).await.unwrap();
+    assert_eq!(users.len(), 1);
+    assert_eq!(users[0].id, "1002");
+    let unsubscribed = User::get_all_non_subscribers(
+        &env.pool,
+        "channel.channel_points_custom_reward_redemption.add",
+    )
+    .await
+    .unwrap();
+    assert_eq!(unsubscribed, vec!["1002".to_owned()]);
+    // the data is kept
+    assert!(User::get_by_id(&revoked.id, &env.pool).await.is_ok());
+
+    // logging in again clears the flag
+    revoked.create(&env.pool).await.unwrap();
+    let mut names = User::get_all_names(&env.pool).await.unwrap();
+    names.sort();
+    assert_eq!(names, vec!["active".to_owned(), "revoked".to_owned()]);
+
+    env.finish().await;
+}
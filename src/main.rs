@@ -9,6 +9,7 @@ use actix_web::{
 use anyhow::Error as AnyError;
 use log::LevelFilter;
 use sqlx::{postgres::PgConnectOptions, ConnectOptions, PgPool};
+use std::sync::Arc;
 use tokio::sync::RwLock;
 use twitch_api::{
     helix::Scope,
@@ -19,23 +20,28 @@ use actors::irc::JoinAllMessage;
 
 use crate::{
     actors::{
-        chat::ChatActor, db::DbActor, discord::DiscordActor, irc::IrcActor,
-        live::LiveActor, rewards::RewardsActor, slot::SlotActor,
-        supinic::SupinicActor, timeout::TimeoutActor,
+        approval::ApprovalActor, chat::ChatActor, db::DbActor,
+        discord::DiscordActor, eventsub_ws::EventSubWsActor, irc::IrcActor,
+        live::LiveActor, now_playing::NowPlayingActor, rewards::RewardsActor,
+        slot::SlotActor, song_queue::SongQueueActor, supinic::SupinicActor,
+        timed_mode::TimedModeActor, timeout::TimeoutActor,
         token_refresher::TokenRefresher,
     },
     middleware::useragent::UserAgentGuard,
     repositories::init_repositories,
     services::{
         eventsub::{
-            clear_invalid_subs, register_eventsub_for_all_unregistered,
+            clear_invalid_subs, register_authorization_revoke,
+            register_eventsub_for_all_unregistered, EventCtx,
         },
+        i18n::CATALOG,
         redemptions::clear_unfulfilled_redemptions,
         twitch::{self, requests::send_chat_message},
     },
+    util::result::ResultExt,
 };
 use actix_web_prom::PrometheusMetricsBuilder;
-use config::CONFIG;
+use config::{EventSubTransport, CONFIG};
 use models::user::User;
 
 pub type RedisPool = deadpool_redis::Pool;
@@ -43,6 +49,8 @@ pub type RedisConn = deadpool_redis::Connection;
 
 mod actors;
 mod chat;
+#[cfg(test)]
+mod e2e;
 mod extractors;
 mod middleware;
 mod repositories;
@@ -57,6 +65,10 @@ async fn main() -> std::io::Result<()> {
     log::info!("Initializing config");
 
     lazy_static::initialize(&CONFIG);
+    CONFIG.urls.export_twitch_urls();
+
+    log::info!("Loading locales");
+    lazy_static::initialize(&CATALOG);
 
     let prometheus = PrometheusMetricsBuilder::new("actix")
         .endpoint("/api/v1/metrics")
@@ -112,8 +124,13 @@ async fn main() -> std::io::Result<()> {
     let timeout_actor = TimeoutActor::new(redis_pool.clone()).start();
 
     let db_actor = DbActor::new(pg_pool.clone()).start();
-    let irc_actor =
-        IrcActor::new(chat_actor.recipient(), timeout_actor.clone()).start();
+    let irc_actor = IrcActor::new(
+        chat_actor.recipient(),
+        timeout_actor.clone(),
+        pg_pool.clone(),
+        redis_pool.clone(),
+    )
+    .start();
     let discord_user_actor = DiscordActor::new(pg_pool.clone()).start();
 
     SystemRegistry::set(
@@ -138,32 +155,65 @@ async fn main() -> std::io::Result<()> {
 
     TokenRefresher::new(pg_pool.clone(), db_actor).start();
     let live_actor = LiveActor::new(pg_pool.clone()).start();
+    let timed_mode_actor = TimedModeActor::new(pg_pool.clone()).start();
 
     let rewards_actor = RewardsActor {
         db: pg_pool.clone(),
         app_access_token: app_access_token.clone().into_inner(),
         timeout: timeout_actor.clone(),
+        timed_mode: timed_mode_actor,
         redis: redis_pool.clone(),
         discord: discord_user_actor,
     }
     .start();
+    SystemRegistry::set(
+        ApprovalActor::new(pg_pool.clone(), rewards_actor.clone()).start(),
+    );
+    SystemRegistry::set(SongQueueActor::new(pg_pool.clone()).start());
+    SystemRegistry::set(NowPlayingActor::new(pg_pool.clone()).start());
 
     SupinicActor.start();
 
+    let transport = CONFIG.twitch.eventsub.transport;
+    if transport == EventSubTransport::Websocket {
+        log::info!("Starting EventSub websocket");
+
+        EventSubWsActor::new(
+            pg_pool.clone(),
+            app_access_token.clone().into_inner(),
+            EventCtx {
+                pool: Arc::new(pg_pool.clone()),
+                app_access_token: app_access_token.clone().into_inner(),
+                executor: Arc::new(rewards_actor.clone()),
+                live_actor: live_actor.clone(),
+                timeout_actor: timeout_actor.clone(),
+                irc: irc_actor.clone(),
+            },
+        )
+        .start();
+    }
+
     let clear_pool = pg_pool.clone();
     let clear_token = app_access_token.clone();
     actix::spawn(async move {
-        log::info!("Clearing old rewards");
+        if transport == EventSubTransport::Webhook {
+            log::info!("Clearing old rewards");
 
-        clear_invalid_subs(&clear_token, &clear_pool)
-            .await
-            .expect("Could not clear invalid rewards");
+            clear_invalid_subs(&clear_token, &clear_pool)
+                .await
+                .expect("Could not clear invalid rewards");
 
-        log::info!("Registering eventsub callbacks");
+            log::info!("Registering eventsub callbacks");
 
-        register_eventsub_for_all_unregistered(&clear_token, &clear_pool)
-            .await
-            .expect("Could not register eventsub FeelsMan");
+            register_eventsub_for_all_unregistered(&clear_token, &clear_pool)
+                .await
+                .expect("Could not register eventsub FeelsMan");
+
+            // this fails if the subscription already exists
+            register_authorization_revoke(&clear_token)
+                .await
+                .dbg_if_err("register authorization revoke");
+        }
 
         log_err!(
             clear_unfulfilled_redemptions(&clear_pool).await,
@@ -154,13 +154,16 @@ fn redirect_to_twitch_auth() -> future::Ready<HttpResponse> {
         scope: [
             Scope::ChannelManageRedemptions,
             Scope::ChannelReadRedemptions,
+            Scope::ChannelManagePolls,
+            Scope::ChannelManagePredictions,
         ]
         .iter()
         .map(ToString::to_string)
         .join(" "),
     };
     let url = format!(
-        "https://id.twitch.tv/oauth2/authorize?{}",
+        "{}/authorize?{}",
+        CONFIG.urls.twitch_oauth2(),
         serde_qs::to_string(&params).expect("Failed to serialize")
     );
 
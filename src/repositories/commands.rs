@@ -0,0 +1,270 @@
+use crate::{
+    actors::irc::{IrcActor, SetPrefixMessage},
+    chat::settings::{
+        invalidate_command_settings, invalidate_custom_commands,
+        verify_command_settings, verify_custom_command, COMMANDS,
+    },
+    services::{jwt::JwtClaims, sql::get_user_or_editor},
+    RedisConn, RedisPool,
+};
+use actix::Addr;
+use actix_web::{
+    delete, get, patch, put,
+    web::{self, ServiceConfig},
+    HttpResponse, Result,
+};
+use config::CONFIG;
+use models::{
+    command_settings::CommandSettings, custom_command::CustomCommand,
+};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+
+const MAX_CUSTOM_COMMANDS: usize = 50;
+
+#[derive(Serialize)]
+struct BuiltinCommand {
+    name: &'static str,
+    default_names: &'static [&'static str],
+}
+
+#[derive(Serialize)]
+struct CommandSettingsResponse {
+    default_prefix: &'static str,
+    builtin: Vec<BuiltinCommand>,
+    settings: CommandSettings,
+}
+
+#[get("/{broadcaster_id}")]
+async fn get_command_settings(
+    claims: JwtClaims,
+    broadcaster_id: web::Path<String>,
+    pool: web::Data<PgPool>,
+) -> Result<HttpResponse> {
+    let user = get_user_or_editor(&claims, &broadcaster_id, &pool).await?;
+    let settings = CommandSettings::get(&user.id, &pool).await?;
+
+    Ok(HttpResponse::Ok().json(CommandSettingsResponse {
+        default_prefix: &CONFIG.bot.prefix,
+        builtin: COMMANDS
+            .iter()
+            .map(|names| BuiltinCommand {
+                name: names[0],
+                default_names: names,
+            })
+            .collect(),
+        settings,
+    }))
+}
+
+#[put("/{broadcaster_id}")]
+async fn update_command_settings(
+    claims: JwtClaims,
+    broadcaster_id: web::Path<String>,
+    body: web::Json<CommandSettings>,
+    pool: web::Data<PgPool>,
+    redis: web::Data<RedisPool>,
+    irc: web::Data<Addr<IrcActor>>,
+) -> Result<HttpResponse> {
+    let user = get_user_or_editor(&claims, &broadcaster_id, &pool).await?;
+
+    verify_command_settings(&body).map_err(|e| {
+        errors::ErrorBadRequest(format!("Your settings are invalid: {}", e))
+    })?;
+    body.save(&user.id, &pool).await?;
+    let mut conn = get_redis(&redis).await?;
+    map_cache_err(invalidate_command_settings(&user.id, &mut conn).await)?;
+    irc.do_send(SetPrefixMessage {
+        channel_id: user.id,
+        prefix: body.into_inner().prefix,
+    });
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+#[delete("/{broadcaster_id}")]
+async fn reset_command_settings(
+    claims: JwtClaims,
+    broadcaster_id: web::Path<String>,
+    pool: web::Data<PgPool>,
+    redis: web::Data<RedisPool>,
+    irc: web::Data<Addr<IrcActor>>,
+) -> Result<HttpResponse> {
+    let user = get_user_or_editor(&claims, &broadcaster_id, &pool).await?;
+
+    CommandSettings::delete(&user.id, &pool).await?;
+    let mut conn = get_redis(&redis).await?;
+    map_cache_err(invalidate_command_settings(&user.id, &mut conn).await)?;
+    irc.do_send(SetPrefixMessage {
+        channel_id: user.id,
+        prefix: None,
+    });
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+#[derive(Deserialize)]
+struct CustomCommandBody {
+    name: String,
+    response: String,
+    #[serde(default = "default_user_cooldown")]
+    user_cooldown: i32,
+    #[serde(default = "default_channel_cooldown")]
+    channel_cooldown: i32,
+}
+
+fn default_user_cooldown() -> i32 {
+    5
+}
+
+fn default_channel_cooldown() -> i32 {
+    1
+}
+
+impl CustomCommandBody {
+    fn verify(&self, others: &[CustomCommand]) -> Result<()> {
+        verify_custom_command(
+            &self.name,
+            &self.response,
+            [self.user_cooldown, self.channel_cooldown],
+        )
+        .map_err(|e| {
+            errors::ErrorBadRequest(format!("Your command is invalid: {}", e))
+        })?;
+        if others.iter().any(|c| c.name == self.name) {
+            return Err(errors::ErrorBadRequest(format!(
+                "There's already a command named {}",
+                self.name
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+#[get("/{broadcaster_id}/custom")]
+async fn list_custom_commands(
+    claims: JwtClaims,
+    broadcaster_id: web::Path<String>,
+    pool: web::Data<PgPool>,
+) -> Result<HttpResponse> {
+    let user = get_user_or_editor(&claims, &broadcaster_id, &pool).await?;
+
+    Ok(HttpResponse::Ok()
+        .json(CustomCommand::get_all_for_user(&user.id, &pool).await?))
+}
+
+#[put("/{broadcaster_id}/custom")]
+async fn create_custom_command(
+    claims: JwtClaims,
+    broadcaster_id: web::Path<String>,
+    body: web::Json<CustomCommandBody>,
+    pool: web::Data<PgPool>,
+    redis: web::Data<RedisPool>,
+) -> Result<HttpResponse> {
+    let user = get_user_or_editor(&claims, &broadcaster_id, &pool).await?;
+
+    let existing = CustomCommand::get_all_for_user(&user.id, &pool).await?;
+    if existing.len() >= MAX_CUSTOM_COMMANDS {
+        return Err(errors::ErrorBadRequest(format!(
+            "You can't have more than {MAX_CUSTOM_COMMANDS} commands"
+        )));
+    }
+    body.verify(&existing)?;
+
+    let command = CustomCommand::create(
+        &user.id,
+        &body.name,
+        &body.response,
+        body.user_cooldown,
+        body.channel_cooldown,
+        &pool,
+    )
+    .await?;
+    let mut conn = get_redis(&redis).await?;
+    map_cache_err(invalidate_custom_commands(&user.id, &mut conn).await)?;
+
+    Ok(HttpResponse::Ok().json(command))
+}
+
+#[patch("/{broadcaster_id}/custom/{id}")]
+async fn update_custom_command(
+    claims: JwtClaims,
+    path: web::Path<(String, i32)>,
+    body: web::Json<CustomCommandBody>,
+    pool: web::Data<PgPool>,
+    redis: web::Data<RedisPool>,
+) -> Result<HttpResponse> {
+    let (broadcaster_id, id) = path.into_inner();
+    let user = get_user_or_editor(&claims, &broadcaster_id, &pool).await?;
+
+    let existing = CustomCommand::get_all_for_user(&user.id, &pool).await?;
+    let others = existing
+        .into_iter()
+        .filter(|c| c.id != id)
+        .collect::<Vec<_>>();
+    body.verify(&others)?;
+
+    let body = body.into_inner();
+    let command = CustomCommand {
+        id,
+        user_id: user.id,
+        name: body.name,
+        response: body.response,
+        user_cooldown: body.user_cooldown,
+        channel_cooldown: body.channel_cooldown,
+    };
+    if !command.update(&pool).await? {
+        return Err(errors::ErrorNotFound("The command doesn't exist"));
+    }
+    let mut conn = get_redis(&redis).await?;
+    map_cache_err(
+        invalidate_custom_commands(&command.user_id, &mut conn).await,
+    )?;
+
+    Ok(HttpResponse::Ok().json(command))
+}
+
+#[delete("/{broadcaster_id}/custom/{id}")]
+async fn delete_custom_command(
+    claims: JwtClaims,
+    path: web::Path<(String, i32)>,
+    pool: web::Data<PgPool>,
+    redis: web::Data<RedisPool>,
+) -> Result<HttpResponse> {
+    let (broadcaster_id, id) = path.into_inner();
+    let user = get_user_or_editor(&claims, &broadcaster_id, &pool).await?;
+
+    if !CustomCommand::delete(id, &user.id, &pool).await? {
+        return Err(errors::ErrorNotFound("The command doesn't exist"));
+    }
+    let mut conn = get_redis(&redis).await?;
+    map_cache_err(invalidate_custom_commands(&user.id, &mut conn).await)?;
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+async fn get_redis(redis: &RedisPool) -> Result<RedisConn> {
+    redis
+        .get()
+        .await
+        .map_err(|_| errors::ErrorInternalServerError("Redis is dead"))
+}
+
+fn map_cache_err(res: anyhow::Result<()>) -> Result<()> {
+    res.map_err(|e| {
+        log::warn!("Could not clear the command cache: {}", e);
+        errors::ErrorInternalServerError("Could not clear the cache")
+    })
+}
+
+pub fn init_command_routes(config: &mut ServiceConfig) {
+    config
+        .service(get_command_settings)
+        .service(update_command_settings)
+        .service(reset_command_settings)
+        .service(list_custom_commands)
+        .service(create_custom_command)
+        .service(update_custom_command)
+        .service(delete_custom_command);
+}
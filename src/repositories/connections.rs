@@ -2,12 +2,13 @@ use crate::services::{
     jwt::{decode_jwt, JwtClaims},
     spotify::{
         auth::{get_auth_url, SpotifyAuthResponse},
+        overlay::generate_token,
         requests::get_token,
     },
 };
 use actix_web::{
     cookie::CookieBuilder,
-    delete, get, patch,
+    delete, get, patch, post,
     web::{self, ServiceConfig},
     HttpRequest, HttpResponse, Result,
 };
@@ -22,6 +23,11 @@ struct ConnectionsList {
     spotify: Option<SpotifySettings>,
 }
 
+#[derive(Serialize)]
+struct OverlayToken {
+    token: String,
+}
+
 #[get("")]
 async fn list_connections(
     claims: JwtClaims,
@@ -108,6 +114,36 @@ async fn update_spotify_data(
     Ok(HttpResponse::NoContent().finish())
 }
 
+/// Creates a new secret for the now-playing overlay, the old URL stops
+/// working.
+#[post("/spotify/overlay")]
+async fn reset_spotify_overlay(
+    claims: JwtClaims,
+    pool: web::Data<PgPool>,
+) -> Result<HttpResponse> {
+    if SpotifyData::get_by_id(claims.user_id(), &pool)
+        .await?
+        .is_none()
+    {
+        return Err(errors::ErrorNotFound("Spotify isn't connected"));
+    }
+    let token = generate_token();
+    SpotifyData::set_overlay_token(claims.user_id(), Some(&token), &pool)
+        .await?;
+
+    Ok(HttpResponse::Ok().json(OverlayToken { token }))
+}
+
+#[delete("/spotify/overlay")]
+async fn remove_spotify_overlay(
+    claims: JwtClaims,
+    pool: web::Data<PgPool>,
+) -> Result<HttpResponse> {
+    SpotifyData::set_overlay_token(claims.user_id(), None, &pool).await?;
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
 #[delete("/spotify")]
 async fn remove_spotify_data(
     claims: JwtClaims,
@@ -124,5 +160,7 @@ pub fn init_connection_routes(config: &mut ServiceConfig) {
         .service(spotify_callback)
         .service(list_connections)
         .service(remove_spotify_data)
-        .service(update_spotify_data);
+        .service(update_spotify_data)
+        .service(reset_spotify_overlay)
+        .service(remove_spotify_overlay);
 }
@@ -1,10 +1,14 @@
 use crate::{
     log_discord,
-    services::{jwt::JwtClaims, twitch::get_many_users},
+    services::{jwt::JwtClaims, logs::log_change, twitch::get_many_users},
     RedisPool,
 };
 use actix_web::{delete, get, put, web, HttpResponse, Result};
-use models::editor::Editor;
+use models::{
+    editor::Editor,
+    log_entry::{LogEntry, LogEntryType},
+};
+use serde_json::json;
 use sqlx::PgPool;
 
 #[get("")]
@@ -61,6 +65,18 @@ async fn add_editor(
     editor: web::Path<String>,
 ) -> Result<HttpResponse> {
     Editor::add_editor(claims.user_id(), &editor, &pool).await?;
+    log_change(
+        &claims,
+        LogEntry::new(
+            claims.user_id(),
+            LogEntryType::EditorChanged,
+            format!("Added {} as an editor", editor.as_str()),
+        )
+        .target(&editor)
+        .payload(json!({ "action": "added" })),
+        &pool,
+    )
+    .await;
     log_discord!(
         "Editors",
         format!("✏ Added editor for {}", claims.user_id()),
@@ -77,6 +93,18 @@ async fn delete_editor(
     editor: web::Path<String>,
 ) -> Result<HttpResponse> {
     Editor::delete_editor(claims.user_id(), &editor, &pool).await?;
+    log_change(
+        &claims,
+        LogEntry::new(
+            claims.user_id(),
+            LogEntryType::EditorChanged,
+            format!("Removed {} as an editor", editor.as_str()),
+        )
+        .target(&editor)
+        .payload(json!({ "action": "removed" })),
+        &pool,
+    )
+    .await;
     log_discord!(
         "Editors",
         format!("🗑 Removed editor for {}", claims.user_id()),
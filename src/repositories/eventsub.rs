@@ -1,5 +1,3 @@
-use std::time::Duration;
-
 use actix::Addr;
 use actix_web::{
     post,
@@ -7,23 +5,20 @@ use actix_web::{
     HttpResponse, Result,
 };
 use sqlx::PgPool;
-use twitch_api::eventsub::{
-    user::UserAuthorizationRevokeV1Payload, Event, Message, Payload,
+use tokio::sync::RwLock;
+use twitch_api::{
+    eventsub::{Event, Message, Payload},
+    twitch_oauth2::AppAccessToken,
 };
 
 use crate::{
     actors::{
-        live::{self, LiveActor},
-        rewards::RewardsActor,
-        timeout::{self, TimeoutActor},
+        irc::IrcActor, live::LiveActor, rewards::RewardsActor,
+        timeout::TimeoutActor,
     },
     extractors::eventsub::EventsubPayload,
-    log_discord,
-    services::rewards::redemption::{
-        self, ReceiveRedemptionCtx, ReceiveRedemptionError,
-    },
+    services::eventsub::{handle_event, EventCtx},
 };
-use models::user::User;
 
 #[post("/reward")]
 async fn reward_redemption(
@@ -32,6 +27,8 @@ async fn reward_redemption(
     executor: web::Data<Addr<RewardsActor>>,
     live_actor: web::Data<Addr<LiveActor>>,
     timeout_actor: web::Data<Addr<TimeoutActor>>,
+    irc: web::Data<Addr<IrcActor>>,
+    app_access_token: web::Data<RwLock<AppAccessToken>>,
 ) -> Result<HttpResponse> {
     match payload.0 {
         // verification
@@ -50,105 +47,26 @@ async fn reward_redemption(
         | Event::ChannelModerateV2(Payload {
             message: Message::VerificationRequest(req),
             ..
+        })
+        | Event::UserAuthorizationRevokeV1(Payload {
+            message: Message::VerificationRequest(req),
+            ..
         }) => Ok(HttpResponse::Ok().body(req.challenge)),
 
         // actual events
-        Event::ChannelPointsCustomRewardRedemptionAddV1(Payload {
-            message: Message::Notification(notification),
-            subscription,
-            ..
-        }) => {
-            // main path
-            let user = User::get_by_id(
-                notification.broadcaster_user_id.as_ref(),
-                &pool,
-            )
-            .await?;
-
-            log::info!(
-                "redemption: {:?} - sub: {:?}",
-                notification,
-                subscription
-            );
-
-            let ctx = ReceiveRedemptionCtx {
+        event => {
+            let ctx = EventCtx {
                 pool: pool.into_inner(),
+                app_access_token: app_access_token.into_inner(),
                 executor: executor.into_inner(),
-                user,
-                notification,
+                live_actor: live_actor.get_ref().clone(),
+                timeout_actor: timeout_actor.get_ref().clone(),
+                irc: irc.get_ref().clone(),
             };
-            actix_web::rt::spawn(async move {
-                match redemption::receive(ctx).await {
-                    Ok(_) => (),
-                    Err(ReceiveRedemptionError::NoReward) => (),
-                }
-            });
-
-            Ok(HttpResponse::Ok().finish())
-        }
+            handle_event(event, &ctx).await?;
 
-        Event::StreamOnlineV1(Payload {
-            message: Message::Notification(notification),
-            ..
-        }) => {
-            live_actor.do_send(live::LiveMessage(
-                notification.broadcaster_user_id.take(),
-            ));
-            Ok(HttpResponse::Ok().finish())
-        }
-        Event::StreamOfflineV1(Payload {
-            message: Message::Notification(notification),
-            ..
-        }) => {
-            live_actor.do_send(live::OfflineMessage(
-                notification.broadcaster_user_id.take(),
-            ));
-            Ok(HttpResponse::Ok().finish())
-        }
-
-        Event::ChannelModerateV2(Payload {
-            message: Message::Notification(notification),
-            ..
-        }) => {
-            match notification.action {
-                twitch_api::eventsub::channel::moderate::ActionV2::Untimeout(untimeout) => {
-                    timeout_actor
-                        .do_send(timeout::RemoveTimeoutMessage {
-                            channel_id: notification.broadcaster_user_id.take(),
-                            user_id: untimeout.user_id.take(),
-                            later: Duration::from_secs(0),
-                        });
-                },
-                _ => (),
-            }
-            Ok(HttpResponse::Ok().finish())
-        }
-
-        Event::UserAuthorizationRevokeV1(re) => {
-            log::warn!("auth revoke: {:?}", re);
-            log_discord!(
-                "Auth",
-                "Unhandled revocation",
-                "User Login/Id" = match re.message {
-                    Message::Notification(
-                        UserAuthorizationRevokeV1Payload {
-                            user_name: Some(login),
-                            ..
-                        },
-                    ) => login.take(),
-                    Message::Notification(
-                        UserAuthorizationRevokeV1Payload { user_id, .. },
-                    ) => user_id.take(),
-                    _ => "no login or id".to_string(),
-                }
-            );
-            // TODO
             Ok(HttpResponse::Ok().finish())
         }
-        other => {
-            log::warn!("unknown payload: {:?}", other);
-            Ok(HttpResponse::Ok().body("I can't handle that!"))
-        }
     }
 }
 
@@ -1,24 +1,150 @@
-use crate::services::{jwt::JwtClaims, sql::get_user_or_editor};
+use crate::services::{
+    emotes::events, jwt::JwtClaims, sql::get_user_or_editor,
+};
 use actix_web::{
-    delete, get, patch,
+    delete, get,
+    http::header::ContentDisposition,
+    patch,
     web::{self, ServiceConfig},
     HttpResponse, Result,
 };
-use models::{discord, log_entry::LogEntry};
+use models::{
+    discord,
+    emote_event::{EmoteEvent, EmoteEventFilter},
+    log_entry::{LogEntry, LogFilter},
+};
 use regex::Regex;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use sqlx::PgPool;
 use url::Url;
 
+const LOGS_PAGE_SIZE: i64 = 100;
+const LOGS_MAX_PAGE_SIZE: i64 = 500;
+
+#[derive(Deserialize)]
+struct LogsPage {
+    /// Only returns entries older than this id
+    before: Option<i32>,
+    limit: Option<i64>,
+}
+
+#[derive(Serialize)]
+struct LogsResponse {
+    entries: Vec<LogEntry>,
+    /// The cursor for the next page, `None` if this is the last page
+    next: Option<i32>,
+}
+
 #[get("/{target_id}")]
 async fn get_logs(
     claims: JwtClaims,
     target_id: web::Path<String>,
+    filter: web::Query<LogFilter>,
+    page: web::Query<LogsPage>,
+    pool: web::Data<PgPool>,
+) -> Result<HttpResponse> {
+    let user = get_user_or_editor(&claims, &target_id, &pool).await?;
+    let limit = page
+        .limit
+        .unwrap_or(LOGS_PAGE_SIZE)
+        .clamp(1, LOGS_MAX_PAGE_SIZE);
+
+    let entries =
+        LogEntry::query(&user.id, &filter, page.before, limit, &pool).await?;
+    let next = if entries.len() as i64 == limit {
+        entries.last().map(|e| e.id)
+    } else {
+        None
+    };
+
+    Ok(HttpResponse::Ok().json(LogsResponse { entries, next }))
+}
+
+const EMOTE_EVENTS_PAGE_SIZE: i64 = 50;
+const EMOTE_EVENTS_MAX_PAGE_SIZE: i64 = 200;
+const EMOTE_EVENTS_MAX_EXPORT: i64 = 10_000;
+
+#[derive(Deserialize)]
+struct EmoteEventsPage {
+    /// Only returns events older than this id
+    before: Option<i64>,
+    limit: Option<i64>,
+}
+
+#[derive(Serialize)]
+struct EmoteEventsResponse {
+    events: Vec<EmoteEvent>,
+    /// The cursor for the next page, `None` if this is the last page
+    next: Option<i64>,
+}
+
+#[get("/{target_id}/emotes")]
+async fn get_emote_events(
+    claims: JwtClaims,
+    target_id: web::Path<String>,
+    filter: web::Query<EmoteEventFilter>,
+    page: web::Query<EmoteEventsPage>,
+    pool: web::Data<PgPool>,
+) -> Result<HttpResponse> {
+    let user = get_user_or_editor(&claims, &target_id, &pool).await?;
+    let limit = page
+        .limit
+        .unwrap_or(EMOTE_EVENTS_PAGE_SIZE)
+        .clamp(1, EMOTE_EVENTS_MAX_PAGE_SIZE);
+
+    let events =
+        EmoteEvent::query(&user.id, &filter, page.before, limit, &pool).await?;
+    let next = if events.len() as i64 == limit {
+        events.last().map(|e| e.id)
+    } else {
+        None
+    };
+
+    Ok(HttpResponse::Ok().json(EmoteEventsResponse { events, next }))
+}
+
+#[derive(Deserialize, Default, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+enum ExportFormat {
+    #[default]
+    Json,
+    Csv,
+}
+
+#[derive(Deserialize)]
+struct ExportQuery {
+    #[serde(default)]
+    format: ExportFormat,
+}
+
+#[get("/{target_id}/emotes/export")]
+async fn export_emote_events(
+    claims: JwtClaims,
+    target_id: web::Path<String>,
+    filter: web::Query<EmoteEventFilter>,
+    query: web::Query<ExportQuery>,
     pool: web::Data<PgPool>,
 ) -> Result<HttpResponse> {
     let user = get_user_or_editor(&claims, &target_id, &pool).await?;
 
-    Ok(HttpResponse::Ok().json(LogEntry::get_for_user(&user.id, &pool).await?))
+    let events = EmoteEvent::query(
+        &user.id,
+        &filter,
+        None,
+        EMOTE_EVENTS_MAX_EXPORT,
+        &pool,
+    )
+    .await?;
+
+    Ok(match query.format {
+        ExportFormat::Json => HttpResponse::Ok()
+            .insert_header(ContentDisposition::attachment("emote-history.json"))
+            .json(events),
+        ExportFormat::Csv => HttpResponse::Ok()
+            .insert_header(ContentDisposition::attachment("emote-history.csv"))
+            .content_type("text/csv; charset=utf-8")
+            .body(events::to_csv(&events)),
+    })
 }
 
 #[derive(Deserialize)]
@@ -83,6 +209,8 @@ async fn delete_discord_url(
 pub fn init_log_routes(config: &mut ServiceConfig) {
     config
         .service(get_logs)
+        .service(get_emote_events)
+        .service(export_emote_events)
         .service(get_discord_settings)
         .service(set_discord_url)
         .service(delete_discord_url);
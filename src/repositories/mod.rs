@@ -1,16 +1,20 @@
 mod auth;
+mod commands;
 mod connections;
 mod editors;
 mod eventsub;
 mod logs;
+mod overlay;
+mod owner;
 mod rewards;
 mod user;
 
 use crate::repositories::{
-    auth::init_auth_routes, connections::init_connection_routes,
-    editors::init_editor_routes, eventsub::init_eventsub_routes,
-    logs::init_log_routes, rewards::init_rewards_routes,
-    user::init_user_routes,
+    auth::init_auth_routes, commands::init_command_routes,
+    connections::init_connection_routes, editors::init_editor_routes,
+    eventsub::init_eventsub_routes, logs::init_log_routes,
+    overlay::init_overlay_routes, owner::init_owner_routes,
+    rewards::init_rewards_routes, user::init_user_routes,
 };
 use actix_web::web;
 
@@ -21,6 +25,9 @@ pub fn init_repositories(config: &mut web::ServiceConfig) {
         .service(web::scope("/users").configure(init_user_routes))
         .service(web::scope("/editors").configure(init_editor_routes))
         .service(web::scope("/logs").configure(init_log_routes))
+        .service(web::scope("/commands").configure(init_command_routes))
         .service(web::scope("/connections").configure(init_connection_routes))
-        .service(web::scope("/eventsub").configure(init_eventsub_routes));
+        .service(web::scope("/eventsub").configure(init_eventsub_routes))
+        .service(web::scope("/overlay").configure(init_overlay_routes))
+        .service(web::scope("/owner").configure(init_owner_routes));
 }
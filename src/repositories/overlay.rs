@@ -0,0 +1,39 @@
+use crate::{
+    actors::now_playing::{NowPlayingActor, SubscribeNowPlaying},
+    services::spotify::overlay,
+};
+use actix::SystemService;
+use actix_web::{
+    get,
+    http::header::{CacheControl, CacheDirective},
+    web::{self, ServiceConfig},
+    HttpResponse, Result,
+};
+use models::spotify::SpotifyData;
+use sqlx::PgPool;
+
+/// Streams the current Spotify track as server-sent events.
+/// The token is the only authentication, so the URL can be used in OBS.
+#[get("/spotify/{token}")]
+async fn spotify_now_playing(
+    token: web::Path<String>,
+    pool: web::Data<PgPool>,
+) -> Result<HttpResponse> {
+    let broadcaster_id = SpotifyData::get_id_by_overlay_token(&token, &pool)
+        .await?
+        .ok_or_else(|| errors::ErrorNotFound("Unknown overlay"))?;
+
+    let subscription = NowPlayingActor::from_registry()
+        .send(SubscribeNowPlaying { broadcaster_id })
+        .await
+        .map_err(|_| errors::ErrorInternalServerError("Too much traffic"))?;
+
+    Ok(HttpResponse::Ok()
+        .content_type("text/event-stream")
+        .insert_header(CacheControl(vec![CacheDirective::NoCache]))
+        .streaming(overlay::event_stream(subscription)))
+}
+
+pub fn init_overlay_routes(config: &mut ServiceConfig) {
+    config.service(spotify_now_playing);
+}
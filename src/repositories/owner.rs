@@ -0,0 +1,82 @@
+use crate::services::jwt::JwtClaims;
+use actix_web::{delete, get, put, web, HttpResponse, Result};
+use config::CONFIG;
+use models::{
+    blocklist::{BlocklistEntry, BlocklistKind},
+    emote::SlotPlatform,
+};
+use serde::Deserialize;
+use sqlx::PgPool;
+
+fn ensure_owner(claims: &JwtClaims) -> Result<()> {
+    if claims.user_id() == CONFIG.owner.id {
+        Ok(())
+    } else {
+        Err(errors::ErrorForbidden("Only the owner can do this."))
+    }
+}
+
+#[get("/blocklist")]
+async fn get_blocklist(
+    claims: JwtClaims,
+    pool: web::Data<PgPool>,
+) -> Result<HttpResponse> {
+    ensure_owner(&claims)?;
+
+    Ok(HttpResponse::Ok().json(BlocklistEntry::get_all(&pool).await?))
+}
+
+#[derive(Deserialize)]
+struct AddBlocklistEntryBody {
+    platform: SlotPlatform,
+    kind: BlocklistKind,
+    value: String,
+    reason: Option<String>,
+}
+
+#[put("/blocklist")]
+async fn add_blocklist_entry(
+    claims: JwtClaims,
+    pool: web::Data<PgPool>,
+    body: web::Json<AddBlocklistEntryBody>,
+) -> Result<HttpResponse> {
+    ensure_owner(&claims)?;
+    let value = body.value.trim();
+    if value.is_empty() || value.len() > 64 {
+        return Err(errors::ErrorBadRequest("Invalid id"));
+    }
+
+    let entry = BlocklistEntry::add(
+        body.platform,
+        body.kind,
+        value,
+        body.reason.as_deref().filter(|r| !r.is_empty()),
+        &pool,
+    )
+    .await
+    .map_err(|_| errors::ErrorConflict("This entry might exist already."))?;
+
+    Ok(HttpResponse::Ok().json(entry))
+}
+
+#[delete("/blocklist/{id}")]
+async fn remove_blocklist_entry(
+    claims: JwtClaims,
+    pool: web::Data<PgPool>,
+    id: web::Path<i32>,
+) -> Result<HttpResponse> {
+    ensure_owner(&claims)?;
+
+    if !BlocklistEntry::remove(id.into_inner(), &pool).await? {
+        return Err(errors::ErrorNotFound("There's no such entry."));
+    }
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+pub fn init_owner_routes(config: &mut web::ServiceConfig) {
+    config
+        .service(get_blocklist)
+        .service(add_blocklist_entry)
+        .service(remove_blocklist_entry);
+}
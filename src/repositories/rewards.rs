@@ -1,11 +1,21 @@
 use crate::{
+    actors::approval::{ApprovalActor, ResolveApprovalMessage},
     log_discord,
     services::{
+        emotes::{
+            ban_rules::{rule_value_from_emote, verify_rule},
+            events,
+        },
         jwt::JwtClaims,
+        logs::log_change,
         rewards::{
+            error::is_user_error,
             save::save_reward,
-            verify::{verify_live_delay, verify_reward},
+            verify::{
+                verify_live_delay, verify_reply_templates, verify_reward,
+            },
         },
+        spotify::queue as song_queue,
         sql::get_user_or_editor,
         twitch::requests::{
             create_reward, delete_reward, get_reward_for_broadcaster_by_id,
@@ -14,9 +24,20 @@ use crate::{
     },
     RedisPool,
 };
+use actix::SystemService;
 use actix_web::{delete, get, patch, put, web, HttpResponse, Result};
-use models::reward::{Reward, RewardData};
+use models::{
+    ban_rule::{BanRule, BanRuleKind},
+    emote::SlotPlatform,
+    emote_event::EmoteEventReason,
+    log_entry::{LogEntry, LogEntryType},
+    pending_approval::PendingApproval,
+    reward::{RefundPolicy, ReplyTemplates, Reward, RewardData},
+    song_request::SongRequest,
+    user::User,
+};
 use serde::{Deserialize, Serialize};
+use serde_json::json;
 use sqlx::PgPool;
 use twitch_api::{
     helix::points::{
@@ -31,6 +52,10 @@ struct CreateRewardBody {
     pub data: RewardData,
     pub live_delay: Option<String>,
     pub auto_accept: bool,
+    #[serde(default)]
+    pub refund_on_error: RefundPolicy,
+    #[serde(default)]
+    pub reply_templates: ReplyTemplates,
 }
 
 #[derive(Deserialize, Debug)]
@@ -39,6 +64,10 @@ struct UpdateRewardBody {
     pub data: RewardData,
     pub live_delay: Option<String>,
     pub auto_accept: bool,
+    #[serde(default)]
+    pub refund_on_error: RefundPolicy,
+    #[serde(default)]
+    pub reply_templates: ReplyTemplates,
 }
 
 #[derive(Serialize)]
@@ -47,6 +76,8 @@ struct CustomRewardResponse {
     data: RewardData,
     live_delay: Option<String>,
     auto_accept: bool,
+    refund_on_error: RefundPolicy,
+    reply_templates: ReplyTemplates,
 }
 
 #[put("/{broadcaster_id}")]
@@ -72,14 +103,25 @@ async fn create(
     verify_live_delay(&body.live_delay).map_err(|e| {
         errors::ErrorBadRequest(format!("Your live delay is invalid: {}", e))
     })?;
-    verify_reward(&body.data, &broadcaster_id, &pool, &token)
-        .await
-        .map_err(|e| {
+    verify_reward(
+        &body.data,
+        body.twitch.is_user_input_required.unwrap_or_default(),
+        &broadcaster_id,
+        &pool,
+        &token,
+    )
+    .await
+    .map_err(|e| {
+        errors::ErrorBadRequest(format!("Your reward action is invalid: {}", e))
+    })?;
+    verify_reply_templates(&body.reply_templates, Some(&body.data)).map_err(
+        |e| {
             errors::ErrorBadRequest(format!(
-                "Your reward action is invalid: {}",
+                "Your reply templates are invalid: {}",
                 e
             ))
-        })?;
+        },
+    )?;
 
     let reward = create_reward(&broadcaster_id, body.twitch, &token).await?;
 
@@ -88,6 +130,8 @@ async fn create(
         body.data.clone(),
         body.live_delay,
         body.auto_accept,
+        body.refund_on_error,
+        body.reply_templates,
     );
     db_reward.create(&pool).await?;
 
@@ -124,6 +168,22 @@ async fn create(
         )));
     }
 
+    log_change(
+        &claims,
+        LogEntry::new(
+            &broadcaster_id,
+            LogEntryType::SettingsChanged,
+            format!("Created reward {}", reward.title),
+        )
+        .reward(reward.id.as_str())
+        .payload(json!({
+            "action": "created",
+            "type": body.data.to_string(),
+        })),
+        &pool,
+    )
+    .await;
+
     log_discord!(
         "Rewards",
         "🎉 Created reward",
@@ -139,6 +199,8 @@ async fn create(
         data: db_reward.data.0,
         live_delay: db_reward.live_delay,
         auto_accept: db_reward.auto_accept,
+        refund_on_error: db_reward.refund_on_error,
+        reply_templates: db_reward.reply_templates.0,
     }))
 }
 
@@ -167,14 +229,38 @@ async fn update(
     verify_live_delay(&body.live_delay).map_err(|e| {
         errors::ErrorBadRequest(format!("Your live delay is invalid: {}", e))
     })?;
-    verify_reward(&body.data, &broadcaster_id, &pool, &token)
-        .await
-        .map_err(|e| {
+    // the setting is only sent if it changed
+    let user_input_required = match body.twitch.is_user_input_required {
+        Some(required) => required,
+        None => {
+            get_reward_for_broadcaster_by_id(
+                &broadcaster_id,
+                &[reward_id.as_str().into()],
+                &token,
+            )
+            .await?
+            .is_user_input_required
+        }
+    };
+    verify_reward(
+        &body.data,
+        user_input_required,
+        &broadcaster_id,
+        &pool,
+        &token,
+    )
+    .await
+    .map_err(|e| {
+        errors::ErrorBadRequest(format!("Your reward action is invalid: {}", e))
+    })?;
+    verify_reply_templates(&body.reply_templates, Some(&body.data)).map_err(
+        |e| {
             errors::ErrorBadRequest(format!(
-                "Your reward action is invalid: {}",
+                "Your reply templates are invalid: {}",
                 e
             ))
-        })?;
+        },
+    )?;
 
     // check this before it's actually saved
     if let Err(e) =
@@ -197,9 +283,24 @@ async fn update(
         body.data,
         body.live_delay,
         body.auto_accept,
+        body.refund_on_error,
+        body.reply_templates,
     );
     db_reward.update(&pool).await?;
 
+    log_change(
+        &claims,
+        LogEntry::new(
+            reward.broadcaster_id.as_str(),
+            LogEntryType::SettingsChanged,
+            format!("Updated reward {}", reward.title),
+        )
+        .reward(reward.id.as_str())
+        .payload(json!({ "action": "updated", "type": data_type })),
+        &pool,
+    )
+    .await;
+
     log_discord!(
         "Rewards",
         "🔁 Updated reward",
@@ -215,6 +316,8 @@ async fn update(
         data: db_reward.data.0,
         live_delay: db_reward.live_delay,
         auto_accept: db_reward.auto_accept,
+        refund_on_error: db_reward.refund_on_error,
+        reply_templates: db_reward.reply_templates.0,
     }))
 }
 
@@ -233,6 +336,19 @@ async fn delete(
     // this has to be done afterwards as only then the reward is removed
     Reward::delete(&reward_id, &pool).await?;
 
+    log_change(
+        &claims,
+        LogEntry::new(
+            &broadcaster_id,
+            LogEntryType::SettingsChanged,
+            "Deleted a reward",
+        )
+        .reward(&reward_id)
+        .payload(json!({ "action": "deleted" })),
+        &pool,
+    )
+    .await;
+
     log_discord!(
         "Rewards",
         "🗑 Deleted reward",
@@ -272,6 +388,99 @@ async fn list_for_user(
     }))
 }
 
+#[get("/{broadcaster_id}/reply-templates")]
+async fn get_reply_templates(
+    claims: JwtClaims,
+    pool: web::Data<PgPool>,
+    broadcaster_id: web::Path<String>,
+) -> Result<HttpResponse> {
+    let user = get_user_or_editor(&claims, &broadcaster_id, &pool).await?;
+
+    Ok(HttpResponse::Ok()
+        .json(User::get_reply_templates(&user.id, &pool).await?))
+}
+
+#[put("/{broadcaster_id}/reply-templates")]
+async fn update_reply_templates(
+    claims: JwtClaims,
+    pool: web::Data<PgPool>,
+    body: web::Json<ReplyTemplates>,
+    broadcaster_id: web::Path<String>,
+) -> Result<HttpResponse> {
+    let user = get_user_or_editor(&claims, &broadcaster_id, &pool).await?;
+
+    verify_reply_templates(&body, None).map_err(|e| {
+        errors::ErrorBadRequest(format!(
+            "Your reply templates are invalid: {}",
+            e
+        ))
+    })?;
+    User::set_reply_templates(&user.id, &body, &pool).await?;
+    log_change(
+        &claims,
+        LogEntry::new(
+            &user.id,
+            LogEntryType::SettingsChanged,
+            "Updated the reply templates",
+        )
+        .payload(json!({ "action": "reply_templates" })),
+        &pool,
+    )
+    .await;
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+#[get("/{broadcaster_id}/approvals")]
+async fn list_pending_approvals(
+    claims: JwtClaims,
+    pool: web::Data<PgPool>,
+    broadcaster_id: web::Path<String>,
+) -> Result<HttpResponse> {
+    let user = get_user_or_editor(&claims, &broadcaster_id, &pool).await?;
+
+    Ok(HttpResponse::Ok()
+        .json(PendingApproval::get_all_for_user(&user.id, &pool).await?))
+}
+
+#[derive(Deserialize)]
+struct ResolveApprovalBody {
+    approve: bool,
+}
+
+#[patch("/{broadcaster_id}/approvals/{id}")]
+async fn resolve_pending_approval(
+    claims: JwtClaims,
+    pool: web::Data<PgPool>,
+    path: web::Path<(String, i32)>,
+    body: web::Json<ResolveApprovalBody>,
+) -> Result<HttpResponse> {
+    let (broadcaster_id, id) = path.into_inner();
+    let user = get_user_or_editor(&claims, &broadcaster_id, &pool).await?;
+    let resolved_by = claims.get_user(&pool).await?.name;
+
+    let pending = ApprovalActor::from_registry()
+        .send(ResolveApprovalMessage {
+            broadcaster_id: user.id,
+            id,
+            approve: body.approve,
+            resolved_by,
+        })
+        .await
+        .map_err(|_| errors::ErrorInternalServerError("Too much traffic"))?
+        .map_err(|e| {
+            if is_user_error(&e) {
+                errors::ErrorNotFound(e.to_string())
+            } else {
+                errors::ErrorInternalServerError(format!(
+                    "Could not resolve the redemption: {e}"
+                ))
+            }
+        })?;
+
+    Ok(HttpResponse::Ok().json(pending))
+}
+
 #[derive(Serialize)]
 struct ListSwapEmotesResponse {
     twitch: CustomReward,
@@ -279,6 +488,140 @@ struct ListSwapEmotesResponse {
     emotes: Vec<models::swap_emote::SwapEmote>,
 }
 
+#[get("/{broadcaster_id}/ban-rules")]
+async fn list_ban_rules(
+    claims: JwtClaims,
+    pool: web::Data<PgPool>,
+    broadcaster_id: web::Path<String>,
+) -> Result<HttpResponse> {
+    let user = get_user_or_editor(&claims, &broadcaster_id, &pool).await?;
+
+    Ok(HttpResponse::Ok()
+        .json(BanRule::get_all_for_channel(&user.id, &pool).await?))
+}
+
+#[derive(Deserialize)]
+struct CreateBanRuleBody {
+    platform: Option<SlotPlatform>,
+    kind: BanRuleKind,
+    value: Option<String>,
+    /// Takes the owner or hash from this emote instead of `value`
+    emote_id: Option<String>,
+}
+
+#[put("/{broadcaster_id}/ban-rules")]
+async fn create_ban_rule(
+    claims: JwtClaims,
+    pool: web::Data<PgPool>,
+    broadcaster_id: web::Path<String>,
+    body: web::Json<CreateBanRuleBody>,
+) -> Result<HttpResponse> {
+    let user = get_user_or_editor(&claims, &broadcaster_id, &pool).await?;
+    let CreateBanRuleBody {
+        platform,
+        kind,
+        value,
+        emote_id,
+    } = body.into_inner();
+
+    let value = match (emote_id, platform) {
+        (Some(emote_id), Some(platform)) => Some(
+            rule_value_from_emote(kind, platform, &emote_id)
+                .await
+                .map_err(|e| errors::ErrorBadRequest(e.to_string()))?,
+        ),
+        (Some(_), None) => {
+            return Err(errors::ErrorBadRequest(
+                "A platform is required to find the emote",
+            ))
+        }
+        (None, _) => value.map(|v| v.trim().to_string()),
+    };
+    verify_rule(kind, value.as_deref())
+        .map_err(|e| errors::ErrorBadRequest(e.to_string()))?;
+
+    let rule =
+        BanRule::create(&user.id, platform, kind, value.as_deref(), &pool)
+            .await?;
+
+    Ok(HttpResponse::Ok().json(rule))
+}
+
+#[delete("/{broadcaster_id}/ban-rules/{id}")]
+async fn delete_ban_rule(
+    claims: JwtClaims,
+    pool: web::Data<PgPool>,
+    path: web::Path<(String, i32)>,
+) -> Result<HttpResponse> {
+    let (broadcaster_id, id) = path.into_inner();
+    let user = get_user_or_editor(&claims, &broadcaster_id, &pool).await?;
+
+    if !BanRule::delete(id, &user.id, &pool).await? {
+        return Err(errors::ErrorNotFound("There's no such rule"));
+    }
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+#[get("/{broadcaster_id}/song-queue")]
+async fn list_song_requests(
+    claims: JwtClaims,
+    pool: web::Data<PgPool>,
+    broadcaster_id: web::Path<String>,
+) -> Result<HttpResponse> {
+    let user = get_user_or_editor(&claims, &broadcaster_id, &pool).await?;
+
+    Ok(HttpResponse::Ok().json(SongRequest::get_open(&user.id, &pool).await?))
+}
+
+#[patch("/{broadcaster_id}/song-queue/{id}")]
+async fn resolve_song_request(
+    claims: JwtClaims,
+    pool: web::Data<PgPool>,
+    path: web::Path<(String, i32)>,
+    body: web::Json<ResolveApprovalBody>,
+) -> Result<HttpResponse> {
+    let (broadcaster_id, id) = path.into_inner();
+    let user = get_user_or_editor(&claims, &broadcaster_id, &pool).await?;
+    let resolved_by = claims.get_user(&pool).await?.name;
+
+    let request = if body.approve {
+        song_queue::approve(&user.id, id, &resolved_by, &pool).await
+    } else {
+        song_queue::reject(&user.id, id, &resolved_by, &pool).await
+    }
+    .map_err(song_request_error)?;
+
+    Ok(HttpResponse::Ok().json(request))
+}
+
+#[delete("/{broadcaster_id}/song-queue/{id}")]
+async fn remove_song_request(
+    claims: JwtClaims,
+    pool: web::Data<PgPool>,
+    path: web::Path<(String, i32)>,
+) -> Result<HttpResponse> {
+    let (broadcaster_id, id) = path.into_inner();
+    let user = get_user_or_editor(&claims, &broadcaster_id, &pool).await?;
+    let resolved_by = claims.get_user(&pool).await?.name;
+
+    let (request, _) = song_queue::remove(&user.id, id, &resolved_by, &pool)
+        .await
+        .map_err(song_request_error)?;
+
+    Ok(HttpResponse::Ok().json(request))
+}
+
+fn song_request_error(e: anyhow::Error) -> actix_web::Error {
+    if is_user_error(&e) {
+        errors::ErrorNotFound(e.to_string())
+    } else {
+        errors::ErrorInternalServerError(format!(
+            "Could not update the song request: {e}"
+        ))
+    }
+}
+
 #[get("/{broadcaster_id}/{reward_id}/swap-emotes")]
 async fn list_swap_emotes(
     claims: JwtClaims,
@@ -344,14 +687,23 @@ async fn untrack_swap_emote(
 ) -> Result<HttpResponse> {
     let (broadcaster_id, reward_id, id) = path.into_inner();
     get_user_or_editor(&claims, &broadcaster_id, &pool).await?;
+    let actor = claims.get_user(&pool).await?.name;
 
-    models::swap_emote::SwapEmote::remove_on_reward(
+    let removed = models::swap_emote::SwapEmote::remove_on_reward(
         id,
         &broadcaster_id,
         &reward_id,
         &pool,
     )
     .await?;
+    if let Some(emote) = removed {
+        events::record(
+            events::swap_removal(&emote, EmoteEventReason::Untracked)
+                .actor(&actor),
+            &pool,
+        )
+        .await;
+    }
 
     Ok(HttpResponse::NoContent().finish())
 }
@@ -362,6 +714,16 @@ pub fn init_rewards_routes(config: &mut web::ServiceConfig) {
         .service(update)
         .service(delete)
         .service(list_for_user)
+        .service(get_reply_templates)
+        .service(update_reply_templates)
+        .service(list_pending_approvals)
+        .service(resolve_pending_approval)
+        .service(list_ban_rules)
+        .service(create_ban_rule)
+        .service(delete_ban_rule)
+        .service(list_song_requests)
+        .service(resolve_song_request)
+        .service(remove_song_request)
         .service(list_swap_emotes)
         .service(get_swap_emotes_usage)
         .service(untrack_swap_emote);
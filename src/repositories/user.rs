@@ -1,16 +1,19 @@
 use crate::{
+    chat::settings::invalidate_locale,
     log_err,
     services::{
         emotes,
+        i18n::CATALOG,
         jwt::JwtClaims,
         sql::get_user_or_editor,
         twitch::requests::{get_user, get_user_by_login},
     },
     RedisConn, RedisPool,
 };
-use actix_web::{get, web, HttpResponse, Result};
+use actix_web::{get, put, web, HttpResponse, Result};
 use deadpool_redis::redis::AsyncCommands;
-use serde::Serialize;
+use models::user::User;
+use serde::{Deserialize, Serialize};
 use sqlx::PgPool;
 use twitch_api::twitch_oauth2::UserToken;
 
@@ -129,6 +132,64 @@ async fn refresh_emotes(
     Ok(HttpResponse::Ok().json(RefreshEmotesResult { n_removed }))
 }
 
+#[derive(Serialize)]
+struct LocaleResponse {
+    locale: String,
+    available: Vec<&'static str>,
+}
+
+#[get("/{broadcaster_id}/locale")]
+async fn get_locale(
+    claims: JwtClaims,
+    pool: web::Data<PgPool>,
+    path: web::Path<String>,
+) -> Result<HttpResponse> {
+    let user = get_user_or_editor(&claims, &path, &pool).await?;
+    let locale = User::get_locale(&user.id, &pool).await?;
+
+    Ok(HttpResponse::Ok().json(LocaleResponse {
+        locale,
+        available: CATALOG.locales(),
+    }))
+}
+
+#[derive(Deserialize)]
+struct UpdateLocaleBody {
+    locale: String,
+}
+
+#[put("/{broadcaster_id}/locale")]
+async fn update_locale(
+    claims: JwtClaims,
+    pool: web::Data<PgPool>,
+    redis: web::Data<RedisPool>,
+    path: web::Path<String>,
+    body: web::Json<UpdateLocaleBody>,
+) -> Result<HttpResponse> {
+    let user = get_user_or_editor(&claims, &path, &pool).await?;
+    if !CATALOG.has_locale(&body.locale) {
+        return Err(errors::ErrorBadRequest(format!(
+            "Unknown locale {} (available: {})",
+            body.locale,
+            CATALOG.locales().join(", ")
+        )));
+    }
+    User::set_locale(&user.id, &body.locale, &pool).await?;
+
+    let mut redis_conn = redis
+        .get()
+        .await
+        .map_err(|_| errors::ErrorInternalServerError("Redis is dead"))?;
+    invalidate_locale(&user.id, &mut redis_conn)
+        .await
+        .map_err(|e| {
+            log::warn!("Could not clear the locale cache: {e}");
+            errors::ErrorInternalServerError("Could not clear the cache")
+        })?;
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
 async fn check_update_refresh_cooldown(
     conn: &mut RedisConn,
     channel: &str,
@@ -143,5 +204,10 @@ async fn check_update_refresh_cooldown(
 }
 
 pub fn init_user_routes(config: &mut web::ServiceConfig) {
-    config.service(me).service(info).service(refresh_emotes);
+    config
+        .service(me)
+        .service(info)
+        .service(refresh_emotes)
+        .service(get_locale)
+        .service(update_locale);
 }
@@ -4,7 +4,7 @@ use futures::TryFutureExt;
 use lazy_static::lazy_static;
 use reqwest::{
     header::{HeaderMap, AUTHORIZATION},
-    Client, IntoUrl, Response,
+    Client, IntoUrl, Response, Url,
 };
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use std::time::Duration;
@@ -52,6 +52,11 @@ pub struct BttvLimits {
 pub struct BttvEmote {
     pub id: String,
     pub code: String,
+    #[serde(default)]
+    pub animated: bool,
+    /// The uploader (only set for single emotes)
+    #[serde(default)]
+    pub user_id: Option<String>,
 }
 
 impl PartialEq for BttvEmote {
@@ -70,19 +75,28 @@ pub struct BttvUserInfo {
 }
 
 pub async fn get_dashboards() -> AnyResult<Vec<BttvEditor>> {
-    bttv_get("https://api.betterttv.net/3/account/dashboards").await
+    bttv_get(format!("{}/account/dashboards", CONFIG.urls.bttv)).await
 }
 
 pub async fn get_user(bttv_id: &str) -> AnyResult<BttvUserInfo> {
     bttv_get(format!(
-        "https://api.betterttv.net/3/users/{}?limited=false&personal=false",
-        bttv_id
+        "{}/users/{}?limited=false&personal=false",
+        CONFIG.urls.bttv, bttv_id
     ))
     .await
 }
 
 pub async fn get_emote(emote_id: &str) -> AnyResult<BttvEmote> {
-    bttv_get(format!("https://api.betterttv.net/3/emotes/{}", emote_id)).await
+    bttv_get(format!("{}/emotes/{}", CONFIG.urls.bttv, emote_id)).await
+}
+
+/// Searches shared emotes by name, most used first
+pub async fn search_shared_emotes(query: &str) -> AnyResult<Vec<BttvEmote>> {
+    bttv_get(Url::parse_with_params(
+        &format!("{}/emotes/shared/search", CONFIG.urls.bttv),
+        &[("query", query), ("offset", "0"), ("limit", "25")],
+    )?)
+    .await
 }
 
 pub async fn add_shared_emote(
@@ -90,7 +104,8 @@ pub async fn add_shared_emote(
     user_id: &str,
 ) -> AnyResult<String> {
     bttv_put(format!(
-        "https://api.betterttv.net/3/emotes/{emote_id}/shared/{user_id}/{user_id}",
+        "{}/emotes/{emote_id}/shared/{user_id}/{user_id}",
+        CONFIG.urls.bttv,
     ))
     .await
 }
@@ -100,17 +115,14 @@ pub async fn delete_shared_emote(
     user_id: &str,
 ) -> AnyResult<String> {
     bttv_delete(format!(
-        "https://api.betterttv.net/3/emotes/{emote_id}/shared/{user_id}/{user_id}",
+        "{}/emotes/{emote_id}/shared/{user_id}/{user_id}",
+        CONFIG.urls.bttv,
     ))
     .await
 }
 
 pub async fn get_user_by_twitch_id(id: &str) -> AnyResult<BttvUserInfo> {
-    bttv_get(format!(
-        "https://api.betterttv.net/3/cached/users/twitch/{}",
-        id
-    ))
-    .await
+    bttv_get(format!("{}/cached/users/twitch/{}", CONFIG.urls.bttv, id)).await
 }
 
 async fn bttv_get<T, U>(url: U) -> AnyResult<T>
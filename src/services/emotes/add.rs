@@ -1,8 +1,9 @@
-use crate::PgPool;
+use crate::{services::emotes::events, PgPool};
 use anyhow::{anyhow, Result as AnyResult};
 use config::CONFIG;
 use models::{
     emote::SlotPlatform,
+    emote_event::{EmoteEventKind, EmoteEventReason, NewEmoteEvent},
     reward::{self},
     swap_emote::SwapEmote,
 };
@@ -39,6 +40,21 @@ pub async fn track_emote(
         pool,
     )
     .await?;
+    events::record(
+        NewEmoteEvent::new(
+            channel_id,
+            slot_platform,
+            EmoteEventKind::Added,
+            EmoteEventReason::Injected,
+            emote_id,
+            emote_name,
+        )
+        .actor(executing_user_login)
+        .reward(&chosen_reward.reward_id)
+        .command("emote inject"),
+        pool,
+    )
+    .await;
 
     Ok(())
 }
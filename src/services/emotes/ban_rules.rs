@@ -0,0 +1,331 @@
+use crate::{
+    services::{
+        bttv::requests as bttv,
+        emotes::{
+            bttv::BttvEmotes, ffz::FfzEmotes, seven_tv::SevenTvEmotes, Emote,
+            EmoteRW,
+        },
+        ffz::requests as ffz,
+        rewards::error::user_error,
+        seven_tv::requests as seven_tv,
+    },
+    text,
+};
+use anyhow::{anyhow, Result as AnyResult};
+use lazy_static::lazy_static;
+use models::{
+    ban_rule::{BanRule, BanRuleKind},
+    emote::SlotPlatform,
+};
+use regex::{Regex, RegexBuilder};
+use reqwest::Client;
+use sha2::{Digest, Sha256};
+use sqlx::PgPool;
+use std::{fmt::Display, time::Duration};
+
+lazy_static! {
+    static ref IMAGE_CLIENT: Client = Client::builder()
+        .user_agent(format!(
+            "RewardMore/{} github.com/Nerixyz/rewards",
+            env!("CARGO_PKG_VERSION")
+        ))
+        .timeout(Duration::from_secs(15))
+        .build()
+        .unwrap();
+}
+
+/// Limits the size of the compiled regex of name rules
+const NAME_REGEX_SIZE_LIMIT: usize = 1 << 16;
+
+/// What ban rules know about an emote. Platforms that don't have a property
+/// (e.g. tags on BTTV) leave it empty.
+#[derive(Debug, Default)]
+pub struct EmoteProperties {
+    pub name: String,
+    pub owner_id: Option<String>,
+    pub tags: Vec<String>,
+    pub zero_width: bool,
+    pub nsfw: bool,
+    pub animated: bool,
+}
+
+/// Checks that the value can be used with this kind of rule.
+pub fn verify_rule(kind: BanRuleKind, value: Option<&str>) -> AnyResult<()> {
+    let value = match (kind.has_value(), value.map(str::trim)) {
+        (true, Some(value)) if !value.is_empty() => value,
+        (true, _) => return Err(anyhow!("A {kind} rule needs a value")),
+        (false, None) => return Ok(()),
+        (false, Some(_)) => {
+            return Err(anyhow!("A {kind} rule doesn't take a value"))
+        }
+    };
+    match kind {
+        BanRuleKind::Name => {
+            compile_name_regex(value)?;
+        }
+        BanRuleKind::Hash
+            if value.len() != 64
+                || !value.chars().all(|c| c.is_ascii_hexdigit()) =>
+        {
+            return Err(anyhow!("The hash must be a hex-encoded SHA-256 hash"));
+        }
+        _ => (),
+    }
+
+    Ok(())
+}
+
+/// Fails with a user-error if any rule of the channel matches the emote.
+pub async fn check_ban_rules<RW>(
+    broadcaster_id: &str,
+    emote: &RW::Emote,
+    override_name: Option<&str>,
+    pool: &PgPool,
+) -> AnyResult<()>
+where
+    RW: EmoteRW,
+    RW::Emote: Emote<RW::EmoteId>,
+    RW::EmoteId: Display,
+{
+    let rules = BanRule::get_all_for_channel(broadcaster_id, pool).await?;
+    let platform = RW::platform();
+    let props = emote.properties();
+    // only download the image if there's a hash rule
+    let mut hash = None;
+
+    for rule in rules
+        .iter()
+        .filter(|r| r.platform.map(|p| p == platform).unwrap_or(true))
+    {
+        let matches = match matches_properties(rule, &props, override_name) {
+            Some(matches) => matches,
+            None => {
+                if hash.is_none() {
+                    hash = Some(
+                        hash_image(&RW::format_emote_url(
+                            &emote.id().to_string(),
+                        ))
+                        .await?,
+                    );
+                }
+                hash.as_deref().is_some_and(|h| {
+                    h.eq_ignore_ascii_case(
+                        rule.value.as_deref().unwrap_or_default(),
+                    )
+                })
+            }
+        };
+        if matches {
+            return Err(user_error(text!(
+                "reward.emote.banned_rule",
+                rule = describe_rule(rule)
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+/// Checks a rule against the properties of an emote.
+/// Returns `None` if the rule needs the image (hash rules).
+fn matches_properties(
+    rule: &BanRule,
+    props: &EmoteProperties,
+    override_name: Option<&str>,
+) -> Option<bool> {
+    let value = rule.value.as_deref().unwrap_or_default();
+    Some(match rule.kind {
+        BanRuleKind::Name => match compile_name_regex(value) {
+            Ok(re) => {
+                re.is_match(&props.name)
+                    || override_name.is_some_and(|n| re.is_match(n))
+            }
+            Err(e) => {
+                log::warn!("Invalid ban rule #{}: {}", rule.id, e);
+                false
+            }
+        },
+        BanRuleKind::Owner => props.owner_id.as_deref() == Some(value),
+        BanRuleKind::Tag => {
+            props.tags.iter().any(|t| t.eq_ignore_ascii_case(value))
+        }
+        BanRuleKind::ZeroWidth => props.zero_width,
+        BanRuleKind::Nsfw => props.nsfw,
+        BanRuleKind::Animated => props.animated,
+        BanRuleKind::Static => !props.animated,
+        BanRuleKind::Hash => return None,
+    })
+}
+
+/// Fetches the properties of an emote that isn't necessarily added.
+pub async fn get_emote_properties(
+    platform: SlotPlatform,
+    emote_id: &str,
+) -> AnyResult<EmoteProperties> {
+    Ok(match platform {
+        SlotPlatform::Bttv => bttv::get_emote(emote_id).await?.properties(),
+        SlotPlatform::Ffz => ffz::get_emote(emote_id).await?.properties(),
+        SlotPlatform::SevenTv => {
+            seven_tv::get_emote(emote_id).await?.properties()
+        }
+    })
+}
+
+/// Takes the value of an owner or hash rule from an existing emote.
+pub async fn rule_value_from_emote(
+    kind: BanRuleKind,
+    platform: SlotPlatform,
+    emote_id: &str,
+) -> AnyResult<String> {
+    match kind {
+        BanRuleKind::Owner => get_emote_properties(platform, emote_id)
+            .await?
+            .owner_id
+            .ok_or_else(|| user_error(text!("chat.emote.no_owner"))),
+        BanRuleKind::Hash => hash_image(&image_url(platform, emote_id)).await,
+        _ => Err(anyhow!("A {kind} rule can't be taken from an emote")),
+    }
+}
+
+/// The URL of the image that's hashed for [BanRuleKind::Hash]
+pub fn image_url(platform: SlotPlatform, emote_id: &str) -> String {
+    match platform {
+        SlotPlatform::Bttv => BttvEmotes::format_emote_url(emote_id),
+        SlotPlatform::Ffz => FfzEmotes::format_emote_url(emote_id),
+        SlotPlatform::SevenTv => SevenTvEmotes::format_emote_url(emote_id),
+    }
+}
+
+/// Hex-encoded SHA-256 hash of the image behind `url`
+pub async fn hash_image(url: &str) -> AnyResult<String> {
+    let image = IMAGE_CLIENT
+        .get(url)
+        .send()
+        .await?
+        .error_for_status()?
+        .bytes()
+        .await?;
+
+    Ok(hex::encode(Sha256::digest(&image)))
+}
+
+pub fn describe_rule(rule: &BanRule) -> String {
+    match &rule.value {
+        Some(value) => format!("#{} {} {value}", rule.id, rule.kind),
+        None => format!("#{} {}", rule.id, rule.kind),
+    }
+}
+
+fn compile_name_regex(pattern: &str) -> AnyResult<Regex> {
+    RegexBuilder::new(pattern)
+        .case_insensitive(true)
+        .size_limit(NAME_REGEX_SIZE_LIMIT)
+        .build()
+        .map_err(|e| anyhow!("Invalid regex: {e}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn rule(kind: BanRuleKind, value: Option<&str>) -> BanRule {
+        BanRule {
+            id: 1,
+            channel_id: "1".to_owned(),
+            platform: None,
+            kind,
+            value: value.map(ToOwned::to_owned),
+            created_at: Utc::now(),
+        }
+    }
+
+    fn emote() -> EmoteProperties {
+        EmoteProperties {
+            name: "pepeLaugh".to_owned(),
+            owner_id: Some("owner".to_owned()),
+            tags: vec!["Pepe".to_owned()],
+            zero_width: false,
+            nsfw: false,
+            animated: true,
+        }
+    }
+
+    fn matches(kind: BanRuleKind, value: Option<&str>) -> Option<bool> {
+        matches_properties(&rule(kind, value), &emote(), None)
+    }
+
+    #[test]
+    fn name_rules_are_case_insensitive_regexes() {
+        assert_eq!(matches(BanRuleKind::Name, Some("^PEPE")), Some(true));
+        assert_eq!(matches(BanRuleKind::Name, Some("laugh$")), Some(true));
+        assert_eq!(matches(BanRuleKind::Name, Some("^laugh")), Some(false));
+        // invalid rules never match
+        assert_eq!(matches(BanRuleKind::Name, Some("(")), Some(false));
+    }
+
+    #[test]
+    fn name_rules_check_the_override() {
+        let rule = rule(BanRuleKind::Name, Some("^bad"));
+        assert_eq!(matches_properties(&rule, &emote(), None), Some(false));
+        assert_eq!(
+            matches_properties(&rule, &emote(), Some("badName")),
+            Some(true)
+        );
+    }
+
+    #[test]
+    fn property_rules() {
+        assert_eq!(matches(BanRuleKind::Owner, Some("owner")), Some(true));
+        assert_eq!(matches(BanRuleKind::Owner, Some("other")), Some(false));
+        assert_eq!(matches(BanRuleKind::Tag, Some("pepe")), Some(true));
+        assert_eq!(matches(BanRuleKind::Tag, Some("cat")), Some(false));
+        assert_eq!(matches(BanRuleKind::ZeroWidth, None), Some(false));
+        assert_eq!(matches(BanRuleKind::Nsfw, None), Some(false));
+        assert_eq!(matches(BanRuleKind::Animated, None), Some(true));
+        assert_eq!(matches(BanRuleKind::Static, None), Some(false));
+        // emotes without an owner don't match empty owner rules
+        let no_owner = EmoteProperties {
+            owner_id: None,
+            ..emote()
+        };
+        assert_eq!(
+            matches_properties(
+                &rule(BanRuleKind::Owner, None),
+                &no_owner,
+                None
+            ),
+            Some(false)
+        );
+    }
+
+    #[test]
+    fn hash_rules_need_the_image() {
+        assert_eq!(matches(BanRuleKind::Hash, Some(&"a".repeat(64))), None);
+    }
+
+    #[test]
+    fn rule_values() {
+        assert!(verify_rule(BanRuleKind::Name, Some("^pepe")).is_ok());
+        assert!(verify_rule(BanRuleKind::Name, Some("(")).is_err());
+        assert!(verify_rule(BanRuleKind::Name, Some("  ")).is_err());
+        assert!(verify_rule(BanRuleKind::Owner, None).is_err());
+        assert!(verify_rule(BanRuleKind::Nsfw, None).is_ok());
+        assert!(verify_rule(BanRuleKind::Nsfw, Some("x")).is_err());
+        assert!(verify_rule(BanRuleKind::Hash, Some(&"aB".repeat(32))).is_ok());
+        assert!(verify_rule(BanRuleKind::Hash, Some(&"a".repeat(63))).is_err());
+        assert!(verify_rule(BanRuleKind::Hash, Some(&"g".repeat(64))).is_err());
+    }
+
+    #[test]
+    fn rule_descriptions() {
+        assert_eq!(
+            describe_rule(&rule(BanRuleKind::Name, Some("^pepe"))),
+            "#1 name ^pepe"
+        );
+        assert_eq!(
+            describe_rule(&rule(BanRuleKind::ZeroWidth, None)),
+            "#1 zero-width"
+        );
+    }
+}
@@ -0,0 +1,43 @@
+use crate::{
+    services::{
+        emotes::{Emote, EmoteRW},
+        rewards::error::user_error,
+    },
+    text,
+};
+use anyhow::Result as AnyResult;
+use models::blocklist::BlocklistEntry;
+use sqlx::PgPool;
+use std::fmt::Display;
+
+/// Fails with a user-error if the emote or its owner is on the instance-wide
+/// blocklist.
+pub async fn check_blocklist<RW>(
+    emote: &RW::Emote,
+    pool: &PgPool,
+) -> AnyResult<()>
+where
+    RW: EmoteRW,
+    RW::Emote: Emote<RW::EmoteId>,
+    RW::EmoteId: Display,
+{
+    let props = emote.properties();
+    let entry = BlocklistEntry::find_blocking(
+        RW::platform(),
+        &emote.id().to_string(),
+        props.owner_id.as_deref(),
+        pool,
+    )
+    .await?;
+    if let Some(entry) = entry {
+        log::info!(
+            "Blocked {} ({}) by global entry #{}",
+            props.name,
+            RW::platform(),
+            entry.id
+        );
+        return Err(user_error(text!("reward.emote.blocked")));
+    }
+
+    Ok(())
+}
@@ -1,12 +1,17 @@
 use crate::{
     services::{
         bttv::{get_or_fetch_id, requests as bttv},
-        emotes::{Emote, EmoteEnvData, EmoteId, EmoteInitialData, EmoteRW},
+        emotes::{
+            ban_rules::EmoteProperties, Emote, EmoteEnvData, EmoteId,
+            EmoteInitialData, EmoteRW,
+        },
+        rewards::error::user_error,
     },
-    RedisPool,
+    text, RedisPool,
 };
-use anyhow::{anyhow, Error as AnyError, Result as AnyResult};
+use anyhow::{Error as AnyError, Result as AnyResult};
 use async_trait::async_trait;
+use config::CONFIG;
 use futures::TryFutureExt;
 use models::{emote::SlotPlatform, swap_emote::SwapEmote};
 use sqlx::PgPool;
@@ -27,6 +32,15 @@ impl Emote<String> for bttv::BttvEmote {
     fn into_name(self) -> String {
         self.code
     }
+
+    fn properties(&self) -> EmoteProperties {
+        EmoteProperties {
+            name: self.code.clone(),
+            owner_id: self.user_id.clone(),
+            animated: self.animated,
+            ..Default::default()
+        }
+    }
 }
 
 impl EmoteId for String {
@@ -55,7 +69,10 @@ impl EmoteRW for BttvEmotes {
         _redis_pool: &RedisPool,
     ) -> AnyResult<EmoteInitialData<String, bttv::BttvEmote>> {
         if overwritten_name.is_some() {
-            return Err(anyhow!("BTTV doesn't support renaming emotes"));
+            return Err(user_error(text!(
+                "reward.emote.no_renaming",
+                platform = "BTTV"
+            )));
         }
 
         let (bttv_id, history_len) = futures::future::try_join(
@@ -72,7 +89,7 @@ impl EmoteRW for BttvEmotes {
             get_user_limits(&bttv_id)
                 .map_err(|_| AnyError::msg("I'm not added as an editor.")),
             bttv::get_emote(emote_id)
-                .map_err(|_| AnyError::msg("This emote doesn't exist.")),
+                .map_err(|_| user_error(text!("reward.emote.doesnt_exist"))),
         )
         .await?;
 
@@ -83,18 +100,14 @@ impl EmoteRW for BttvEmotes {
             .iter()
             .any(|e| e.id == emote_id || e.code == emote_data.code)
         {
-            return Err(AnyError::msg(
-                "The emote already exists as a shared emote",
-            ));
+            return Err(user_error(text!("reward.emote.exists_shared")));
         }
         if bttv_user
             .channel_emotes
             .iter()
             .any(|e| e.id == emote_id || e.code == emote_data.code)
         {
-            return Err(AnyError::msg(
-                "The emote already exists as a channel emote",
-            ));
+            return Err(user_error(text!("reward.emote.exists_channel")));
         }
         Ok(EmoteInitialData {
             max_emotes: user_limits.channel_emotes,
@@ -133,6 +146,10 @@ impl EmoteRW for BttvEmotes {
         Ok(bttv_user.shared_emotes)
     }
 
+    async fn search_emotes(query: &str) -> AnyResult<Vec<Self::Emote>> {
+        bttv::search_shared_emotes(query).await
+    }
+
     async fn remove_emote(
         platform_id: &String,
         emote_id: &String,
@@ -187,7 +204,7 @@ impl EmoteRW for BttvEmotes {
     }
 
     fn format_emote_url(emote_id: &str) -> String {
-        format!("https://cdn.betterttv.net/emote/{}/3x", emote_id)
+        format!("{}/emote/{}/3x", CONFIG.urls.bttv_cdn, emote_id)
     }
 
     fn format_emote_page(emote_id: &str) -> String {
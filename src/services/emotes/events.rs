@@ -0,0 +1,129 @@
+use crate::log_err;
+use models::{
+    emote_event::{
+        EmoteEvent, EmoteEventKind, EmoteEventReason, NewEmoteEvent,
+    },
+    slot::Slot,
+    swap_emote::SwapEmote,
+};
+use sqlx::PgPool;
+
+/// Appends the event to the channel's emote history.
+/// Failures are only logged, they never fail the action itself.
+pub async fn record(event: NewEmoteEvent<'_>, pool: &PgPool) {
+    log_err!(event.insert(pool).await, "Could not record emote event");
+}
+
+/// The removal of a tracked swap emote.
+pub fn swap_removal(
+    swap: &SwapEmote,
+    reason: EmoteEventReason,
+) -> NewEmoteEvent<'_> {
+    NewEmoteEvent::new(
+        &swap.user_id,
+        swap.platform,
+        EmoteEventKind::Removed,
+        reason,
+        &swap.emote_id,
+        &swap.name,
+    )
+    .reward(&swap.reward_id)
+}
+
+/// The removal of the emote in a slot, `None` if the slot is empty.
+pub fn slot_removal(
+    slot: &Slot,
+    reason: EmoteEventReason,
+) -> Option<NewEmoteEvent<'_>> {
+    let emote_id = slot.emote_id.as_deref()?;
+    Some(
+        NewEmoteEvent::new(
+            &slot.user_id,
+            slot.platform,
+            EmoteEventKind::Removed,
+            reason,
+            emote_id,
+            slot.name.as_deref().unwrap_or_default(),
+        )
+        .reward(&slot.reward_id),
+    )
+}
+
+/// Formats the events as CSV with a header row.
+pub fn to_csv(events: &[EmoteEvent]) -> String {
+    let mut csv = String::from(
+        "id,created_at,kind,reason,platform,emote_id,emote_name,actor,reward_id,command\n",
+    );
+    for e in events {
+        let fields = [
+            e.id.to_string(),
+            e.created_at.to_rfc3339(),
+            e.kind.to_string(),
+            e.reason.to_string(),
+            e.platform.to_string(),
+            e.emote_id.clone(),
+            e.emote_name.clone(),
+            e.actor.clone().unwrap_or_default(),
+            e.reward_id.clone().unwrap_or_default(),
+            e.command.clone().unwrap_or_default(),
+        ];
+        let row = fields
+            .iter()
+            .map(|f| escape_csv(f))
+            .collect::<Vec<_>>()
+            .join(",");
+        csv.push_str(&row);
+        csv.push('\n');
+    }
+    csv
+}
+
+fn escape_csv(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+    use models::emote::SlotPlatform;
+
+    #[test]
+    fn escapes_csv_fields() {
+        assert_eq!(escape_csv("pepeLaugh"), "pepeLaugh");
+        assert_eq!(escape_csv("a,b"), "\"a,b\"");
+        assert_eq!(escape_csv("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(escape_csv("two\nlines"), "\"two\nlines\"");
+    }
+
+    #[test]
+    fn formats_events_as_csv() {
+        let event = EmoteEvent {
+            id: 7,
+            channel_id: "1001".to_owned(),
+            platform: SlotPlatform::SevenTv,
+            kind: EmoteEventKind::Removed,
+            reason: EmoteEventReason::SwappedOut,
+            emote_id: "emote".to_owned(),
+            emote_name: "Name,With,Commas".to_owned(),
+            actor: Some("viewer".to_owned()),
+            reward_id: None,
+            command: None,
+            created_at: Utc.with_ymd_and_hms(2026, 10, 18, 12, 0, 0).unwrap(),
+        };
+        let csv = to_csv(&[event]);
+        let lines = csv.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].starts_with("id,created_at,kind,reason,platform"));
+        assert_eq!(
+            lines[1],
+            "7,2026-10-18T12:00:00+00:00,removed,swapped_out,7TV,emote,\
+             \"Name,With,Commas\",viewer,,"
+        );
+        assert!(csv.ends_with('\n'));
+    }
+}
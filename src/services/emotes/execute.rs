@@ -1,6 +1,6 @@
 use std::fmt::Display;
 
-use anyhow::{anyhow, bail, Result as AnyResult};
+use anyhow::{anyhow, Result as AnyResult};
 use sqlx::PgPool;
 
 use crate::{
@@ -8,96 +8,127 @@ use crate::{
     chat::parse::opt_next_space,
     embed_builder, send_discord,
     services::{
-        emotes::{slots, swap, Emote, EmoteRW},
-        rewards::{extract::EmoteSpec, Redemption},
+        emotes::{events, resolve, slots, swap, Emote, EmoteRW},
+        formatting::human_format_duration,
+        i18n::Text,
+        rewards::{
+            error::user_error, extract, extract::EmoteSpec,
+            reply::RedemptionReply, Redemption,
+        },
     },
-    RedisPool,
+    text, RedisPool,
 };
 use actix::Addr;
 use models::{
+    emote_event::{EmoteEventKind, EmoteEventReason, NewEmoteEvent},
     reward::{SlotRewardData, SwapRewardData},
     swap_emote::SwapEmote,
 };
 use std::str::FromStr;
 
 pub async fn execute_swap<RW>(
-    extractor: impl FnOnce(&str) -> AnyResult<EmoteSpec>,
+    extractor: impl Fn(&str) -> AnyResult<EmoteSpec>,
     redemption: Redemption,
     reward_data: SwapRewardData,
     pool: &PgPool,
     redis_pool: &RedisPool,
     discord: Addr<DiscordActor>,
-) -> AnyResult<String>
+) -> AnyResult<RedemptionReply>
 where
     RW: EmoteRW,
     RW::PlatformId: Display,
     RW::Emote: Emote<RW::EmoteId>,
     RW::EmoteId: Display + Clone + FromStr + Default,
 {
-    let EmoteSpec {
-        id: platform_id,
-        override_name,
-    } = extractor(&redemption.user_input)?;
+    let extracted = match reward_data.max_per_redemption {
+        Some(max) if max > 1 => {
+            extract::multiple(&redemption.user_input, max as usize, &extractor)
+        }
+        _ => extractor(&redemption.user_input).map(|spec| vec![spec]),
+    };
+    let searched = match (&extracted, reward_data.search) {
+        (Err(_), Some(mode)) => Some(
+            resolve::search_by_name::<RW>(&redemption.user_input, mode).await?,
+        ),
+        _ => None,
+    };
+    let emotes = match (extracted, &searched) {
+        (Ok(emotes), _) => emotes,
+        (Err(_), Some(id)) => {
+            vec![resolve::searched_spec::<RW>(&redemption.user_input, id)]
+        }
+        (Err(e), None) => return Err(e),
+    };
 
-    log::info!(
-        "Adding {:?} emote {} as {override_name:?} in {}",
-        RW::platform(),
-        platform_id,
-        redemption.broadcaster_user_login
-    );
+    for spec in &emotes {
+        log::info!(
+            "Adding {:?} emote {} as {:?} in {}",
+            RW::platform(),
+            spec.id,
+            spec.override_name,
+            redemption.broadcaster_user_login
+        );
+    }
 
     let user: String = redemption.user_login.take();
 
-    Ok(
-        match swap::swap_or_add_emote::<RW>(
-            redemption.broadcaster_user_id.as_ref(),
-            platform_id,
-            redemption.reward.id.as_str(),
-            override_name,
-            reward_data,
-            &user,
-            pool,
-            redis_pool,
-        )
-        .await?
-        {
-            (Some(removed), added) => {
-                let msg = format!("☑ Added {} - 🗑 Removed {}", added, removed);
-                send_discord!(
-                    discord,
-                    redemption.broadcaster_user_id.take(),
-                    embed_builder!(
-                        "Emotes",
-                        "Added an emote",
-                        0x00c8af,
-                        "User" = user.clone(),
-                        "Emote" = added,
-                        "Removed" = removed;
-                        image = Some(RW::format_emote_url(platform_id)),
-                        url = Some(RW::format_emote_page(platform_id)),
-                    )
-                );
-                msg
-            }
-            (None, added) => {
-                let msg = format!("☑ Added {}", added);
-                send_discord!(
-                    discord,
-                    redemption.broadcaster_user_id.take(),
-                    embed_builder!(
-                        "Emotes",
-                        "Added an emote",
-                        0x00c8af,
-                        "User" = user.clone(),
-                        "Emote" = added;
-                        image = Some(RW::format_emote_url(platform_id)),
-                        url = Some(RW::format_emote_page(platform_id)),
-                    )
-                );
-                msg
-            }
-        },
+    let (removed, added) = swap::swap_or_add_emote::<RW>(
+        redemption.broadcaster_user_id.as_ref(),
+        &emotes,
+        redemption.reward.id.as_str(),
+        reward_data,
+        &user,
+        pool,
+        redis_pool,
     )
+    .await?;
+
+    for (spec, added) in emotes.iter().zip(&added) {
+        let discord = discord.clone();
+        send_discord!(
+            discord,
+            redemption.broadcaster_user_id.to_string(),
+            embed_builder!(
+                "Emotes",
+                "Added an emote",
+                0x00c8af,
+                "User" = user.clone(),
+                "Emote" = added.clone();
+                image = Some(RW::format_emote_url(spec.id)),
+                url = Some(RW::format_emote_page(spec.id)),
+            )
+        );
+    }
+    if !removed.is_empty() {
+        send_discord!(
+            discord,
+            redemption.broadcaster_user_id.take(),
+            embed_builder!(
+                "Emotes",
+                "Removed emotes",
+                0x00c8af,
+                "User" = user.clone(),
+                "Removed" = removed.join(", "),
+            )
+        );
+    }
+
+    let mut msg = if removed.is_empty() {
+        text!("reward.swap.added", emote = added.join(", "))
+    } else {
+        text!(
+            "reward.swap.added_removed",
+            emote = added.join(", "),
+            removed = removed.join(", ")
+        )
+    };
+    if let Some(id) = searched {
+        msg = with_emote_page(msg, RW::format_emote_page(&id));
+    }
+    Ok(RedemptionReply::new(msg)
+        .with("emote", added.join(", "))
+        .with("removed", removed.join(", "))
+        .with("platform", RW::platform().to_string()))
 }
 
 pub async fn execute_slot<RW>(
@@ -107,19 +138,36 @@ pub async fn execute_slot<RW>(
     pool: &PgPool,
     redis_pool: &RedisPool,
     discord: Addr<DiscordActor>,
-) -> AnyResult<String>
+) -> AnyResult<RedemptionReply>
 where
     RW: EmoteRW,
     RW::Emote: Emote<RW::EmoteId>,
     RW::EmoteId: Display,
 {
+    let extracted = extractor(&redemption.user_input);
+    let searched = match (&extracted, slot_data.search) {
+        (Err(_), Some(mode)) => Some(
+            resolve::search_by_name::<RW>(&redemption.user_input, mode).await?,
+        ),
+        _ => None,
+    };
     let EmoteSpec {
         id: platform_id,
         override_name,
-    } = extractor(&redemption.user_input)?;
+    } = match (extracted, &searched) {
+        (Ok(spec), _) => spec,
+        (Err(_), Some(id)) => {
+            resolve::searched_spec::<RW>(&redemption.user_input, id)
+        }
+        (Err(e), None) => return Err(e),
+    };
 
     let broadcaster: String = redemption.broadcaster_user_login.take();
     let user: String = redemption.user_login.take();
+    let expires = humantime::parse_duration(&slot_data.expiration)
+        .ok()
+        .and_then(|d| chrono::Duration::from_std(d).ok())
+        .map_or_else(|| Text::from(""), |d| human_format_duration(&-d));
 
     log::info!(
         "Adding {:?} emote {} as {override_name:?} in {}",
@@ -140,15 +188,27 @@ where
     })
     .await?;
 
-    let msg = match &res {
-        (added, remaining) if *remaining > 1 => {
-            format!("☑ Added {} - 🔳 {} slots open", added, remaining)
-        }
+    let mut msg = match &res {
+        (added, remaining) if *remaining > 1 => text!(
+            "reward.slot.added",
+            emote = added.as_str(),
+            remaining = remaining.to_string()
+        ),
         (added, remaining) if *remaining == 1 => {
-            format!("☑ Added {} - 🔳 {} slot open", added, remaining)
+            text!("reward.slot.added_last", emote = added.as_str())
+        }
+        (added, _) => {
+            text!("reward.slot.added_closing", emote = added.as_str())
         }
-        (added, _) => format!("☑ Added {} - 0 slots open - 🔒 closing", added),
     };
+    if searched.is_some() {
+        msg = with_emote_page(msg, RW::format_emote_page(platform_id));
+    }
+
+    let reply = RedemptionReply::new(msg)
+        .with("emote", res.0.clone())
+        .with("platform", RW::platform().to_string())
+        .with("expires", expires);
 
     send_discord!(
         discord,
@@ -164,7 +224,7 @@ where
         )
     );
 
-    Ok(msg)
+    Ok(reply)
 }
 
 enum IdOrName<'a> {
@@ -207,7 +267,7 @@ where
         IdOrName::Id(id) => {
             // ugh, this should be &str or usize for FFZ
             let Ok(id) = RW::EmoteId::from_str(id) else {
-                bail!("Invalid emote ID");
+                return Err(user_error(text!("reward.emote.invalid_id")));
             };
             emotes.into_iter().find(|e| e.id() == &id)
         }
@@ -236,7 +296,7 @@ pub async fn execute_remove_emote<RW>(
     pool: &PgPool,
     redis_pool: &RedisPool,
     discord: Addr<DiscordActor>,
-) -> AnyResult<String>
+) -> AnyResult<RedemptionReply>
 where
     RW: EmoteRW,
     RW::Emote: Emote<RW::EmoteId>,
@@ -260,7 +320,10 @@ where
     )
     .await?;
     let Some((emote_name, emote_id)) = emote else {
-        return Err(anyhow!("This {} emote isn't added!", RW::platform()));
+        return Err(user_error(text!(
+            "reward.emote.not_added",
+            platform = RW::platform().to_string()
+        )));
     };
 
     RW::remove_emote_from_broadcaster(
@@ -273,6 +336,20 @@ where
     .map_err(|e| {
         anyhow!("Failed to remove emote from {} ({e})", RW::platform())
     })?;
+    events::record(
+        NewEmoteEvent::new(
+            redemption.broadcaster_user_id.as_str(),
+            RW::platform(),
+            EmoteEventKind::Removed,
+            EmoteEventReason::Removed,
+            &emote_id,
+            &emote_name,
+        )
+        .actor(&user)
+        .reward(redemption.reward.id.as_str()),
+        pool,
+    )
+    .await;
 
     send_discord!(
         discord,
@@ -288,5 +365,15 @@ where
         )
     );
 
-    Ok(format!("🗑 Removed {}", emote_name))
+    Ok(RedemptionReply::new(text!(
+        "reward.emote.removed",
+        emote = emote_name.as_str()
+    ))
+    .with("emote", emote_name)
+    .with("platform", RW::platform().to_string()))
+}
+
+/// Adds the link to the emote's page if it was found by its name.
+fn with_emote_page(message: Text, page: String) -> Text {
+    text!("reward.emote.with_page", message = message, page = page)
 }
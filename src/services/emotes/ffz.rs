@@ -1,12 +1,17 @@
 use crate::{
     services::{
-        emotes::{Emote, EmoteEnvData, EmoteId, EmoteInitialData, EmoteRW},
+        emotes::{
+            ban_rules::EmoteProperties, Emote, EmoteEnvData, EmoteId,
+            EmoteInitialData, EmoteRW,
+        },
         ffz::requests as ffz,
+        rewards::error::user_error,
     },
-    RedisPool,
+    text, RedisPool,
 };
-use anyhow::{anyhow, Error as AnyError, Result as AnyResult};
+use anyhow::{Error as AnyError, Result as AnyResult};
 use async_trait::async_trait;
+use config::CONFIG;
 use futures::TryFutureExt;
 use models::{emote::SlotPlatform, swap_emote::SwapEmote};
 use sqlx::PgPool;
@@ -27,6 +32,15 @@ impl Emote<usize> for ffz::FfzEmote {
     fn into_name(self) -> String {
         self.name
     }
+
+    fn properties(&self) -> EmoteProperties {
+        EmoteProperties {
+            name: self.name.clone(),
+            owner_id: self.owner.as_ref().map(|o| o.id.to_string()),
+            animated: self.animated.is_some(),
+            ..Default::default()
+        }
+    }
 }
 
 impl EmoteId for usize {
@@ -56,7 +70,10 @@ impl EmoteRW for FfzEmotes {
         _redis_pool: &RedisPool,
     ) -> AnyResult<EmoteInitialData<usize, ffz::FfzEmote>> {
         if overwritten_name.is_some() {
-            return Err(anyhow!("FFZ doesn't support renaming emotes"));
+            return Err(user_error(text!(
+                "reward.emote.no_renaming",
+                platform = "FFZ"
+            )));
         }
 
         let (ffz_user, ffz_emote, ffz_room, ffz_history) =
@@ -67,7 +84,7 @@ impl EmoteRW for FfzEmotes {
                 }),
                 ffz::get_emote(emote_id).map_err(|e| {
                     log::warn!("err: {}", e);
-                    AnyError::msg("No such emote")
+                    user_error(text!("reward.emote.doesnt_exist"))
                 }),
                 ffz::get_room(broadcaster_id).map_err(|e| {
                     log::warn!("err: {}", e);
@@ -91,7 +108,7 @@ impl EmoteRW for FfzEmotes {
             .iter()
             .any(|e| e.id == ffz_emote.id || e.name == ffz_emote.name)
         {
-            return Err(AnyError::msg("The emote is already added"));
+            return Err(user_error(text!("reward.emote.already_added")));
         }
 
         Ok(EmoteInitialData {
@@ -149,6 +166,12 @@ impl EmoteRW for FfzEmotes {
             .collect())
     }
 
+    async fn search_emotes(_query: &str) -> AnyResult<Vec<Self::Emote>> {
+        Err(AnyError::msg(
+            "Searching FFZ emotes isn't supported - use a link instead",
+        ))
+    }
+
     async fn get_platform_id(
         broadcaster_id: &str,
         _pool: &PgPool,
@@ -197,7 +220,7 @@ impl EmoteRW for FfzEmotes {
     }
 
     fn format_emote_url(emote_id: &str) -> String {
-        format!("https://cdn.frankerfacez.com/emote/{}/4", emote_id)
+        format!("{}/emote/{}/4", CONFIG.urls.ffz_cdn, emote_id)
     }
     fn format_emote_page(emote_id: &str) -> String {
         format!("https://www.frankerfacez.com/emoticon/{}", emote_id)
@@ -1,10 +1,14 @@
 pub mod add;
+pub mod ban_rules;
+pub mod blocklist;
 pub mod bttv;
+pub mod events;
 pub mod execute;
 pub mod ffz;
 pub mod format;
 pub mod refresh;
 pub mod remove;
+pub mod resolve;
 pub mod search;
 pub mod seven_tv;
 pub mod slots;
@@ -12,6 +16,7 @@ pub mod swap;
 
 use anyhow::Result as AnyResult;
 use async_trait::async_trait;
+use ban_rules::EmoteProperties;
 use models::emote::SlotPlatform;
 use sqlx::PgPool;
 
@@ -34,6 +39,8 @@ pub trait Emote<I> {
     fn id(&self) -> &I;
     fn name(&self) -> &str;
     fn into_name(self) -> String;
+    /// The properties ban rules match on
+    fn properties(&self) -> EmoteProperties;
 }
 
 pub trait EmoteId {
@@ -72,6 +79,8 @@ pub trait EmoteRW {
         broadcaster_id: &str,
         pool: &PgPool,
     ) -> AnyResult<Vec<Self::Emote>>;
+    /// Searches public emotes by name, the most used emotes come first
+    async fn search_emotes(query: &str) -> AnyResult<Vec<Self::Emote>>;
     async fn remove_emote(
         platform_id: &Self::PlatformId,
         emote_id: &Self::EmoteId,
@@ -1,10 +1,12 @@
 use anyhow::Result as AnyResult;
 use futures::future;
-use models::{slot::Slot, swap_emote::SwapEmote};
+use models::{
+    emote_event::EmoteEventReason, slot::Slot, swap_emote::SwapEmote,
+};
 use sqlx::PgPool;
 
 use crate::{
-    services::emotes::{remove, search::EmoteCache},
+    services::emotes::{events, remove, search::EmoteCache},
     RedisConn,
 };
 
@@ -27,6 +29,11 @@ pub async fn refresh_emotes(
             && !cache.contains(&swap.emote_id, swap.platform)
         {
             SwapEmote::remove(swap.id, pg).await?;
+            events::record(
+                events::swap_removal(&swap, EmoteEventReason::Refreshed),
+                pg,
+            )
+            .await;
             removed += 1;
         }
     }
@@ -41,6 +48,11 @@ pub async fn refresh_emotes(
                 )
                 .await;
                 db?;
+                if let Some(event) =
+                    events::slot_removal(&slot, EmoteEventReason::Refreshed)
+                {
+                    events::record(event, pg).await;
+                }
                 removed += 1;
             }
         }
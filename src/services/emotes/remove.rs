@@ -2,7 +2,7 @@ use crate::{
     actors::slot::Recheck,
     services::{
         emotes::{
-            bttv::BttvEmotes, ffz::FfzEmotes, search::search_by_id,
+            bttv::BttvEmotes, events, ffz::FfzEmotes, search::search_by_id,
             seven_tv::SevenTvEmotes, EmoteRW,
         },
         twitch::requests::update_reward,
@@ -15,7 +15,8 @@ use chrono::{TimeZone, Utc};
 use either::Either;
 use futures_util::{future, TryFutureExt};
 use models::{
-    emote::SlotPlatform, slot::Slot, swap_emote::SwapEmote, user::User,
+    emote::SlotPlatform, emote_event::EmoteEventReason, slot::Slot,
+    swap_emote::SwapEmote, user::User,
 };
 use sqlx::PgPool;
 use std::str::FromStr;
@@ -24,10 +25,13 @@ use twitch_api::{
 };
 
 /// Untracks and removes the emote both from the db and the platform.
+/// Slots are only marked as expired, so they show up as expired in the history.
 pub async fn remove_emote(
     channel_id: &str,
     emote_id: &str,
     slot_platform: SlotPlatform,
+    reason: EmoteEventReason,
+    actor: &str,
     pool: &PgPool,
     redis_pool: &RedisPool,
 ) -> AnyResult<()> {
@@ -40,26 +44,33 @@ pub async fn remove_emote(
             slot.update(pool).await?;
             SlotActor::from_registry().do_send(Recheck);
         }
-        Either::Right(swap) => match slot_platform {
-            SlotPlatform::Bttv => {
-                remove_swap_emote::<BttvEmotes, _, _, _>(
-                    channel_id, emote_id, &swap, pool, redis_pool,
-                )
-                .await?
-            }
-            SlotPlatform::Ffz => {
-                remove_swap_emote::<FfzEmotes, _, _, _>(
-                    channel_id, emote_id, &swap, pool, redis_pool,
-                )
-                .await?
-            }
-            SlotPlatform::SevenTv => {
-                remove_swap_emote::<SevenTvEmotes, _, _, _>(
-                    channel_id, emote_id, &swap, pool, redis_pool,
-                )
-                .await?
+        Either::Right(swap) => {
+            match slot_platform {
+                SlotPlatform::Bttv => {
+                    remove_swap_emote::<BttvEmotes, _, _, _>(
+                        channel_id, emote_id, &swap, pool, redis_pool,
+                    )
+                    .await?
+                }
+                SlotPlatform::Ffz => {
+                    remove_swap_emote::<FfzEmotes, _, _, _>(
+                        channel_id, emote_id, &swap, pool, redis_pool,
+                    )
+                    .await?
+                }
+                SlotPlatform::SevenTv => {
+                    remove_swap_emote::<SevenTvEmotes, _, _, _>(
+                        channel_id, emote_id, &swap, pool, redis_pool,
+                    )
+                    .await?
+                }
             }
-        },
+            events::record(
+                events::swap_removal(&swap, reason).actor(actor),
+                pool,
+            )
+            .await;
+        }
     }
     Ok(())
 }
@@ -69,6 +80,7 @@ pub async fn untrack_emote(
     channel_id: &str,
     emote_id: &str,
     slot_platform: SlotPlatform,
+    actor: &str,
     pool: &PgPool,
 ) -> AnyResult<String> {
     let name = match search_by_id(channel_id, emote_id, slot_platform, pool)
@@ -81,10 +93,23 @@ pub async fn untrack_emote(
                 enable_reward(&slot, pool),
             )
             .await?;
+            if let Some(event) =
+                events::slot_removal(&slot, EmoteEventReason::Ejected)
+            {
+                events::record(event.actor(actor).command("emote eject"), pool)
+                    .await;
+            }
             slot.name.unwrap_or_else(|| "<empty slot>".to_string())
         }
         Either::Right(swap) => {
             SwapEmote::remove(swap.id, pool).await?;
+            events::record(
+                events::swap_removal(&swap, EmoteEventReason::Ejected)
+                    .actor(actor)
+                    .command("emote eject"),
+                pool,
+            )
+            .await;
             swap.name
         }
     };
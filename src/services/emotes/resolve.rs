@@ -0,0 +1,80 @@
+use crate::{
+    chat::parse::opt_next_space,
+    services::{
+        emotes::{Emote, EmoteRW},
+        rewards::{
+            error::user_error,
+            extract::{self, EmoteSpec},
+        },
+    },
+    text,
+};
+use anyhow::{anyhow, Result as AnyResult};
+use models::{emote::SlotPlatform, reward::EmoteSearchMode};
+use std::fmt::Display;
+
+/// Resolves the emote name in `input` using the platform's emote search.
+pub async fn search_by_name<RW>(
+    input: &str,
+    mode: EmoteSearchMode,
+) -> AnyResult<String>
+where
+    RW: EmoteRW,
+    RW::Emote: Emote<RW::EmoteId>,
+    RW::EmoteId: Display,
+{
+    let name = opt_next_space(input.trim()).0;
+    if name.is_empty() {
+        return Err(user_error(text!("reward.emote.none_submitted")));
+    }
+
+    let emotes = RW::search_emotes(name).await.map_err(|e| {
+        anyhow!("Couldn't search for {} emotes ({e})", RW::platform())
+    })?;
+    // the search returns the most used emotes first
+    let exact = emotes
+        .iter()
+        .filter(|e| e.name() == name)
+        .collect::<Vec<_>>();
+    let similar =
+        || emotes.iter().find(|e| e.name().eq_ignore_ascii_case(name));
+
+    let emote = match mode {
+        EmoteSearchMode::Exact => exact.first().copied(),
+        EmoteSearchMode::MostUsed => exact.first().copied().or_else(similar),
+        EmoteSearchMode::ErrorIfAmbiguous => match exact[..] {
+            [emote] => Some(emote),
+            [] => None,
+            _ => {
+                return Err(user_error(text!(
+                    "reward.emote.ambiguous",
+                    n = exact.len().to_string(),
+                    name = name
+                )))
+            }
+        },
+    };
+
+    emote.map(|e| e.id().to_string()).ok_or_else(|| {
+        user_error(text!(
+            "reward.emote.not_found_named",
+            platform = RW::platform().to_string(),
+            name = name
+        ))
+    })
+}
+
+/// Builds the spec for an emote found by [search_by_name].
+pub fn searched_spec<'a, RW: EmoteRW>(
+    input: &'a str,
+    id: &'a str,
+) -> EmoteSpec<'a> {
+    EmoteSpec {
+        id,
+        // only 7TV supports aliases
+        override_name: match RW::platform() {
+            SlotPlatform::SevenTv => extract::parse_overridden(input),
+            _ => None,
+        },
+    }
+}
@@ -1,12 +1,17 @@
 use crate::{
     services::{
-        emotes::{Emote, EmoteEnvData, EmoteInitialData, EmoteRW},
+        emotes::{
+            ban_rules::EmoteProperties, Emote, EmoteEnvData, EmoteInitialData,
+            EmoteRW,
+        },
+        rewards::error::user_error,
         seven_tv::requests as seven_tv,
     },
-    RedisPool,
+    text, RedisPool,
 };
 use anyhow::{anyhow, bail, Error as AnyError, Result as AnyResult};
 use async_trait::async_trait;
+use config::CONFIG;
 use deadpool_redis::redis::{self, AsyncCommands as _};
 use futures::TryFutureExt;
 use models::{emote::SlotPlatform, swap_emote::SwapEmote};
@@ -28,6 +33,17 @@ impl Emote<String> for seven_tv::SevenEmote {
     fn into_name(self) -> String {
         self.name
     }
+
+    fn properties(&self) -> EmoteProperties {
+        EmoteProperties {
+            name: self.name.clone(),
+            owner_id: self.owner.as_ref().map(|o| o.id.clone()),
+            tags: self.tags.clone(),
+            zero_width: self.flags & seven_tv::SEVENTV_FLAG_ZERO_WIDTH != 0,
+            nsfw: self.flags & seven_tv::SEVENTV_FLAG_NSFW != 0,
+            animated: self.animated,
+        }
+    }
 }
 
 async fn was_emote_removed(
@@ -98,7 +114,7 @@ impl EmoteRW for SevenTvEmotes {
             SwapEmote::emote_count(broadcaster_id, reward_id, pool)
                 .map_err(|_| AnyError::msg("Could not get past emotes")),
             seven_tv::get_emote(emote_id)
-                .map_err(|_| AnyError::msg("This emote doesn't exist.")),
+                .map_err(|_| user_error(text!("reward.emote.doesnt_exist"))),
             seven_tv::get_user(broadcaster_id)
                 .map_err(|_| AnyError::msg("No such user?!")),
         )
@@ -118,15 +134,11 @@ impl EmoteRW for SevenTvEmotes {
             .any(|e| e.id == emote.id || e.name == actual_name)
             && !was_emote_removed(&stv_set.id, emote_id, redis_pool).await
         {
-            return Err(AnyError::msg(
-                "The emote or an emote with the same name already exists.",
-            ));
+            return Err(user_error(text!("reward.emote.already_added")));
         }
 
         if !allow_unlisted && !emote.listed {
-            return Err(AnyError::msg(
-                "Attempted to add an unlisted emote, but unlisted emotes aren't allowed.",
-            ));
+            return Err(user_error(text!("reward.emote.unlisted")));
         }
 
         Ok(EmoteInitialData {
@@ -178,6 +190,10 @@ impl EmoteRW for SevenTvEmotes {
             .map(|s| s.emotes)
     }
 
+    async fn search_emotes(query: &str) -> AnyResult<Vec<Self::Emote>> {
+        seven_tv::search_emotes(query).await
+    }
+
     async fn remove_emote(
         platform_id: &Self::PlatformId,
         emote_id: &Self::EmoteId,
@@ -248,7 +264,7 @@ impl EmoteRW for SevenTvEmotes {
     }
 
     fn format_emote_url(emote_id: &str) -> String {
-        format!("https://cdn.7tv.app/emote/{}/4x", emote_id)
+        format!("{}/emote/{}/4x", CONFIG.urls.seven_tv_cdn, emote_id)
     }
 
     fn format_emote_page(emote_id: &str) -> String {
@@ -1,19 +1,28 @@
 use crate::{
     log_err,
     services::{
-        emotes::{Emote, EmoteId, EmoteRW},
+        emotes::{
+            ban_rules::check_ban_rules, blocklist::check_blocklist, events,
+            Emote, EmoteId, EmoteRW,
+        },
+        rewards::error::user_error,
         text::trim_to,
         twitch::requests::update_reward,
     },
-    RedisPool,
+    text, RedisPool,
 };
 use anyhow::{Error as AnyError, Result as AnyResult};
 use chrono::{Duration, Utc};
 use futures::TryFutureExt;
 use models::{
-    banned_emote, log_entry::LogEntry, reward::SlotRewardData, slot::Slot,
+    banned_emote,
+    emote_event::{EmoteEventKind, EmoteEventReason, NewEmoteEvent},
+    log_entry::{LogEntry, LogEntryType},
+    reward::SlotRewardData,
+    slot::Slot,
     user::User,
 };
+use serde_json::json;
 use sqlx::PgPool;
 use std::{cmp::Ordering, fmt::Display};
 use twitch_api::{
@@ -166,7 +175,7 @@ where
     if banned_emote::is_banned(broadcaster_id, emote_id, RW::platform(), pool)
         .await?
     {
-        return Err(AnyError::msg("This emote is banned"));
+        return Err(user_error(text!("reward.emote.banned")));
     }
     let available_slots =
         Slot::get_available_slots(broadcaster_id, reward_id, pool)
@@ -179,7 +188,7 @@ where
     let mut slot = available_slots
         .into_iter()
         .next()
-        .ok_or_else(|| AnyError::msg("No free slot is available!"))?;
+        .ok_or_else(|| user_error(text!("reward.slot.none_available")))?;
     let emote_data = RW::get_check_initial_data(
         broadcaster_id,
         emote_id,
@@ -192,8 +201,16 @@ where
     .await?;
 
     if emote_data.current_emotes >= emote_data.max_emotes {
-        return Err(AnyError::msg("There's no free slot!"));
+        return Err(user_error(text!("reward.slot.platform_full")));
     }
+    check_blocklist::<RW>(&emote_data.emote, pool).await?;
+    check_ban_rules::<RW>(
+        broadcaster_id,
+        &emote_data.emote,
+        override_name,
+        pool,
+    )
+    .await?;
 
     RW::add_emote(
         &emote_data.platform_id,
@@ -229,6 +246,20 @@ where
         log::warn!("Failed to update reward-slot: {}", e);
         AnyError::msg("Internal error")
     })?;
+    events::record(
+        NewEmoteEvent::new(
+            broadcaster_id,
+            RW::platform(),
+            EmoteEventKind::Added,
+            EmoteEventReason::Redeemed,
+            slot.emote_id.as_deref().unwrap_or_default(),
+            &emote_name,
+        )
+        .actor(redeemed_user_login)
+        .reward(reward_id),
+        pool,
+    )
+    .await;
 
     // disable reward if all slots are full now
     if n_available == 1 {
@@ -258,19 +289,23 @@ where
 
     // TODO: log::info
     log_err!(
-        LogEntry::create(
+        LogEntry::new(
             broadcaster_id,
-            &format!(
-                "[slots::{:?}] Added {}; slots-open={}; expires={:?}; redeemed={}; slot_id={}",
-                RW::platform(),
-                emote_name,
-                n_available - 1,
-                slot.expires.map(|exp| exp.to_string()),
-                redeemed_user_login,
-                slot.id
-            ),
-            pool
+            LogEntryType::EmoteAdded,
+            format!("Added {emote_name} ({:?})", RW::platform()),
         )
+        .actor(redeemed_user_login)
+        .target(&emote_name)
+        .reward(reward_id)
+        .payload(json!({
+            "kind": "slot",
+            "platform": RW::platform(),
+            "emote_id": slot.emote_id,
+            "slot_id": slot.id,
+            "slots_open": n_available - 1,
+            "expires": slot.expires,
+        }))
+        .insert(pool)
         .await,
         "Could not create log-entry"
     );
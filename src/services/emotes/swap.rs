@@ -1,143 +1,334 @@
 use crate::{
     log_err,
     services::{
-        emotes::{Emote, EmoteRW},
+        emotes::{
+            ban_rules::check_ban_rules, blocklist::check_blocklist,
+            bttv::BttvEmotes, events, ffz::FfzEmotes, seven_tv::SevenTvEmotes,
+            Emote, EmoteInitialData, EmoteRW,
+        },
+        rewards::{
+            error::{is_user_error, user_error},
+            extract::EmoteSpec,
+        },
         text::trim_to,
     },
-    RedisPool,
+    text, RedisPool,
 };
 use anyhow::{anyhow, Error as AnyError, Result as AnyResult};
 use models::{
-    banned_emote, log_entry::LogEntry, reward::SwapRewardData,
+    banned_emote,
+    emote::SlotPlatform,
+    emote_event::{EmoteEventKind, EmoteEventReason, NewEmoteEvent},
+    log_entry::{LogEntry, LogEntryType},
+    reward::SwapRewardData,
     swap_emote::SwapEmote,
 };
+use serde_json::json;
 use sqlx::PgPool;
 use std::{fmt::Display, str::FromStr};
 
+/// Adds all `emotes` or none of them.
+///
+/// Returns the names of the removed and the added emotes.
 pub async fn swap_or_add_emote<RW>(
     broadcaster_id: &str,
-    emote_id: &str,
+    emotes: &[EmoteSpec<'_>],
     reward_id: &str,
-    override_name: Option<&str>,
     reward_data: SwapRewardData,
     executing_user: &str,
     pool: &PgPool,
     redis_pool: &RedisPool,
-) -> AnyResult<(Option<String>, String)>
+) -> AnyResult<(Vec<String>, Vec<String>)>
 where
     RW: EmoteRW,
     RW::PlatformId: Display,
     RW::Emote: Emote<RW::EmoteId>,
     RW::EmoteId: Display + ToOwned<Owned = RW::EmoteId> + FromStr + Default,
 {
-    if banned_emote::is_banned(broadcaster_id, emote_id, RW::platform(), pool)
-        .await?
-    {
-        return Err(AnyError::msg("This emote is banned"));
+    let mut checked = Vec::with_capacity(emotes.len());
+    let mut failed = Vec::new();
+    for spec in emotes {
+        match check_emote::<RW>(
+            broadcaster_id,
+            spec,
+            reward_id,
+            reward_data.allow_unlisted,
+            pool,
+            redis_pool,
+        )
+        .await
+        {
+            Ok(data) => checked.push((spec, data)),
+            Err(e) => failed.push((spec.id, e)),
+        }
     }
 
-    let data = RW::get_check_initial_data(
-        broadcaster_id,
-        emote_id,
-        reward_id,
-        override_name,
-        reward_data.allow_unlisted,
-        pool,
-        redis_pool,
-    )
-    .await?;
+    // the platform only checks against emotes that are already added
+    for (i, (spec, data)) in checked.iter().enumerate() {
+        let name = spec.override_name.unwrap_or(data.emote.name());
+        if checked[..i].iter().any(|(other, other_data)| {
+            other.id == spec.id
+                || other.override_name.unwrap_or(other_data.emote.name())
+                    == name
+        }) {
+            failed.push((spec.id, user_error(text!("reward.swap.duplicate"))));
+        }
+    }
+
+    if emotes.len() == 1 && !failed.is_empty() {
+        return Err(failed.swap_remove(0).1);
+    }
+    if !failed.is_empty() {
+        let all_user_errors = failed.iter().all(|(_, e)| is_user_error(e));
+        let failed = failed
+            .into_iter()
+            .map(|(id, e)| format!("{id}: {e}"))
+            .collect::<Vec<_>>();
+        let msg = text!(
+            "reward.swap.nothing_added",
+            errors = trim_to(failed.join(", "), 380)
+        );
+        return Err(if all_user_errors {
+            user_error(msg)
+        } else {
+            AnyError::new(msg)
+        });
+    }
+    let Some((_, data)) = checked.first() else {
+        return Err(user_error(text!("reward.emote.not_found")));
+    };
 
-    // remove emote if needed
+    let n_emotes = checked.len();
+    if reward_data.limit.is_some_and(|l| n_emotes > l as usize)
+        || n_emotes > data.max_emotes
+    {
+        return Err(user_error(text!(
+            "reward.swap.too_many",
+            n = n_emotes.to_string()
+        )));
+    }
+
+    // remove emotes if needed - each added emote counts towards the limits
     let above_swap_limit = reward_data
         .limit
-        .map(|l| data.swap_history_len >= l as usize)
-        .unwrap_or(false);
-    let above_platform_limit = data.current_emotes >= data.max_emotes;
+        .map(|l| (data.swap_history_len + n_emotes).saturating_sub(l as usize))
+        .unwrap_or(0);
+    let above_platform_limit =
+        (data.current_emotes + n_emotes).saturating_sub(data.max_emotes);
+    let platform_id = &data.platform_id;
 
-    let removed_emote = if above_platform_limit || above_swap_limit {
-        Some(
-            remove_last_emote::<RW>(
-                broadcaster_id,
-                &data.platform_id,
-                reward_id,
-                pool,
-                redis_pool,
-            )
-            .await
-            .0?,
+    // Slots on the platform have to be freed before adding. If anything fails,
+    // these emotes are restored, so nothing changes.
+    let mut freed = Vec::with_capacity(above_platform_limit);
+    for _ in 0..above_platform_limit {
+        match remove_last_emote::<RW>(
+            broadcaster_id,
+            platform_id,
+            reward_id,
+            Some(RW::platform()),
+            pool,
+            redis_pool,
         )
-    } else {
-        None
-    };
+        .await
+        .0
+        {
+            Ok(emote) => freed.push(emote),
+            Err(e) => {
+                restore_emotes::<RW>(platform_id, &freed, pool, redis_pool)
+                    .await;
+                return Err(e);
+            }
+        }
+    }
 
-    log::info!(
-        "Add emote_id={} to platform_id={}",
-        data.emote.id(),
-        data.platform_id
-    );
+    for (i, (spec, data)) in checked.iter().enumerate() {
+        log::info!(
+            "Add emote_id={} to platform_id={}",
+            data.emote.id(),
+            platform_id
+        );
 
-    if let Err(e) = RW::add_emote(
-        &data.platform_id,
-        data.emote.id(),
-        override_name,
-        redis_pool,
-    )
-    .await
-    {
-        log::warn!("Could not add emote: {} / Removed: {removed_emote:?}", e);
-        let msg = match removed_emote {
-            Some(ref name) => {
-                format!("Removed {name}, but couldn't add emote: {e}")
+        if let Err(e) = RW::add_emote(
+            platform_id,
+            data.emote.id(),
+            spec.override_name,
+            redis_pool,
+        )
+        .await
+        {
+            log::warn!("Could not add emote: {} / Freed: {freed:?}", e);
+            // roll back the emotes we added in this redemption
+            for (_, added) in &checked[..i] {
+                log_err!(
+                    RW::remove_emote(platform_id, added.emote.id(), redis_pool)
+                        .await,
+                    "Could not roll back emote"
+                );
             }
-            None => format!("Couldn't add emote: {}", e),
-        };
+            restore_emotes::<RW>(platform_id, &freed, pool, redis_pool).await;
 
-        return Err(AnyError::msg(trim_to(msg, 200)));
+            return Err(AnyError::msg(trim_to(
+                format!("Couldn't add {}: {e}", spec.id),
+                200,
+            )));
+        }
     }
 
-    let emote_name = override_name.unwrap_or(data.emote.name());
+    let mut removed_emotes = Vec::with_capacity(above_swap_limit);
+    for emote in freed {
+        events::record(
+            events::swap_removal(&emote, EmoteEventReason::SwappedOut),
+            pool,
+        )
+        .await;
+        removed_emotes.push(emote.name);
+    }
+    // the freed emotes were part of the history as well
+    for _ in above_platform_limit..above_swap_limit {
+        match remove_last_emote::<RW>(
+            broadcaster_id,
+            platform_id,
+            reward_id,
+            None,
+            pool,
+            redis_pool,
+        )
+        .await
+        .0
+        {
+            Ok(emote) => {
+                events::record(
+                    events::swap_removal(&emote, EmoteEventReason::SwappedOut),
+                    pool,
+                )
+                .await;
+                removed_emotes.push(emote.name);
+            }
+            // the remaining entries were already gone from the platform
+            Err(e) => {
+                log::info!("Could not enforce the swap limit: {e}");
+                break;
+            }
+        }
+    }
 
-    SwapEmote::add_or_update(
-        broadcaster_id,
-        &data.emote.id().to_string(),
-        RW::platform(),
-        emote_name,
-        executing_user,
-        reward_id,
-        pool,
-    )
-    .await
-    .map_err(|e| anyhow!("Could not save emote in DB ({})", e.error))?;
+    let removed =
+        (!removed_emotes.is_empty()).then(|| removed_emotes.join(", "));
+    let mut added_emotes = Vec::with_capacity(n_emotes);
+    for (spec, data) in checked {
+        let emote_name = spec.override_name.unwrap_or(data.emote.name());
+        let emote_id = data.emote.id().to_string();
 
-    log_err!(
-        LogEntry::create(
+        SwapEmote::add_or_update(
             broadcaster_id,
-            &format!(
-                "[swap::{:?}] Added {} (alias {override_name:?}); Removed {removed_emote:?}; redeemed={executing_user}",
+            &emote_id,
+            RW::platform(),
+            emote_name,
+            executing_user,
+            reward_id,
+            pool,
+        )
+        .await
+        .map_err(|e| anyhow!("Could not save emote in DB ({})", e.error))?;
+        events::record(
+            NewEmoteEvent::new(
+                broadcaster_id,
                 RW::platform(),
+                EmoteEventKind::Added,
+                EmoteEventReason::Redeemed,
+                &emote_id,
                 emote_name,
-            ),
-            pool
+            )
+            .actor(executing_user)
+            .reward(reward_id),
+            pool,
         )
-        .await,
-        "Could not create log-entry"
-    );
-
-    Ok((
-        removed_emote,
-        override_name
-            .map(ToOwned::to_owned)
-            .unwrap_or_else(|| data.emote.into_name()),
-    ))
+        .await;
+
+        log_err!(
+            LogEntry::new(
+                broadcaster_id,
+                LogEntryType::EmoteAdded,
+                format!("Added {emote_name} ({:?})", RW::platform()),
+            )
+            .actor(executing_user)
+            .target(emote_name)
+            .reward(reward_id)
+            .payload(json!({
+                "kind": "swap",
+                "platform": RW::platform(),
+                "emote_id": emote_id,
+                "alias": spec.override_name,
+                "removed": removed,
+            }))
+            .insert(pool)
+            .await,
+            "Could not create log-entry"
+        );
+
+        added_emotes.push(
+            spec.override_name
+                .map(ToOwned::to_owned)
+                .unwrap_or_else(|| data.emote.into_name()),
+        );
+    }
+
+    Ok((removed_emotes, added_emotes))
 }
 
+async fn check_emote<RW>(
+    broadcaster_id: &str,
+    spec: &EmoteSpec<'_>,
+    reward_id: &str,
+    allow_unlisted: bool,
+    pool: &PgPool,
+    redis_pool: &RedisPool,
+) -> AnyResult<EmoteInitialData<RW::PlatformId, RW::Emote>>
+where
+    RW: EmoteRW,
+    RW::Emote: Emote<RW::EmoteId>,
+    RW::EmoteId: Display,
+{
+    if banned_emote::is_banned(broadcaster_id, spec.id, RW::platform(), pool)
+        .await?
+    {
+        return Err(user_error(text!("reward.emote.banned")));
+    }
+
+    let data = RW::get_check_initial_data(
+        broadcaster_id,
+        spec.id,
+        reward_id,
+        spec.override_name,
+        allow_unlisted,
+        pool,
+        redis_pool,
+    )
+    .await?;
+    check_blocklist::<RW>(&data.emote, pool).await?;
+    check_ban_rules::<RW>(
+        broadcaster_id,
+        &data.emote,
+        spec.override_name,
+        pool,
+    )
+    .await?;
+
+    Ok(data)
+}
+
+/// Removes the oldest emote of the reward. If `platform` is set, only emotes
+/// on that platform are considered.
+///
+/// The caller records the removal, since it might still be rolled back.
 pub async fn remove_last_emote<RW>(
     user_id: &str,
     platform_id: &RW::PlatformId,
     reward_id: &str,
+    platform: Option<SlotPlatform>,
     pool: &PgPool,
     redis_pool: &RedisPool,
-) -> (AnyResult<String>, usize)
+) -> (AnyResult<SwapEmote>, usize)
 where
     RW: EmoteRW,
     RW::PlatformId: Display,
@@ -146,16 +337,32 @@ where
 {
     let mut emote = None;
     let mut removed_from_db = 0;
-    while let Ok(Some(db_emote)) =
-        SwapEmote::oldest(user_id, reward_id, pool).await
-    {
-        let actually_removed = if let Err(e) = RW::remove_emote(
-            platform_id,
-            &RW::EmoteId::from_str(&db_emote.emote_id).unwrap_or_default(),
-            redis_pool,
-        )
-        .await
-        {
+    while let Ok(Some(db_emote)) = match platform {
+        Some(platform) => {
+            SwapEmote::oldest_on_platform(user_id, reward_id, platform, pool)
+                .await
+        }
+        None => SwapEmote::oldest(user_id, reward_id, pool).await,
+    } {
+        // rewards accepting any platform share their history
+        let res = if db_emote.platform == RW::platform() {
+            RW::remove_emote(
+                platform_id,
+                &RW::EmoteId::from_str(&db_emote.emote_id).unwrap_or_default(),
+                redis_pool,
+            )
+            .await
+        } else {
+            remove_from_platform(
+                db_emote.platform,
+                user_id,
+                &db_emote.emote_id,
+                pool,
+                redis_pool,
+            )
+            .await
+        };
+        let actually_removed = if let Err(e) = res {
             log::info!("Skipping emote: {:?}; error={}", db_emote, e);
             false
         } else {
@@ -173,7 +380,7 @@ where
     }
 
     (
-        emote.map(|e| e.name).ok_or_else(|| {
+        emote.ok_or_else(|| {
             log::info!("Could not remove any emotes in {}.", user_id);
             AnyError::msg(
                 "There are no recent emotes to remove - refusing to remove random emote.",
@@ -183,6 +390,111 @@ where
     )
 }
 
+/// Adds emotes removed by [remove_last_emote] back to the platform and the
+/// history.
+async fn restore_emotes<RW>(
+    platform_id: &RW::PlatformId,
+    emotes: &[SwapEmote],
+    pool: &PgPool,
+    redis_pool: &RedisPool,
+) where
+    RW: EmoteRW,
+    RW::EmoteId: FromStr + Default,
+{
+    for emote in emotes {
+        log::info!("Restoring emote: {:?}", emote);
+        log_err!(
+            RW::add_emote(
+                platform_id,
+                &RW::EmoteId::from_str(&emote.emote_id).unwrap_or_default(),
+                Some(&emote.name),
+                redis_pool,
+            )
+            .await,
+            "Could not restore emote"
+        );
+        log_err!(emote.restore(pool).await, "Could not restore swap emote");
+    }
+}
+
+async fn remove_from_platform(
+    platform: SlotPlatform,
+    broadcaster_id: &str,
+    emote_id: &str,
+    pool: &PgPool,
+    redis_pool: &RedisPool,
+) -> AnyResult<()> {
+    match platform {
+        SlotPlatform::Bttv => {
+            BttvEmotes::remove_emote_from_broadcaster(
+                broadcaster_id,
+                emote_id,
+                pool,
+                redis_pool,
+            )
+            .await
+        }
+        SlotPlatform::Ffz => {
+            FfzEmotes::remove_emote_from_broadcaster(
+                broadcaster_id,
+                emote_id,
+                pool,
+                redis_pool,
+            )
+            .await
+        }
+        SlotPlatform::SevenTv => {
+            SevenTvEmotes::remove_emote_from_broadcaster(
+                broadcaster_id,
+                emote_id,
+                pool,
+                redis_pool,
+            )
+            .await
+        }
+    }
+    .map(|_| ())
+}
+
+/// Like [update_swap_limit] but for rewards accepting emotes from any platform.
+pub async fn update_any_swap_limit(
+    broadcaster_id: &str,
+    reward_id: &str,
+    limit: u16,
+    pool: &PgPool,
+    redis_pool: &RedisPool,
+) -> AnyResult<()> {
+    let mut current_emotes =
+        SwapEmote::emote_count(broadcaster_id, reward_id, pool).await? as usize;
+    while current_emotes > limit as usize {
+        let Some(db_emote) =
+            SwapEmote::oldest(broadcaster_id, reward_id, pool).await?
+        else {
+            break;
+        };
+        if let Err(e) = remove_from_platform(
+            db_emote.platform,
+            broadcaster_id,
+            &db_emote.emote_id,
+            pool,
+            redis_pool,
+        )
+        .await
+        {
+            log::info!("Skipping emote: {:?}; error={}", db_emote, e);
+        } else {
+            events::record(
+                events::swap_removal(&db_emote, EmoteEventReason::SwappedOut),
+                pool,
+            )
+            .await;
+        }
+        SwapEmote::remove(db_emote.id, pool).await?;
+        current_emotes -= 1;
+    }
+    Ok(())
+}
+
 pub async fn update_swap_limit<RW>(
     broadcaster_id: &str,
     reward_id: &str,
@@ -207,12 +519,17 @@ where
                 broadcaster_id,
                 &platform_id,
                 reward_id,
+                None,
                 pool,
                 redis_pool,
             )
             .await;
             current_emotes -= removed;
-            let _ = res?;
+            events::record(
+                events::swap_removal(&res?, EmoteEventReason::SwappedOut),
+                pool,
+            )
+            .await;
 
             if current_emotes <= limit {
                 break;
@@ -1,85 +1,138 @@
 use crate::{
-    services::twitch::{eventsub::delete_subscription, RHelixClient},
+    actors::{
+        irc::{IrcActor, PartMessage},
+        live::{self, LiveActor},
+        rewards::RewardsActor,
+        timeout::{self, TimeoutActor},
+    },
+    log_discord,
+    services::{
+        rewards::redemption::{
+            self, ReceiveRedemptionCtx, ReceiveRedemptionError,
+        },
+        twitch::{
+            self,
+            eventsub::{delete_subscription, subscribe_to, webhook_transport},
+            RHelixClient,
+        },
+    },
     util::result::{ResultCExt, ResultExt as _},
 };
+use actix::Addr;
 use actix_web::Result as ActixResult;
 use anyhow::Result as AnyhowResult;
-use config::CONFIG;
+use config::{EventSubTransport, CONFIG};
 use futures::FutureExt;
 use models::user::User;
 use regex::Regex;
 use sqlx::PgPool;
-use std::sync::Arc;
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
 use tokio::sync::RwLock;
 use twitch_api::{
     eventsub::{
         channel::{
-            ChannelModerateV2, ChannelPointsCustomRewardRedemptionAddV1,
+            moderate::ActionV2, ChannelModerateV2,
+            ChannelPointsCustomRewardRedemptionAddV1,
         },
         stream::StreamOnlineV1,
-        EventSubscription, Status, TransportResponse,
+        user::{UserAuthorizationRevokeV1, UserAuthorizationRevokeV1Payload},
+        Event, EventSubscription, Message, Payload, Status, Transport,
+        TransportResponse,
     },
     helix::eventsub::{
         CreateEventSubSubscription, EventSubSubscriptions,
         GetEventSubSubscriptionsRequest,
     },
-    twitch_oauth2::AppAccessToken,
+    twitch_oauth2::{AppAccessToken, UserToken},
 };
 
-use super::twitch::HelixResult;
+/// The id of the current EventSub websocket session (if connected).
+static WEBSOCKET_SESSION: Mutex<Option<String>> = Mutex::new(None);
 
-trait KnownSub: EventSubscription {
-    async fn subscribe(
-        id: &str,
-        token: &AppAccessToken,
-    ) -> HelixResult<CreateEventSubSubscription<Self>>;
+pub fn set_websocket_session(session_id: Option<String>) {
+    *WEBSOCKET_SESSION.lock().unwrap_or_else(|e| e.into_inner()) = session_id;
+}
+
+fn websocket_transport() -> ActixResult<Transport> {
+    WEBSOCKET_SESSION
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .as_ref()
+        .map(Transport::websocket)
+        .ok_or_else(|| {
+            errors::ErrorInternalServerError("No EventSub websocket session")
+        })
+}
+
+trait KnownSub: EventSubscription + Send {
+    /// When subscribing over a websocket, the subscription is created with
+    /// the token of the bot instead of the one of the broadcaster.
+    const BOT_AUTHORIZED: bool = false;
+
+    fn for_broadcaster(id: &str) -> Self;
 }
 
 impl KnownSub for ChannelPointsCustomRewardRedemptionAddV1 {
-    async fn subscribe(
-        id: &str,
-        token: &AppAccessToken,
-    ) -> HelixResult<CreateEventSubSubscription<Self>> {
-        super::twitch::eventsub::subscribe_to(
-            token,
-            Self::broadcaster_user_id(id),
-        )
-        .await
+    fn for_broadcaster(id: &str) -> Self {
+        Self::broadcaster_user_id(id)
     }
 }
 
 impl KnownSub for StreamOnlineV1 {
-    async fn subscribe(
-        id: &str,
-        token: &AppAccessToken,
-    ) -> HelixResult<CreateEventSubSubscription<Self>> {
-        super::twitch::eventsub::subscribe_to(
-            token,
-            Self::broadcaster_user_id(id),
-        )
-        .await
+    fn for_broadcaster(id: &str) -> Self {
+        Self::broadcaster_user_id(id)
     }
 }
 
 impl KnownSub for ChannelModerateV2 {
-    async fn subscribe(
-        id: &str,
-        token: &AppAccessToken,
-    ) -> HelixResult<CreateEventSubSubscription<Self>> {
-        super::twitch::eventsub::subscribe_to(
-            token,
-            Self::new(id, CONFIG.twitch.user_id.as_str()),
-        )
-        .await
+    const BOT_AUTHORIZED: bool = true;
+
+    fn for_broadcaster(id: &str) -> Self {
+        Self::new(id, CONFIG.twitch.user_id.as_str())
     }
 }
 
+async fn subscribe<S: KnownSub>(
+    id: &str,
+    token: &AppAccessToken,
+    pool: &PgPool,
+) -> ActixResult<CreateEventSubSubscription<S>> {
+    let sub = match CONFIG.twitch.eventsub.transport {
+        EventSubTransport::Webhook => {
+            subscribe_to(token, S::for_broadcaster(id), webhook_transport())
+                .await?
+        }
+        EventSubTransport::Websocket if S::BOT_AUTHORIZED => {
+            subscribe_to(
+                &twitch::get_token(),
+                S::for_broadcaster(id),
+                websocket_transport()?,
+            )
+            .await?
+        }
+        EventSubTransport::Websocket => {
+            let user_token: UserToken = User::get_by_id(id, pool).await?.into();
+            subscribe_to(
+                &user_token,
+                S::for_broadcaster(id),
+                websocket_transport()?,
+            )
+            .await?
+        }
+    };
+
+    Ok(sub)
+}
+
 async fn register_for_id<S: KnownSub>(
     id: &str,
     token: &AppAccessToken,
     pool: &PgPool,
 ) -> ActixResult<()> {
-    let sub = S::subscribe(id, token).await?;
+    let sub = subscribe::<S>(id, token, pool).await?;
 
     models::eventsub::add(&sub.id, id, S::EVENT_TYPE.to_str(), pool)
         .await
@@ -116,6 +169,13 @@ pub async fn unregister_eventsub_for_user(
     token: &Arc<RwLock<AppAccessToken>>,
     pool: &PgPool,
 ) -> ActixResult<()> {
+    // websocket subscriptions can't be deleted with the app access token,
+    // they end with the session
+    if CONFIG.twitch.eventsub.transport == EventSubTransport::Websocket {
+        return models::eventsub::remove_all_for_user(id, pool)
+            .await
+            .err_into();
+    }
     let token = token.read().await;
 
     let ids = models::eventsub::all_for_user(id, pool).await?;
@@ -125,6 +185,11 @@ pub async fn unregister_eventsub_for_user(
             .map(|id| delete_subscription(&token, id).map(|_| ())),
     )
     .await;
+    for id in &ids {
+        models::eventsub::remove(id.as_str().into(), pool)
+            .await
+            .dbg_if_err("removing eventsub from db");
+    }
 
     Ok(())
 }
@@ -236,3 +301,158 @@ pub async fn clear_invalid_subs(
 
     Ok(())
 }
+
+/// Registers all subscriptions for all users on a new websocket session.
+pub async fn register_eventsub_for_all(
+    token: &Arc<RwLock<AppAccessToken>>,
+    pool: &PgPool,
+) -> AnyhowResult<()> {
+    let users = User::get_all(pool).await?;
+    log::info!("Registering eventsub for {} users", users.len());
+
+    for user in users {
+        // the subscriptions of the previous session are gone
+        models::eventsub::remove_all_for_user(&user.id, pool)
+            .await
+            .log_if_err("clearing eventsubs of the previous session");
+        register_all_eventsub_for_id(&user.id, token, pool)
+            .await
+            .inspect_err(|e| {
+                log::warn!("Register eventsub for {}: {e}", user.name)
+            })
+            .ok();
+    }
+
+    Ok(())
+}
+
+/// Everything needed to handle an incoming event
+/// (regardless of the transport it was received with).
+pub struct EventCtx {
+    pub pool: Arc<PgPool>,
+    pub app_access_token: Arc<RwLock<AppAccessToken>>,
+    pub executor: Arc<Addr<RewardsActor>>,
+    pub live_actor: Addr<LiveActor>,
+    pub timeout_actor: Addr<TimeoutActor>,
+    pub irc: Addr<IrcActor>,
+}
+
+pub async fn handle_event(event: Event, ctx: &EventCtx) -> ActixResult<()> {
+    match event {
+        Event::ChannelPointsCustomRewardRedemptionAddV1(Payload {
+            message: Message::Notification(notification),
+            subscription,
+            ..
+        }) => {
+            // main path
+            let user = User::get_by_id(
+                notification.broadcaster_user_id.as_ref(),
+                &ctx.pool,
+            )
+            .await?;
+
+            log::info!(
+                "redemption: {:?} - sub: {:?}",
+                notification,
+                subscription
+            );
+
+            let ctx = ReceiveRedemptionCtx {
+                pool: ctx.pool.clone(),
+                executor: ctx.executor.clone(),
+                user,
+                notification,
+            };
+            actix_web::rt::spawn(async move {
+                match redemption::receive(ctx).await {
+                    Ok(_) => (),
+                    Err(ReceiveRedemptionError::NoReward) => (),
+                }
+            });
+        }
+
+        Event::StreamOnlineV1(Payload {
+            message: Message::Notification(notification),
+            ..
+        }) => {
+            ctx.live_actor.do_send(live::LiveMessage(
+                notification.broadcaster_user_id.take(),
+            ));
+        }
+        Event::StreamOfflineV1(Payload {
+            message: Message::Notification(notification),
+            ..
+        }) => {
+            ctx.live_actor.do_send(live::OfflineMessage(
+                notification.broadcaster_user_id.take(),
+            ));
+        }
+
+        Event::ChannelModerateV2(Payload {
+            message: Message::Notification(notification),
+            ..
+        }) => {
+            if let ActionV2::Untimeout(untimeout) = notification.action {
+                ctx.timeout_actor.do_send(timeout::RemoveTimeoutMessage {
+                    channel_id: notification.broadcaster_user_id.take(),
+                    user_id: untimeout.user_id.take(),
+                    later: Duration::from_secs(0),
+                });
+            }
+        }
+
+        Event::UserAuthorizationRevokeV1(Payload {
+            message: Message::Notification(notification),
+            ..
+        }) => {
+            handle_authorization_revoke(notification, ctx).await?;
+        }
+        other => {
+            log::warn!("unknown payload: {:?}", other);
+        }
+    }
+
+    Ok(())
+}
+
+/// The user disconnected the app on Twitch, so their token is dead.
+/// Their data is kept in case they log in again.
+async fn handle_authorization_revoke(
+    notification: UserAuthorizationRevokeV1Payload,
+    ctx: &EventCtx,
+) -> ActixResult<()> {
+    let user_id = notification.user_id.take();
+    let Ok(user) = User::get_by_id(&user_id, &ctx.pool).await else {
+        log::info!("auth revoke for unknown user {user_id}");
+        return Ok(());
+    };
+
+    User::set_revoked(&user.id, &ctx.pool).await?;
+    unregister_eventsub_for_user(&user.id, &ctx.app_access_token, &ctx.pool)
+        .await
+        .log_if_err("unregistering eventsub");
+    ctx.irc.do_send(PartMessage(user.name.clone()));
+
+    log::info!("AUTH: {} revoked the authorization", user.name);
+    log_discord!(
+        "Auth",
+        format!("❌ {} revoked the authorization", user.name),
+    );
+
+    Ok(())
+}
+
+/// Twitch only sends revocations for the client over webhooks.
+pub async fn register_authorization_revoke(
+    token: &Arc<RwLock<AppAccessToken>>,
+) -> AnyhowResult<()> {
+    let token = token.read().await;
+    subscribe_to(
+        &*token,
+        UserAuthorizationRevokeV1::new(CONFIG.twitch.client_id.clone()),
+        webhook_transport(),
+    )
+    .await?;
+
+    Ok(())
+}
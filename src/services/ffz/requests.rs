@@ -17,18 +17,20 @@ lazy_static! {
         .cookie_provider(Arc::new({
             let jar = Jar::default();
 
-            let url = "https://frankerfacez.com".parse::<Url>().unwrap();
+            let url = CONFIG.urls.ffz.parse::<Url>().unwrap();
+            let host = url.host_str().unwrap_or_default();
             jar.add_cookie_str(
                 &format!(
-                    "session={}; Domain=frankerfacez.com",
-                    CONFIG.emotes.ffz.session
+                    "session={}; Domain={}",
+                    CONFIG.emotes.ffz.session,
+                    host.trim_start_matches("www.")
                 ),
                 &url,
             );
             jar.add_cookie_str(
                 &format!(
-                    "remember_token={}; Domain=www.frankerfacez.com",
-                    CONFIG.emotes.ffz.remember
+                    "remember_token={}; Domain={}",
+                    CONFIG.emotes.ffz.remember, host
                 ),
                 &url,
             );
@@ -47,6 +49,18 @@ lazy_static! {
 pub struct FfzEmote {
     pub id: usize,
     pub name: String,
+    /// The urls of the animated images if the emote is animated
+    #[serde(default)]
+    pub animated: Option<HashMap<String, String>>,
+    #[serde(default)]
+    pub owner: Option<FfzEmoteOwner>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[non_exhaustive]
+pub struct FfzEmoteOwner {
+    #[serde(rename = "_id")]
+    pub id: usize,
 }
 
 impl PartialEq for FfzEmote {
@@ -95,29 +109,28 @@ struct FfzUserReply {
 
 pub async fn get_emote<T: Display>(id: T) -> AnyResult<FfzEmote> {
     ffz_get_json::<FfzEmoteReply, _>(format!(
-        "https://api.frankerfacez.com/v1/emote/{}",
-        id
+        "{}/emote/{}",
+        CONFIG.urls.ffz_api, id
     ))
     .await
     .map(|e| e.emote)
 }
 
 pub async fn get_room(id: &str) -> AnyResult<FfzRoomData> {
-    ffz_get_json(format!("https://api.frankerfacez.com/v1/room/id/{}", id))
-        .await
+    ffz_get_json(format!("{}/room/id/{}", CONFIG.urls.ffz_api, id)).await
 }
 
 pub async fn get_user(id: &str) -> AnyResult<FfzUser> {
     ffz_get_json::<FfzUserReply, _>(format!(
-        "https://api.frankerfacez.com/v1/user/id/{}",
-        id
+        "{}/user/id/{}",
+        CONFIG.urls.ffz_api, id
     ))
     .await
     .map(|u| u.user)
 }
 
 pub async fn get_channels() -> AnyResult<Vec<String>> {
-    let text = ffz_get_text("https://www.frankerfacez.com/").await?;
+    let text = ffz_get_text(format!("{}/", CONFIG.urls.ffz)).await?;
     Ok(CHANNEL_REGEX
         .captures_iter(&text)
         .filter_map(|c: Captures| {
@@ -131,7 +144,7 @@ pub async fn get_channels() -> AnyResult<Vec<String>> {
 }
 
 pub async fn logged_in() -> AnyResult<bool> {
-    let text = ffz_get_text("https://www.frankerfacez.com/").await?;
+    let text = ffz_get_text(format!("{}/", CONFIG.urls.ffz)).await?;
     Ok(CHANNEL_REGEX.is_match(&text))
 }
 
@@ -147,8 +160,8 @@ pub async fn add_emote(channel_id: usize, emote_id: usize) -> AnyResult<()> {
 
     let text = ffz_get_text_auth(
         format!(
-            "https://www.frankerfacez.com/emoticons/channel/True?channels={}&ids={}&unlock_code=",
-            channel_id, emote_id
+            "{}/emoticons/channel/True?channels={}&ids={}&unlock_code=",
+            CONFIG.urls.ffz, channel_id, emote_id
         ),
         &emote_id.to_string(),
     )
@@ -168,8 +181,8 @@ pub async fn delete_emote(channel_id: usize, emote_id: usize) -> AnyResult<()> {
 
     let text = ffz_get_text_auth(
         format!(
-            "https://www.frankerfacez.com/emoticons/channel/False?channels={}&ids={}&unlock_code=",
-            channel_id, emote_id
+            "{}/emoticons/channel/False?channels={}&ids={}&unlock_code=",
+            CONFIG.urls.ffz, channel_id, emote_id
         ),
         &emote_id.to_string(),
     )
@@ -219,7 +232,7 @@ where
         .get(url)
         .header(
             "Referer",
-            format!("https://www.frankerfacez.com/emoticon/{}", referer),
+            format!("{}/emoticon/{}", CONFIG.urls.ffz, referer),
         )
         .send()
         .and_then(Response::text)
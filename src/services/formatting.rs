@@ -1,39 +1,47 @@
+use crate::{services::i18n::Text, text};
+
 const S_IN_MNTH: u64 = 2628003; // 2628002,88 seconds according to Google
 
 /// This is basically the Short formatting if [`timeago`](https://docs.rs/timeago/0.0.2/src/timeago/lib.rs.html#24-98) without the "ago".
-pub fn human_format_duration(duration: &chrono::Duration) -> String {
+pub fn human_format_duration(duration: &chrono::Duration) -> Text {
     match duration.num_seconds() {
-        secs if secs > 0 => format!("{} ago", format_secs_short(secs as u64)),
+        secs if secs > 0 => {
+            text!("duration.ago", duration = format_secs_short(secs as u64))
+        }
         secs if secs < 0 => {
             let actual_secs = (-secs) as u64;
-            format!("in {}", format_secs_short(actual_secs))
+            text!("duration.in", duration = format_secs_short(actual_secs))
         }
-        _ => "now".into(),
+        _ => text!("duration.now"),
     }
 }
 
-fn format_secs_short(secs: u64) -> String {
+fn format_secs_short(secs: u64) -> Text {
     match secs {
-        0 => "0 seconds".into(), // never
-        1 => "1 second".into(),
-        x if x > 1 && x < 60 => format!("{} seconds", x),
-        x if (60..120).contains(&x) => "1 minute".into(),
-        x if (120..60 * 60).contains(&x) => format!("{} minutes", x / 60),
-        x if (60 * 60..60 * 60 * 2).contains(&x) => "1 hour".into(),
+        0 => text!("duration.seconds", n = "0"), // never
+        1 => text!("duration.second"),
+        x if x > 1 && x < 60 => text!("duration.seconds", n = x.to_string()),
+        x if (60..120).contains(&x) => text!("duration.minute"),
+        x if (120..60 * 60).contains(&x) => {
+            text!("duration.minutes", n = (x / 60).to_string())
+        }
+        x if (60 * 60..60 * 60 * 2).contains(&x) => text!("duration.hour"),
         x if (60 * 60 * 2..60 * 60 * 24).contains(&x) => {
-            format!("{} hours", x / 60 / 60)
+            text!("duration.hours", n = (x / 60 / 60).to_string())
+        }
+        x if (60 * 60 * 24..60 * 60 * 24 * 2).contains(&x) => {
+            text!("duration.day")
         }
-        x if (60 * 60 * 24..60 * 60 * 24 * 2).contains(&x) => "1 day".into(),
         x if (60 * 60 * 24 * 2..S_IN_MNTH).contains(&x) => {
-            format!("{} days", x / 60 / 60 / 24)
+            text!("duration.days", n = (x / 60 / 60 / 24).to_string())
         }
-        x if (S_IN_MNTH..2 * S_IN_MNTH).contains(&x) => "~1 month".into(),
+        x if (S_IN_MNTH..2 * S_IN_MNTH).contains(&x) => text!("duration.month"),
         x if (2 * S_IN_MNTH..12 * S_IN_MNTH).contains(&x) => {
-            format!("~{} months", x / S_IN_MNTH)
+            text!("duration.months", n = (x / S_IN_MNTH).to_string())
         }
         x if (12 * S_IN_MNTH..12 * 2 * S_IN_MNTH).contains(&x) => {
-            "~1 year".into()
+            text!("duration.year")
         }
-        x => format!("~{} years", x / 12 / S_IN_MNTH),
+        x => text!("duration.years", n = (x / 12 / S_IN_MNTH).to_string()),
     }
 }
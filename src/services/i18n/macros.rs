@@ -0,0 +1,12 @@
+/// Creates a [Text](crate::services::i18n::Text) from a message in the catalog.
+///
+/// ```ignore
+/// text!("chat.emote.banned", user = login, emote = url)
+/// ```
+#[macro_export]
+macro_rules! text {
+    ($key:literal $(, $name:ident = $value:expr)* $(,)?) => {
+        $crate::services::i18n::Text::new($key)
+            $(.arg(stringify!($name), $value))*
+    };
+}
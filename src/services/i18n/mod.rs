@@ -0,0 +1,262 @@
+pub mod macros;
+
+use crate::services::template;
+use anyhow::{bail, Error as AnyError, Result as AnyResult};
+use config::CONFIG;
+use lazy_static::lazy_static;
+use std::{collections::HashMap, fmt, fs, path::Path};
+
+/// The locale that contains every message. Other locales fall back to it.
+pub const DEFAULT_LOCALE: &str = "en";
+
+const ENGLISH: &str = include_str!("../../../locales/en.json");
+
+type Messages = HashMap<String, String>;
+
+lazy_static! {
+    pub static ref CATALOG: Catalog = Catalog::load(&CONFIG.bot.locales_dir);
+}
+
+pub struct Catalog {
+    english: Messages,
+    translations: HashMap<String, Messages>,
+}
+
+impl Catalog {
+    /// Loads the english messages and the translations (`<locale>.json`)
+    /// in `dir`. Invalid translations are skipped.
+    fn load(dir: &str) -> Self {
+        let english: Messages =
+            serde_json::from_str(ENGLISH).expect("english catalog must parse");
+        let mut translations = HashMap::new();
+
+        let entries = match fs::read_dir(dir) {
+            Ok(entries) => entries,
+            Err(e) => {
+                log::warn!("Could not read translations from {dir}: {e}");
+                return Self {
+                    english,
+                    translations,
+                };
+            }
+        };
+        for path in entries.filter_map(|e| e.ok().map(|e| e.path())) {
+            let Some(locale) = locale_of(&path) else {
+                continue;
+            };
+            if locale == DEFAULT_LOCALE {
+                continue;
+            }
+            match load_translation(&path, &english) {
+                Ok(messages) => {
+                    log::info!(
+                        "Loaded locale {locale} ({}/{} messages)",
+                        messages.len(),
+                        english.len()
+                    );
+                    translations.insert(locale.to_string(), messages);
+                }
+                Err(e) => {
+                    log::warn!("Could not load locale {locale}: {e}")
+                }
+            }
+        }
+
+        Self {
+            english,
+            translations,
+        }
+    }
+
+    pub fn has_locale(&self, locale: &str) -> bool {
+        locale == DEFAULT_LOCALE || self.translations.contains_key(locale)
+    }
+
+    /// All available locales, sorted
+    pub fn locales(&self) -> Vec<&str> {
+        let mut locales = self
+            .translations
+            .keys()
+            .map(String::as_str)
+            .chain([DEFAULT_LOCALE])
+            .collect::<Vec<_>>();
+        locales.sort_unstable();
+        locales
+    }
+
+    fn get(&self, locale: &str, key: &str) -> Option<&str> {
+        self.translations
+            .get(locale)
+            .and_then(|messages| messages.get(key))
+            .or_else(|| self.english.get(key))
+            .map(String::as_str)
+    }
+}
+
+/// Gets the locale from a file name like `de.json` or `pt-BR.json`.
+fn locale_of(path: &Path) -> Option<&str> {
+    if path.extension()? != "json" {
+        return None;
+    }
+    let locale = path.file_stem()?.to_str()?;
+    let valid = (2..=10).contains(&locale.len())
+        && locale
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-');
+    valid.then_some(locale)
+}
+
+/// Reads a translation and drops the messages the english catalog doesn't
+/// have or that use variables which wouldn't be provided.
+fn load_translation(path: &Path, english: &Messages) -> AnyResult<Messages> {
+    let messages: Messages = serde_json::from_str(&fs::read_to_string(path)?)?;
+    if messages.is_empty() {
+        bail!("The translation is empty");
+    }
+
+    Ok(messages
+        .into_iter()
+        .filter(|(key, message)| {
+            let Some(original) = english.get(key) else {
+                log::warn!("{}: unknown message {key}", path.display());
+                return false;
+            };
+            let known = template::variables(original).collect::<Vec<_>>();
+            if let Some(var) =
+                template::variables(message).find(|v| !known.contains(v))
+            {
+                log::warn!(
+                    "{}: unknown variable {var} in {key}",
+                    path.display()
+                );
+                return false;
+            }
+            true
+        })
+        .collect())
+}
+
+/// Text shown to users that's rendered once the locale of the channel is known.
+#[derive(Debug, Clone)]
+pub enum Text {
+    /// A message from the catalog and the values of its variables
+    Message {
+        key: &'static str,
+        args: Vec<(&'static str, Text)>,
+    },
+    /// Text that isn't translated (e.g. names or user-defined responses)
+    Raw(String),
+}
+
+impl Text {
+    pub fn new(key: &'static str) -> Self {
+        Self::Message {
+            key,
+            args: Vec::new(),
+        }
+    }
+
+    pub fn arg(mut self, name: &'static str, value: impl Into<Text>) -> Self {
+        if let Self::Message { args, .. } = &mut self {
+            args.push((name, value.into()));
+        }
+        self
+    }
+
+    pub fn render(&self, locale: &str) -> String {
+        match self {
+            Self::Message { key, args } => {
+                let Some(message) = CATALOG.get(locale, key) else {
+                    log::warn!("Missing message {key}");
+                    return key.to_string();
+                };
+                template::render(message, |var| {
+                    args.iter()
+                        .find(|(name, _)| *name == var)
+                        .map(|(_, value)| value.render(locale))
+                })
+            }
+            Self::Raw(text) => text.clone(),
+        }
+    }
+}
+
+impl fmt::Display for Text {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.render(DEFAULT_LOCALE))
+    }
+}
+
+impl std::error::Error for Text {}
+
+impl From<String> for Text {
+    fn from(text: String) -> Self {
+        Self::Raw(text)
+    }
+}
+
+impl From<&str> for Text {
+    fn from(text: &str) -> Self {
+        Self::Raw(text.to_string())
+    }
+}
+
+/// Renders an error in the locale if it's a [Text].
+/// Other errors are shown as they are.
+pub fn render_error(error: &AnyError, locale: &str) -> String {
+    match error.downcast_ref::<Text>() {
+        Some(text) => text.render(locale),
+        None => error.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use regex::Regex;
+    use std::path::PathBuf;
+
+    fn sources(dir: PathBuf, files: &mut Vec<PathBuf>) {
+        for entry in fs::read_dir(dir).unwrap() {
+            let path = entry.unwrap().path();
+            if path.is_dir() {
+                sources(path, files);
+            } else if path.extension().is_some_and(|e| e == "rs") {
+                files.push(path);
+            }
+        }
+    }
+
+    #[test]
+    fn used_messages_exist() {
+        let english: Messages = serde_json::from_str(ENGLISH).unwrap();
+        let key = Regex::new(r#"text!\(\s*"([^"]+)""#).unwrap();
+
+        let mut files = Vec::new();
+        sources(
+            Path::new(env!("CARGO_MANIFEST_DIR")).join("src"),
+            &mut files,
+        );
+        assert!(!files.is_empty());
+        for file in files {
+            let source = fs::read_to_string(&file).unwrap();
+            for captures in key.captures_iter(&source) {
+                assert!(
+                    english.contains_key(&captures[1]),
+                    "{}: missing message {}",
+                    file.display(),
+                    &captures[1]
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn locale_from_file_name() {
+        assert_eq!(locale_of(Path::new("de.json")), Some("de"));
+        assert_eq!(locale_of(Path::new("pt-BR.json")), Some("pt-BR"));
+        assert_eq!(locale_of(Path::new("de.toml")), None);
+        assert_eq!(locale_of(Path::new("a.json")), None);
+        assert_eq!(locale_of(Path::new("../x.json")), None);
+    }
+}
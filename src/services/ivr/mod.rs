@@ -1,4 +1,5 @@
 use anyhow::{anyhow, Result as AnyResult};
+use config::CONFIG;
 use lazy_static::lazy_static;
 use reqwest::{Client, IntoUrl};
 use serde::{de::DeserializeOwned, Deserialize};
@@ -41,7 +42,7 @@ struct IvrError {
 }
 
 pub async fn modvips(username: &str) -> AnyResult<ModVips> {
-    ivr_get(format!("https://api.ivr.fi/v2/twitch/modvip/{username}")).await
+    ivr_get(format!("{}/twitch/modvip/{username}", CONFIG.urls.ivr)).await
 }
 
 async fn ivr_get<R>(url: impl IntoUrl) -> AnyResult<R>
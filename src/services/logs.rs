@@ -0,0 +1,18 @@
+use crate::{log_err, services::jwt::JwtClaims};
+use models::log_entry::NewLogEntry;
+use sqlx::PgPool;
+
+/// Saves a log entry for a change made on the dashboard.
+/// The user who made the change is the actor.
+pub async fn log_change(
+    claims: &JwtClaims,
+    entry: NewLogEntry<'_>,
+    pool: &PgPool,
+) {
+    let actor = claims.get_user(pool).await.ok().map(|user| user.name);
+    let entry = match &actor {
+        Some(actor) => entry.actor(actor),
+        None => entry,
+    };
+    log_err!(entry.insert(pool).await, "Could not create log-entry");
+}
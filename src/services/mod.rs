@@ -6,8 +6,10 @@ pub mod eventsub;
 pub mod ffz;
 pub mod formatting;
 pub mod h2h;
+pub mod i18n;
 pub mod ivr;
 pub mod jwt;
+pub mod logs;
 pub mod metrics;
 pub mod redemptions;
 pub mod rewards;
@@ -15,5 +17,6 @@ pub mod seven_tv;
 pub mod spotify;
 pub mod sql;
 pub mod supinic;
+pub mod template;
 pub mod text;
 pub mod twitch;
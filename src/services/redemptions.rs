@@ -1,7 +1,8 @@
 use anyhow::Result as AnyhowResult;
 use futures::TryStreamExt;
-use models::reward::RewardToUpdate;
+use models::{pending_approval::PendingApproval, reward::RewardToUpdate};
 use sqlx::PgPool;
+use std::collections::HashSet;
 use twitch_api::{
     helix::{
         points::{
@@ -20,14 +21,21 @@ use crate::log_err;
 use super::twitch::RHelixClient;
 
 pub async fn clear_unfulfilled_redemptions(pool: &PgPool) -> AnyhowResult<()> {
+    // these are resolved by moderators
+    let pending = PendingApproval::get_all_redemption_ids(pool)
+        .await?
+        .into_iter()
+        .collect::<HashSet<_>>();
     let mut stream = RewardToUpdate::get_all(pool);
     let client = RHelixClient::default();
 
     while let Some(reward_with_user) = stream.try_next().await? {
         let (reward_id, token) = reward_with_user.into();
         log_err!(
-            clear_unfulfilled_redemptions_for_id(reward_id, &token, &client)
-                .await,
+            clear_unfulfilled_redemptions_for_id(
+                reward_id, &token, &client, &pending,
+            )
+            .await,
             "Could not clear redemptions for id"
         );
     }
@@ -39,6 +47,7 @@ async fn clear_unfulfilled_redemptions_for_id<'a>(
     reward_id: impl IntoCow<'a, RewardIdRef> + 'a,
     token: &'a UserToken,
     client: &RHelixClient<'_>,
+    pending: &HashSet<String>,
 ) -> AnyhowResult<()> {
     let mut rewards: Response<
         GetCustomRewardRedemptionRequest,
@@ -56,6 +65,9 @@ async fn clear_unfulfilled_redemptions_for_id<'a>(
 
     loop {
         for redemption in &rewards.data {
+            if pending.contains(redemption.id.as_str()) {
+                continue;
+            }
             log::info!(
                 "Clearing unfulfilled: broadcaster={}; reward_id={}; redemption_id={}",
                 redemption.broadcaster_login,
@@ -0,0 +1,247 @@
+use super::{
+    error::user_error,
+    redemption::{execute_redemption, RedemptionUpdateHandle},
+    Redemption,
+};
+use crate::{
+    actors::rewards::RewardsActor,
+    log_err,
+    services::{
+        i18n::{Text, DEFAULT_LOCALE},
+        twitch::{self, requests::send_chat_message},
+    },
+    text,
+};
+use actix::Addr;
+use anyhow::Result as AnyResult;
+use chrono::{Duration, Utc};
+use models::{
+    log_entry::{LogEntry, LogEntryType},
+    pending_approval::PendingApproval,
+    reward::Reward,
+    user::User,
+};
+use serde_json::json;
+use sqlx::PgPool;
+use std::time::Instant;
+use twitch_api::helix::points::CustomRewardRedemptionStatus;
+
+/// Pending redemptions are denied if nobody resolved them in this time.
+const APPROVAL_TIMEOUT_MINUTES: i64 = 60;
+
+/// Parks a redemption until a moderator approves or denies it.
+/// The redemption is refunded if it can't be saved.
+pub async fn request_approval(
+    redemption: Redemption,
+    user: User,
+    pool: &PgPool,
+) {
+    let expires_at = Utc::now() + Duration::minutes(APPROVAL_TIMEOUT_MINUTES);
+    let pending =
+        match PendingApproval::create(&redemption, expires_at, pool).await {
+            Ok(pending) => pending,
+            Err(e) => {
+                log::warn!("Could not save pending redemption: {}", e);
+                RedemptionUpdateHandle::from(&redemption)
+                    .update(user, CustomRewardRedemptionStatus::Canceled)
+                    .await;
+                return;
+            }
+        };
+
+    log_err!(
+        LogEntry::new(
+            &pending.user_id,
+            LogEntryType::Message,
+            format!(
+                "{} is waiting for approval (#{})",
+                redemption.reward.title, pending.id
+            ),
+        )
+        .actor(&pending.redeemed_by)
+        .reward(&pending.reward_id)
+        .payload(json!({
+            "approval": pending.id,
+            "input": pending.user_input,
+        }))
+        .insert(pool)
+        .await,
+        "Could not create log-entry"
+    );
+    reply(
+        &pending,
+        text!(
+            "reward.approval.pending",
+            user = pending.redeemed_by.as_str(),
+            id = pending.id.to_string()
+        ),
+        pool,
+    )
+    .await;
+}
+
+/// Executes a pending redemption as if it was just redeemed.
+pub async fn approve(
+    id: i32,
+    broadcaster_id: &str,
+    resolved_by: &str,
+    pool: &PgPool,
+    executor: &Addr<RewardsActor>,
+) -> AnyResult<PendingApproval> {
+    let pending = take(id, broadcaster_id, pool).await?;
+    log_resolution(
+        &pending,
+        LogEntryType::Message,
+        "approved",
+        Some(resolved_by),
+        pool,
+    )
+    .await;
+
+    let user = User::get_by_id(broadcaster_id, pool).await?;
+    let reward = Reward::get_by_id(&pending.reward_id, pool).await?;
+    execute_redemption(
+        pool,
+        executor,
+        pending.redemption.0.clone(),
+        user,
+        reward,
+        Instant::now(),
+    )
+    .await;
+
+    Ok(pending)
+}
+
+/// Refunds a pending redemption.
+pub async fn deny(
+    id: i32,
+    broadcaster_id: &str,
+    resolved_by: &str,
+    pool: &PgPool,
+) -> AnyResult<PendingApproval> {
+    let pending = take(id, broadcaster_id, pool).await?;
+    log_resolution(
+        &pending,
+        LogEntryType::RewardFailed,
+        "denied",
+        Some(resolved_by),
+        pool,
+    )
+    .await;
+    refund(
+        &pending,
+        text!(
+            "reward.approval.denied",
+            user = pending.redeemed_by.as_str(),
+            input = pending.user_input.as_str()
+        ),
+        pool,
+    )
+    .await?;
+
+    Ok(pending)
+}
+
+/// Refunds all redemptions that weren't resolved in time.
+pub async fn deny_expired(pool: &PgPool) -> AnyResult<()> {
+    let expired = PendingApproval::take_expired(pool).await?;
+    if !expired.is_empty() {
+        log::info!("Denying {} expired redemptions", expired.len());
+    }
+    for pending in expired {
+        log_resolution(
+            &pending,
+            LogEntryType::RewardFailed,
+            "expired",
+            None,
+            pool,
+        )
+        .await;
+        log_err!(
+            refund(
+                &pending,
+                text!(
+                    "reward.approval.expired",
+                    user = pending.redeemed_by.as_str(),
+                    input = pending.user_input.as_str()
+                ),
+                pool
+            )
+            .await,
+            "Could not refund expired redemption"
+        );
+    }
+
+    Ok(())
+}
+
+async fn take(
+    id: i32,
+    broadcaster_id: &str,
+    pool: &PgPool,
+) -> AnyResult<PendingApproval> {
+    PendingApproval::take(id, broadcaster_id, pool)
+        .await?
+        .ok_or_else(|| {
+            user_error(text!("reward.approval.not_found", id = id.to_string()))
+        })
+}
+
+async fn refund(
+    pending: &PendingApproval,
+    message: Text,
+    pool: &PgPool,
+) -> AnyResult<()> {
+    let user = User::get_by_id(&pending.user_id, pool).await?;
+    RedemptionUpdateHandle::from(&pending.redemption.0)
+        .update(user, CustomRewardRedemptionStatus::Canceled)
+        .await;
+    reply(pending, message, pool).await;
+
+    Ok(())
+}
+
+async fn reply(pending: &PendingApproval, message: Text, pool: &PgPool) {
+    let locale = User::get_locale(&pending.user_id, pool)
+        .await
+        .unwrap_or_else(|_| DEFAULT_LOCALE.to_string());
+    log_err!(
+        send_chat_message(
+            &pending.user_id,
+            &message.render(&locale),
+            &twitch::get_token()
+        )
+        .await,
+        "Failed to send chat"
+    );
+}
+
+/// Approved redemptions are logged again once they're executed.
+async fn log_resolution(
+    pending: &PendingApproval,
+    kind: LogEntryType,
+    resolution: &str,
+    resolved_by: Option<&str>,
+    pool: &PgPool,
+) {
+    let mut entry = LogEntry::new(
+        &pending.user_id,
+        kind,
+        format!(
+            "Redemption #{} from {} was {resolution}",
+            pending.id, pending.redeemed_by
+        ),
+    )
+    .target(&pending.redeemed_by)
+    .reward(&pending.reward_id)
+    .payload(json!({
+        "approval": pending.id,
+        "resolution": resolution,
+        "input": pending.user_input,
+    }));
+    if let Some(resolved_by) = resolved_by {
+        entry = entry.actor(resolved_by);
+    }
+    log_err!(entry.insert(pool).await, "Could not create log-entry");
+}
@@ -0,0 +1,77 @@
+use crate::services::i18n::{self, Text};
+use anyhow::Error as AnyError;
+use thiserror::Error;
+
+/// An error caused by the redemption itself (e.g. invalid input).
+/// All other errors are internal errors (e.g. a platform being down).
+#[derive(Error, Debug)]
+#[error("{0}")]
+pub struct UserError(pub Text);
+
+pub fn user_error(msg: impl Into<Text>) -> AnyError {
+    UserError(msg.into()).into()
+}
+
+pub fn is_user_error(error: &AnyError) -> bool {
+    error.chain().any(|e| e.is::<UserError>())
+}
+
+/// Renders the error in the locale of the channel.
+pub fn render_error(error: &AnyError, locale: &str) -> String {
+    match error.chain().find_map(|e| e.downcast_ref::<UserError>()) {
+        Some(UserError(text)) => text.render(locale),
+        None => i18n::render_error(error, locale),
+    }
+}
+
+/// The catalog key of a [UserError]. Tests can't render the messages, since
+/// that needs the config.
+#[cfg(test)]
+pub fn user_error_key(error: &AnyError) -> Option<&'static str> {
+    match error.chain().find_map(|e| e.downcast_ref::<UserError>()) {
+        Some(UserError(Text::Message { key, .. })) => Some(*key),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::text;
+    use anyhow::Context;
+    use models::reward::RefundPolicy;
+
+    #[test]
+    fn classifies_user_errors() {
+        assert!(is_user_error(&user_error("bad input")));
+        assert!(is_user_error(&user_error(text!("reward.no_user"))));
+        assert!(!is_user_error(&AnyError::msg("BTTV is down")));
+        // context added by callers doesn't hide the user error
+        let wrapped = Err::<(), _>(user_error("bad input"))
+            .context("Could not add emote")
+            .unwrap_err();
+        assert!(is_user_error(&wrapped));
+    }
+
+    #[test]
+    fn refunds_by_policy() {
+        let user = user_error("bad input");
+        let internal = AnyError::msg("BTTV is down");
+        for (policy, user_refund, internal_refund) in [
+            (RefundPolicy::Always, true, true),
+            (RefundPolicy::Never, false, false),
+            (RefundPolicy::UserErrorOnly, true, false),
+        ] {
+            assert_eq!(
+                policy.should_refund(is_user_error(&user)),
+                user_refund,
+                "{policy:?}"
+            );
+            assert_eq!(
+                policy.should_refund(is_user_error(&internal)),
+                internal_refund,
+                "{policy:?}"
+            );
+        }
+    }
+}
@@ -1,45 +1,52 @@
 use std::sync::Arc;
 
 use actix::Addr;
-use anyhow::{anyhow, bail, Error as AnyError, Result as AnyResult};
+use anyhow::{Error as AnyError, Result as AnyResult};
 use futures::TryFutureExt;
 use sqlx::PgPool;
 use tokio::sync::RwLock;
-use twitch_api::twitch_oauth2::AppAccessToken;
+use twitch_api::twitch_oauth2::{AppAccessToken, UserToken};
 
 use crate::{
     actors::{
         discord::DiscordActor,
+        timed_mode::{TimedModeActor, TimedModeMessage},
         timeout::{ChannelTimeoutMessage, CheckValidTimeoutMessage},
     },
-    log_err,
     services::{
         emotes::{
+            bttv::BttvEmotes,
             execute::{execute_remove_emote, execute_slot, execute_swap},
+            ffz::FfzEmotes,
+            seven_tv::SevenTvEmotes,
             Emote, EmoteRW,
         },
         ivr,
         rewards::{
+            error::user_error,
             extract,
-            reply::{
-                format_spotify_result, get_reply_data, reply_to_redemption,
-                SpotifyAction,
-            },
+            reply::{format_spotify_result, RedemptionReply, SpotifyAction},
             Redemption,
         },
-        spotify::rewards as spotify,
+        spotify::{queue as song_queue, rewards as spotify},
         twitch::requests::get_user_by_login,
     },
-    twitch,
-    twitch::requests::{get_chat_settings, timeout_user, update_chat_settings},
+    text, twitch,
+    twitch::requests::{
+        create_poll, create_prediction, get_chat_settings, has_active_poll,
+        has_active_prediction, timeout_user, update_chat_settings,
+    },
     RedisPool, TimeoutActor,
 };
 use config::CONFIG;
 use models::{
+    emote::SlotPlatform,
     reward::{
-        RemEmoteRewardData, SlotRewardData, SpotifyPlayOptions, SwapRewardData,
+        AnySwapRewardData, PollRewardData, RemEmoteRewardData, SlotRewardData,
+        SpotifyPlayOptions, SpotifyQueueOptions, SwapRewardData,
         TimeoutRewardData,
     },
+    song_request::SongRequestStatus,
     timed_mode,
     user::User,
 };
@@ -55,9 +62,8 @@ pub async fn timeout(
         Arc<RwLock<AppAccessToken>>,
         Addr<TimeoutActor>,
     ),
-) -> AnyResult<()> {
-    let reply_data = get_reply_data(&redemption);
-    let result = async move {
+) -> AnyResult<Option<RedemptionReply>> {
+    async move {
         // check timeout
         let username =
             extract::username(&redemption.user_input)?.to_lowercase();
@@ -67,19 +73,24 @@ pub async fn timeout(
             get_user_by_login(username.clone(), &*app_token.read().await)
                 .await
                 .map_err(|e| {
-                    AnyError::msg(format!(
-                        "This user doesn't seem to exist: {}",
-                        e
+                    user_error(text!(
+                        "reward.timeout.unknown_user",
+                        error = e.to_string()
                     ))
                 })?;
 
         if timeout.vip {
             let vips = ivr::modvips(redemption.broadcaster_user_login.as_str())
                 .await
-                .map_err(|e| anyhow!("Attempt to read VIPs failed: {}", e))?
+                .map_err(|e| {
+                    AnyError::new(text!(
+                        "reward.timeout.vips_failed",
+                        error = e.to_string()
+                    ))
+                })?
                 .vips;
             if vips.iter().any(|v| v.id == user.id.as_str()) {
-                return Err(anyhow!("I won't timeout VIPs."));
+                return Err(user_error(text!("reward.timeout.vip")));
             }
         }
 
@@ -91,18 +102,16 @@ pub async fn timeout(
                 user_id: user.id.clone().take(),
             })
             .await
-            .map_err(|_| anyhow!("Too much traffic"))?
+            .map_err(|_| AnyError::new(text!("reward.busy")))?
             .map_err(|e| {
-                anyhow!(
-                    "Cannot check {}'s timeout/ban status: {}",
-                    user.login,
-                    e
-                )
+                AnyError::new(text!(
+                    "reward.timeout.check_failed",
+                    user = user.login.to_string(),
+                    error = e.to_string()
+                ))
             })?
         {
-            return Err(anyhow!(
-                "This user was timed out by another moderator."
-            ));
+            return Err(user_error(text!("reward.timeout.already_timed_out")));
         }
 
         timeout_handler
@@ -113,7 +122,7 @@ pub async fn timeout(
                 is_self: true,
             })
             .await
-            .map_err(|_| anyhow!("Too much traffic"))?;
+            .map_err(|_| AnyError::new(text!("reward.busy")))?;
 
         timeout_user(
             &broadcaster.id,
@@ -124,44 +133,51 @@ pub async fn timeout(
             &token,
         )
         .await
-        .map_err(|e| anyhow!("Cannot timeout user: {e}"))?;
+        .map_err(|e| {
+            AnyError::new(text!("reward.timeout.failed", error = e.to_string()))
+        })?;
 
         Ok(())
     }
-    .await;
-    if result.is_err() {
-        reply_to_redemption(
-            result.map(|_| unreachable!("only errors are printed")),
-            &reply_data.0,
-            &reply_data.1,
-        )
-        .await?;
-    }
-    Ok(())
+    .await
+    .map(|()| None)
 }
 
 pub async fn timed_mode(
     mode: timed_mode::Mode,
     duration: String,
+    setting: Option<String>,
     broadcaster: User,
     redemption: Redemption,
-) -> AnyResult<()> {
-    let reply_data = get_reply_data(&redemption);
+    timed_mode_handler: Addr<TimedModeActor>,
+) -> AnyResult<Option<RedemptionReply>> {
     let duration =
         extract::duration(&duration).map(std::time::Duration::from_secs)?;
-    let broadcaster_id = broadcaster.id.clone();
     let token = twitch::get_token();
 
-    let res = async move {
+    async move {
+        let setting = mode_setting(mode, setting.as_deref())?;
+
         let chat_settings = get_chat_settings(&broadcaster.id, &token)
             .await
-            .map_err(|e| anyhow!("Cannot get chat settings {e}"))?;
+            .map_err(|e| {
+                AnyError::new(text!(
+                    "reward.mode.get_failed",
+                    error = e.to_string()
+                ))
+            })?;
         match mode {
             timed_mode::Mode::Subonly if chat_settings.subscriber_mode => {
-                bail!("This chat is already in subscriber mode.");
+                return Err(user_error(text!("reward.mode.subonly_active")));
             }
             timed_mode::Mode::Emoteonly if chat_settings.emote_mode => {
-                bail!("This chat is already in emote only mode.");
+                return Err(user_error(text!("reward.mode.emoteonly_active")));
+            }
+            timed_mode::Mode::Slow if chat_settings.slow_mode => {
+                return Err(user_error(text!("reward.mode.slow_active")));
+            }
+            timed_mode::Mode::Followers if chat_settings.follower_mode => {
+                return Err(user_error(text!("reward.mode.followers_active")));
             }
             _ => (),
         }
@@ -171,62 +187,93 @@ pub async fn timed_mode(
             CONFIG.twitch.user_id.clone(),
             mode,
             true,
+            setting,
             &token,
         )
         .await
-        .map_err(|e| anyhow!("Cannot update chat settings: {e}"))?;
+        .map_err(|e| {
+            AnyError::new(text!(
+                "reward.mode.update_failed",
+                error = e.to_string()
+            ))
+        })?;
 
-        tokio::spawn(async move {
-            tokio::time::sleep(duration).await;
-            let res = update_chat_settings(
-                &broadcaster_id,
-                CONFIG.twitch.user_id.clone(),
+        timed_mode_handler
+            .send(TimedModeMessage {
+                broadcaster_id: broadcaster.id,
                 mode,
-                false,
-                &token,
-            )
-            .await;
-            log_err!(res, "cannot update chat settings");
-        });
+                duration,
+            })
+            .await
+            .map_err(|_| AnyError::new(text!("reward.busy")))?;
         Ok(())
     }
-    .await;
+    .await
+    .map(|()| None)
+}
 
-    if let Err(e) = res {
-        reply_to_redemption(Err(e), &reply_data.0, &reply_data.1).await?;
-    }
-    Ok(())
+/// Converts the setting of slow and follower-only mode to the value Twitch
+/// expects.
+fn mode_setting(
+    mode: timed_mode::Mode,
+    setting: Option<&str>,
+) -> AnyResult<Option<u64>> {
+    Ok(match (mode, setting) {
+        // twitch allows 3 to 120 seconds
+        (timed_mode::Mode::Slow, Some(wait_time)) => {
+            Some(extract::duration(wait_time)?.clamp(3, 120))
+        }
+        // twitch expects minutes, at most three months
+        (timed_mode::Mode::Followers, Some(follow_age)) => {
+            Some((extract::duration(follow_age)? / 60).min(129_600))
+        }
+        _ => None,
+    })
 }
 
 pub async fn swap<RW>(
-    extractor: impl FnOnce(&str) -> AnyResult<EmoteSpec>,
+    extractor: impl Fn(&str) -> AnyResult<EmoteSpec>,
     redemption: Redemption,
     data: SwapRewardData,
     (db, redis_pool, discord): (PgPool, RedisPool, Addr<DiscordActor>),
-) -> AnyResult<()>
+) -> AnyResult<Option<RedemptionReply>>
 where
     RW: EmoteRW,
     RW::PlatformId: Display,
     RW::Emote: Emote<RW::EmoteId>,
     RW::EmoteId: Display + Clone + FromStr + Default,
 {
-    let (broadcaster_id, user) = get_reply_data(&redemption);
     let should_reply = data.reply;
-    let res = execute_swap::<RW>(
-        extractor,
-        redemption,
-        data,
-        &db,
-        &redis_pool,
-        discord,
-    )
-    .await;
-    reply_to_redemption(
-        res.map(|r| should_reply.then_some(r)),
-        &broadcaster_id,
-        &user,
-    )
-    .await
+    execute_swap::<RW>(extractor, redemption, data, &db, &redis_pool, discord)
+        .await
+        .map(|r| should_reply.then_some(r))
+}
+
+pub async fn any_swap(
+    redemption: Redemption,
+    data: AnySwapRewardData,
+    ctx: (PgPool, RedisPool, Addr<DiscordActor>),
+) -> AnyResult<Option<RedemptionReply>> {
+    let platform = extract::emote_platform(&redemption.user_input)?;
+    if !data.is_enabled(platform) {
+        return Err(user_error(text!(
+            "reward.swap.platform_disabled",
+            platform = platform.to_string()
+        )));
+    }
+
+    match platform {
+        SlotPlatform::Bttv => {
+            swap::<BttvEmotes>(extract::bttv, redemption, data.swap, ctx).await
+        }
+        SlotPlatform::Ffz => {
+            swap::<FfzEmotes>(extract::ffz, redemption, data.swap, ctx).await
+        }
+        SlotPlatform::SevenTv => {
+            swap::<SevenTvEmotes>(extract::seventv, redemption, data.swap, ctx)
+                .await
+        }
+    }
 }
 
 pub async fn slot<RW>(
@@ -234,23 +281,16 @@ pub async fn slot<RW>(
     redemption: Redemption,
     slot: SlotRewardData,
     (db, redis, discord): (PgPool, RedisPool, Addr<DiscordActor>),
-) -> AnyResult<()>
+) -> AnyResult<Option<RedemptionReply>>
 where
     RW: EmoteRW,
     RW::Emote: Emote<RW::EmoteId>,
     RW::EmoteId: Display,
 {
-    let (broadcaster_id, user) = get_reply_data(&redemption);
     let should_reply = slot.reply;
-    let res =
-        execute_slot::<RW>(extractor, redemption, slot, &db, &redis, discord)
-            .await;
-    reply_to_redemption(
-        res.map(|r| should_reply.then_some(r)),
-        &broadcaster_id,
-        &user,
-    )
-    .await
+    execute_slot::<RW>(extractor, redemption, slot, &db, &redis, discord)
+        .await
+        .map(|r| should_reply.then_some(r))
 }
 
 pub async fn rem_emote<RW>(
@@ -258,48 +298,36 @@ pub async fn rem_emote<RW>(
     redemption: Redemption,
     data: RemEmoteRewardData,
     (db, redis, discord): (PgPool, RedisPool, Addr<DiscordActor>),
-) -> AnyResult<()>
+) -> AnyResult<Option<RedemptionReply>>
 where
     RW: EmoteRW,
     RW::Emote: Emote<RW::EmoteId>,
     RW::EmoteId: Display + FromStr + PartialEq,
 {
-    let (broadcaster_id, user) = get_reply_data(&redemption);
     let should_reply = data.reply;
-    let res = execute_remove_emote::<RW>(
-        extract_id, redemption, &db, &redis, discord,
-    )
-    .await;
-    reply_to_redemption(
-        res.map(|r| should_reply.then_some(r)),
-        &broadcaster_id,
-        &user,
-    )
-    .await
+    execute_remove_emote::<RW>(extract_id, redemption, &db, &redis, discord)
+        .await
+        .map(|r| should_reply.then_some(r))
 }
 
-pub async fn spotify_skip(redemption: Redemption, db: PgPool) -> AnyResult<()> {
-    let (broadcaster_id, user) = get_reply_data(&redemption);
+pub async fn spotify_skip(
+    redemption: Redemption,
+    db: PgPool,
+) -> AnyResult<Option<RedemptionReply>> {
     let res = spotify::skip_track(
         redemption.broadcaster_user_id.as_ref(),
         false,
         &db,
     )
     .await;
-    reply_to_redemption(
-        format_spotify_result(res, SpotifyAction::Skip).map(Some),
-        &broadcaster_id,
-        &user,
-    )
-    .await
+    format_spotify_result(res, SpotifyAction::Skip).map(Some)
 }
 
 pub async fn spotify_play(
     opts: SpotifyPlayOptions,
     redemption: Redemption,
     db: PgPool,
-) -> AnyResult<()> {
-    let (broadcaster_id, user) = get_reply_data(&redemption);
+) -> AnyResult<Option<RedemptionReply>> {
     let res = spotify::get_track_uri_from_input(
         &redemption.user_input,
         redemption.broadcaster_user_id.as_ref(),
@@ -311,39 +339,227 @@ pub async fn spotify_play(
             .await
     })
     .await;
-    reply_to_redemption(
-        format_spotify_result(res, SpotifyAction::Play).map(Some),
-        &broadcaster_id,
-        &user,
-    )
-    .await
+    format_spotify_result(res, SpotifyAction::Play).map(Some)
 }
 
 pub async fn spotify_queue(
-    opts: SpotifyPlayOptions,
+    opts: SpotifyQueueOptions,
     redemption: Redemption,
     db: PgPool,
-) -> AnyResult<()> {
-    let (broadcaster_id, user) = get_reply_data(&redemption);
-    let res = spotify::get_track_uri_from_input(
+) -> AnyResult<Option<RedemptionReply>> {
+    let track = spotify::get_track_uri_from_input(
         &redemption.user_input,
         redemption.broadcaster_user_id.as_ref(),
-        &opts,
+        &opts.play,
         &db,
     )
-    .and_then(|track| async {
-        spotify::queue_track(
-            redemption.broadcaster_user_id.as_ref(),
-            track,
-            &db,
+    .await?;
+    let (request, position) =
+        song_queue::request_track(&opts, &redemption, track, &db).await?;
+
+    let track = song_queue::describe(&request);
+    let message = if request.status == SongRequestStatus::Pending {
+        text!(
+            "reward.spotify.awaiting_approval",
+            id = request.id.to_string()
         )
-        .await
-    })
+    } else {
+        text!(
+            "reward.spotify.queued",
+            position = (position + 1).to_string()
+        )
+    };
+    Ok(Some(
+        RedemptionReply::new(message.arg("track", track.clone()))
+            .with("track", track),
+    ))
+}
+
+pub async fn poll(
+    data: PollRewardData,
+    redemption: Redemption,
+    broadcaster: User,
+) -> AnyResult<Option<RedemptionReply>> {
+    let broadcaster_id = redemption.broadcaster_user_id.clone().take();
+    let should_reply = data.reply;
+    let res = async {
+        // twitch allows 15s - 30min
+        let duration = extract::duration(&data.duration)?.clamp(15, 30 * 60);
+        let (question, choices) = extract::poll(&redemption.user_input)?;
+        check_poll_input(question, &choices, 60, 5)?;
+
+        let token: UserToken = broadcaster.into();
+        if has_active_poll(&broadcaster_id, &token)
+            .await
+            .map_err(|e| {
+                AnyError::new(text!(
+                    "reward.poll.get_failed",
+                    error = e.to_string()
+                ))
+            })?
+        {
+            return Err(user_error(text!("reward.poll.running")));
+        }
+        create_poll(&broadcaster_id, question, &choices, duration, &token)
+            .await
+            .map_err(|e| {
+                AnyError::new(text!(
+                    "reward.poll.create_failed",
+                    error = e.to_string()
+                ))
+            })?;
+
+        Ok(RedemptionReply::new(text!(
+            "reward.poll.started",
+            question = question
+        )))
+    }
     .await;
-    reply_to_redemption(
-        format_spotify_result(res, SpotifyAction::Queue).map(Some),
-        &broadcaster_id,
-        &user,
-    )
-    .await
+    res.map(|r| should_reply.then_some(r))
+}
+
+pub async fn prediction(
+    data: PollRewardData,
+    redemption: Redemption,
+    broadcaster: User,
+) -> AnyResult<Option<RedemptionReply>> {
+    let broadcaster_id = redemption.broadcaster_user_id.clone().take();
+    let should_reply = data.reply;
+    let res = async {
+        // twitch allows 30s - 30min
+        let window = extract::duration(&data.duration)?.clamp(30, 30 * 60);
+        let (question, outcomes) = extract::poll(&redemption.user_input)?;
+        check_poll_input(question, &outcomes, 45, 10)?;
+
+        let token: UserToken = broadcaster.into();
+        if has_active_prediction(&broadcaster_id, &token)
+            .await
+            .map_err(|e| {
+                AnyError::new(text!(
+                    "reward.prediction.get_failed",
+                    error = e.to_string()
+                ))
+            })?
+        {
+            return Err(user_error(text!("reward.prediction.running")));
+        }
+        create_prediction(&broadcaster_id, question, &outcomes, window, &token)
+            .await
+            .map_err(|e| {
+                AnyError::new(text!(
+                    "reward.prediction.create_failed",
+                    error = e.to_string()
+                ))
+            })?;
+
+        Ok(RedemptionReply::new(text!(
+            "reward.prediction.started",
+            question = question
+        )))
+    }
+    .await;
+    res.map(|r| should_reply.then_some(r))
+}
+
+/// Checks the limits imposed by Twitch (options are limited to 25 chars)
+fn check_poll_input(
+    question: &str,
+    options: &[&str],
+    max_question_len: usize,
+    max_options: usize,
+) -> AnyResult<()> {
+    if question.chars().count() > max_question_len {
+        return Err(user_error(text!(
+            "reward.poll.question_too_long",
+            max = max_question_len.to_string()
+        )));
+    }
+    if options.len() > max_options {
+        return Err(user_error(text!(
+            "reward.poll.too_many_options",
+            max = max_options.to_string()
+        )));
+    }
+    if let Some(option) = options.iter().find(|o| o.chars().count() > 25) {
+        return Err(user_error(text!(
+            "reward.poll.option_too_long",
+            option = *option
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::rewards::error::user_error_key;
+
+    fn poll_error(question: &str, options: &[&str]) -> Option<&'static str> {
+        check_poll_input(question, options, 60, 5)
+            .err()
+            .map(|e| user_error_key(&e).unwrap())
+    }
+
+    #[test]
+    fn poll_question_length() {
+        assert_eq!(poll_error(&"q".repeat(60), &["a", "b"]), None);
+        assert_eq!(
+            poll_error(&"q".repeat(61), &["a", "b"]),
+            Some("reward.poll.question_too_long")
+        );
+        // characters are counted, not bytes
+        assert_eq!(poll_error(&"ä".repeat(60), &["a", "b"]), None);
+    }
+
+    #[test]
+    fn poll_option_limits() {
+        assert_eq!(poll_error("q", &["a"; 5]), None);
+        assert_eq!(
+            poll_error("q", &["a"; 6]),
+            Some("reward.poll.too_many_options")
+        );
+        assert_eq!(poll_error("q", &["a", &"b".repeat(25)]), None);
+        assert_eq!(
+            poll_error("q", &["a", &"b".repeat(26)]),
+            Some("reward.poll.option_too_long")
+        );
+    }
+
+    #[test]
+    fn slow_mode_is_clamped() {
+        let slow = |s| mode_setting(timed_mode::Mode::Slow, Some(s)).unwrap();
+        assert_eq!(slow("1s"), Some(3));
+        assert_eq!(slow("30s"), Some(30));
+        assert_eq!(slow("5m"), Some(120));
+    }
+
+    #[test]
+    fn follow_age_is_in_minutes() {
+        let followers =
+            |s| mode_setting(timed_mode::Mode::Followers, Some(s)).unwrap();
+        assert_eq!(followers("30s"), Some(0));
+        assert_eq!(followers("10m"), Some(10));
+        assert_eq!(followers("1day"), Some(1440));
+        assert_eq!(followers("1year"), Some(129_600));
+    }
+
+    #[test]
+    fn other_modes_have_no_setting() {
+        assert_eq!(
+            mode_setting(timed_mode::Mode::Subonly, None).unwrap(),
+            None
+        );
+        assert_eq!(
+            mode_setting(timed_mode::Mode::Emoteonly, Some("10s")).unwrap(),
+            None
+        );
+        assert!(mode_setting(timed_mode::Mode::Slow, Some("soon")).is_err());
+    }
+
+    #[test]
+    fn prediction_option_limits() {
+        assert!(check_poll_input("q", &["a"; 10], 45, 10).is_ok());
+        assert!(check_poll_input("q", &["a"; 11], 45, 10).is_err());
+        assert!(check_poll_input(&"q".repeat(46), &["a", "b"], 45, 10).is_err());
+    }
 }
@@ -1,6 +1,11 @@
-use crate::services::text::first_capture;
+use crate::{
+    chat::parse::opt_next_space,
+    services::{rewards::error::user_error, text::first_capture},
+    text,
+};
 use anyhow::{Error as AnyError, Result as AnyResult};
 use lazy_static::lazy_static;
+use models::emote::SlotPlatform;
 use once_cell::sync::Lazy;
 use regex::Regex;
 
@@ -25,7 +30,7 @@ pub fn username(str: &str) -> AnyResult<String> {
         .captures(str)
         .and_then(|m| m.get(1))
         .map(|m| m.as_str().to_string())
-        .ok_or_else(|| AnyError::msg("No user submitted"))
+        .ok_or_else(|| user_error(text!("reward.no_user")))
 }
 
 pub fn bttv(s: &str) -> AnyResult<EmoteSpec<'_>> {
@@ -47,6 +52,61 @@ pub fn seventv(s: &str) -> AnyResult<EmoteSpec<'_>> {
     })
 }
 
+/// Detects the platform of the emote(s) from the link or id format.
+pub fn emote_platform(s: &str) -> AnyResult<SlotPlatform> {
+    let found = [
+        (SlotPlatform::Bttv, bttv_id(s).is_ok()),
+        (SlotPlatform::Ffz, ffz_id(s).is_ok()),
+        (SlotPlatform::SevenTv, seventv_id(s).is_ok()),
+    ]
+    .into_iter()
+    .filter_map(|(platform, found)| found.then_some(platform))
+    .collect::<Vec<_>>();
+
+    match found[..] {
+        [platform] => Ok(platform),
+        [] => Err(user_error(text!("reward.emote.not_found"))),
+        _ => Err(user_error(text!("reward.emote.mixed_platforms"))),
+    }
+}
+
+/// Extracts up to `max` emotes separated by spaces.
+/// Words that aren't emotes (e.g. `as=name`) belong to the preceding emote.
+pub fn multiple<'a>(
+    s: &'a str,
+    max: usize,
+    extractor: impl Fn(&'a str) -> AnyResult<EmoteSpec<'a>>,
+) -> AnyResult<Vec<EmoteSpec<'a>>> {
+    let s = s.trim();
+    let mut starts = s
+        .match_indices(' ')
+        .map(|(i, _)| i + 1)
+        .filter(|&i| extractor(opt_next_space(&s[i..]).0).is_ok())
+        .collect::<Vec<_>>();
+    if extractor(opt_next_space(s).0).is_ok() {
+        starts.insert(0, 0);
+    }
+
+    if starts.is_empty() {
+        return extractor(s).map(|spec| vec![spec]);
+    }
+    if starts.len() > max {
+        return Err(user_error(text!(
+            "reward.emote.too_many",
+            max = max.to_string()
+        )));
+    }
+
+    starts
+        .iter()
+        .enumerate()
+        .map(|(i, &start)| {
+            let end = starts.get(i + 1).map_or(s.len(), |&end| end - 1);
+            extractor(s[start..end].trim())
+        })
+        .collect()
+}
+
 pub fn bttv_id(str: &str) -> AnyResult<&str> {
     lazy_static! {
         static ref BTTV_REGEX: Regex = Regex::new(
@@ -55,7 +115,7 @@ pub fn bttv_id(str: &str) -> AnyResult<&str> {
         .expect("must compile");
     }
     first_capture(str, &BTTV_REGEX)
-        .ok_or_else(|| AnyError::msg("Could not find an emote code there!"))
+        .ok_or_else(|| user_error(text!("reward.emote.no_code")))
 }
 
 pub fn ffz_id(str: &str) -> AnyResult<&str> {
@@ -66,7 +126,7 @@ pub fn ffz_id(str: &str) -> AnyResult<&str> {
         .expect("must compile");
     }
     first_capture(str, &FFZ_REGEX)
-        .ok_or_else(|| AnyError::msg("Could not find an emote there!"))
+        .ok_or_else(|| user_error(text!("reward.emote.not_found")))
 }
 
 pub fn seventv_id(str: &str) -> AnyResult<&str> {
@@ -77,7 +137,22 @@ pub fn seventv_id(str: &str) -> AnyResult<&str> {
         .expect("must compile");
     }
     first_capture(str, &SEVENTV_REGEX)
-        .ok_or_else(|| AnyError::msg("Could not find an emote code there!"))
+        .ok_or_else(|| user_error(text!("reward.emote.no_code")))
+}
+
+/// Parses `Question? | A | B | C` into the question and its options
+pub fn poll(str: &str) -> AnyResult<(&str, Vec<&str>)> {
+    let mut parts = str.split('|').map(str::trim);
+    let question = parts
+        .next()
+        .filter(|q| !q.is_empty())
+        .ok_or_else(|| user_error(text!("reward.poll.usage")))?;
+    let options = parts.filter(|o| !o.is_empty()).collect::<Vec<_>>();
+    if options.len() < 2 {
+        return Err(user_error(text!("reward.poll.two_options")));
+    }
+
+    Ok((question, options))
 }
 
 /// in seconds
@@ -95,12 +170,7 @@ pub fn duration(duration: &str) -> AnyResult<u64> {
 
         let (first, second) = match (first, second) {
             (Some(Ok(first)), Some(Ok(second))) => (first, second),
-            tuple => {
-                return Err(AnyError::msg(format!(
-                    "Could not parse duration: {:?}",
-                    tuple
-                )))
-            }
+            _ => return Err(invalid_duration(duration)),
         };
 
         let (start, diff) = if first < second {
@@ -114,14 +184,162 @@ pub fn duration(duration: &str) -> AnyResult<u64> {
                 as u64,
         )
     } else {
-        Ok(humantime::parse_duration(duration)?.as_secs())
+        humantime::parse_duration(duration)
+            .map(|d| d.as_secs())
+            .map_err(|_| invalid_duration(duration))
     }
 }
 
-fn parse_overridden(s: &str) -> Option<&str> {
+fn invalid_duration(duration: &str) -> AnyError {
+    AnyError::new(text!("reward.invalid_duration", duration = duration))
+}
+
+pub fn parse_overridden(s: &str) -> Option<&str> {
     static OVERRIDE_REGEX: Lazy<Regex> = Lazy::new(|| {
         Regex::new("(?: |^)as=([-_A-Za-z(!?&)$+:0-9]{2,100})\\b")
             .expect("must compile")
     });
     first_capture(s, &OVERRIDE_REGEX)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::{i18n::Text, rewards::error::user_error_key};
+
+    #[test]
+    fn duration_parses_humantime() {
+        assert_eq!(duration("10s").unwrap(), 10);
+        assert_eq!(duration(" 1m 30s ").unwrap(), 90);
+        assert_eq!(duration("2h").unwrap(), 7200);
+        assert!(duration("").is_err());
+        assert!(duration("forever").is_err());
+    }
+
+    #[test]
+    fn invalid_durations_are_localized() {
+        for input in ["forever", "rand(5s; never)"] {
+            let err = duration(input).unwrap_err();
+            assert!(
+                matches!(
+                    err.downcast_ref::<Text>(),
+                    Some(Text::Message {
+                        key: "reward.invalid_duration",
+                        ..
+                    })
+                ),
+                "{input}"
+            );
+        }
+    }
+
+    #[test]
+    fn random_duration_is_in_range() {
+        for _ in 0..100 {
+            let secs = duration("rand(10s; 1m)").unwrap();
+            assert!((10..=60).contains(&secs), "{secs}");
+            // the bounds can be swapped
+            let secs = duration("rand(1m;10s)").unwrap();
+            assert!((10..=60).contains(&secs), "{secs}");
+        }
+        assert_eq!(duration("rand(5s; 5s)").unwrap(), 5);
+        assert!(duration("rand(5s; never)").is_err());
+    }
+
+    const BTTV_A: &str = "5f1b0186cf6d2144653d2970";
+    const BTTV_B: &str = "60a4e5c967644f1d67e8b01e";
+    const SEVENTV_A: &str = "01F6MZGCNG000255K4X1K96SBZ";
+    const SEVENTV_B: &str = "01F6MZGCNG000255K4X1K96SBA";
+
+    fn ids<'a>(specs: &[EmoteSpec<'a>]) -> Vec<(&'a str, Option<&'a str>)> {
+        specs.iter().map(|s| (s.id, s.override_name)).collect()
+    }
+
+    #[test]
+    fn multiple_splits_emotes() {
+        let input = format!("https://betterttv.com/emotes/{BTTV_A} {BTTV_B}");
+        assert_eq!(
+            ids(&multiple(&input, 3, bttv).unwrap()),
+            [(BTTV_A, None), (BTTV_B, None)]
+        );
+        assert_eq!(ids(&multiple(BTTV_A, 1, bttv).unwrap()), [(BTTV_A, None)]);
+    }
+
+    #[test]
+    fn multiple_keeps_names_with_their_emote() {
+        let input = format!("{SEVENTV_A} as=NewName {SEVENTV_B}");
+        assert_eq!(
+            ids(&multiple(&input, 3, seventv).unwrap()),
+            [(SEVENTV_A, Some("NewName")), (SEVENTV_B, None)]
+        );
+    }
+
+    #[test]
+    fn multiple_rejects_too_many_or_no_emotes() {
+        let input = format!("{BTTV_A} {BTTV_B}");
+        assert_eq!(
+            user_error_key(&multiple(&input, 1, bttv).unwrap_err()),
+            Some("reward.emote.too_many")
+        );
+        assert_eq!(
+            user_error_key(&multiple("no emote here", 3, bttv).unwrap_err()),
+            Some("reward.emote.no_code")
+        );
+    }
+
+    #[test]
+    fn detects_the_platform() {
+        assert_eq!(emote_platform(BTTV_A).unwrap(), SlotPlatform::Bttv);
+        assert_eq!(
+            emote_platform(
+                "https://www.frankerfacez.com/emoticon/128054-OMEGALUL"
+            )
+            .unwrap(),
+            SlotPlatform::Ffz
+        );
+        assert_eq!(
+            emote_platform(&format!("https://7tv.app/emotes/{SEVENTV_A}"))
+                .unwrap(),
+            SlotPlatform::SevenTv
+        );
+        assert_eq!(
+            user_error_key(&emote_platform("OMEGALUL").unwrap_err()),
+            Some("reward.emote.not_found")
+        );
+        assert_eq!(
+            user_error_key(
+                &emote_platform(&format!("{BTTV_A} {SEVENTV_A}")).unwrap_err()
+            ),
+            Some("reward.emote.mixed_platforms")
+        );
+    }
+
+    #[test]
+    fn poll_splits_question_and_options() {
+        assert_eq!(
+            poll("Best emote? | Kappa | PogChamp").unwrap(),
+            ("Best emote?", vec!["Kappa", "PogChamp"])
+        );
+        // empty options are skipped
+        assert_eq!(
+            poll("Question|a||b | ").unwrap(),
+            ("Question", vec!["a", "b"])
+        );
+    }
+
+    #[test]
+    fn poll_rejects_incomplete_input() {
+        assert_eq!(
+            user_error_key(&poll(" | a | b").unwrap_err()),
+            Some("reward.poll.usage")
+        );
+        assert_eq!(
+            user_error_key(&poll("Question | a").unwrap_err()),
+            Some("reward.poll.two_options")
+        );
+        assert_eq!(
+            user_error_key(&poll("Question").unwrap_err()),
+            Some("reward.poll.two_options")
+        );
+    }
+}
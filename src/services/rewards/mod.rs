@@ -1,5 +1,7 @@
 use twitch_api::eventsub::channel::ChannelPointsCustomRewardRedemptionAddV1Payload;
 
+pub mod approval;
+pub mod error;
 pub mod execute;
 pub mod extract;
 pub mod redemption;
@@ -1,11 +1,26 @@
-use super::Redemption;
+use super::{
+    approval,
+    error::is_user_error,
+    reply::{
+        pick_template, reply_to_failed_redemption, reply_to_redemption,
+        RedemptionReply,
+    },
+    Redemption,
+};
 use crate::{
-    actors::rewards::ExecuteRewardMessage, log_discord,
-    services::twitch::eventsub::update_reward_redemption, PgPool, RewardsActor,
-    User,
+    actors::rewards::ExecuteRewardMessage,
+    log_discord, log_err,
+    services::{
+        i18n::DEFAULT_LOCALE, twitch::eventsub::update_reward_redemption,
+    },
+    PgPool, RewardsActor, User,
 };
 use actix::{Addr, MailboxError};
-use models::reward::Reward;
+use models::{
+    log_entry::{LogEntry, LogEntryType},
+    reward::{RefundPolicy, ReplyTemplates, Reward, RewardData},
+};
+use serde_json::json;
 use std::{
     sync::Arc,
     time::{Duration, Instant},
@@ -27,31 +42,40 @@ pub struct ReceiveRedemptionCtx {
 }
 
 struct RedemptionCtx {
+    broadcaster_id: String,
     broadcaster_login: String,
     executing_user_login: String,
     user_input: String,
 
+    reward_id: String,
     reward_name: String,
     reward_type: String,
+
+    locale: String,
 }
 
-struct RedemptionUpdateHandle {
+pub(super) struct RedemptionUpdateHandle {
     broadcaster_id: String,
     reward_id: String,
     redemption_id: String,
 }
 
-impl From<(&Redemption, &Reward)> for RedemptionCtx {
-    fn from((notification, reward): (&Redemption, &Reward)) -> Self {
+impl From<(&Redemption, &Reward, String)> for RedemptionCtx {
+    fn from(
+        (notification, reward, locale): (&Redemption, &Reward, String),
+    ) -> Self {
         Self {
-            executing_user_login: notification.user_name.clone().take(),
+            broadcaster_id: notification.broadcaster_user_id.clone().take(),
+            executing_user_login: notification.user_login.clone().take(),
             broadcaster_login: notification
                 .broadcaster_user_login
                 .clone()
                 .take(),
+            reward_id: notification.reward.id.clone().take(),
             reward_name: notification.reward.title.clone(),
             reward_type: reward.data.0.to_string(),
             user_input: notification.user_input.clone(),
+            locale,
         }
     }
 }
@@ -67,25 +91,85 @@ impl From<&Redemption> for RedemptionUpdateHandle {
 }
 
 impl RedemptionCtx {
+    /// Reports the error to the user and returns whether the redemption should be refunded.
     async fn handle_execution_error(
         &self,
-        error: Result<anyhow::Result<()>, MailboxError>,
-    ) {
-        let (debug, display) = match error {
-            Err(e) => (format!("{:?}", e), e.to_string()),
-            Ok(Err(e)) => (format!("{:?}", e), e.to_string()),
+        error: Result<anyhow::Result<Option<RedemptionReply>>, MailboxError>,
+        policy: RefundPolicy,
+        template: Option<&str>,
+        pool: &PgPool,
+    ) -> bool {
+        let (debug, display, user_error) = match &error {
+            Err(e) => (format!("{:?}", e), e.to_string(), false),
+            Ok(Err(e)) => (format!("{:?}", e), e.to_string(), is_user_error(e)),
             Ok(Ok(_)) => unreachable!(),
         };
+        let refunded = policy.should_refund(user_error);
 
         log::warn!("Could not execute reward: {:?}", debug);
 
+        if let Ok(Err(e)) = &error {
+            reply_to_failed_redemption(
+                e,
+                refunded,
+                template,
+                &self.broadcaster_id,
+                &self.executing_user_login,
+                &self.locale,
+            )
+            .await;
+        }
+
+        log_err!(
+            LogEntry::new(
+                &self.broadcaster_id,
+                LogEntryType::RewardFailed,
+                format!("{} failed: {display}", self.reward_name),
+            )
+            .actor(&self.executing_user_login)
+            .reward(&self.reward_id)
+            .payload(json!({
+                "reward_type": self.reward_type,
+                "error": display,
+                "user_error": user_error,
+                "refunded": refunded,
+                "policy": format!("{policy:?}"),
+            }))
+            .insert(pool)
+            .await,
+            "Could not create log-entry"
+        );
+
         log_discord!(
             "Rewards",
             format!("⚠ Failed to execute reward in {}", self.broadcaster_login),
             0xfab43e,
             "Reward" = self.reward_name.clone(),
             "Type" = self.reward_type.clone(),
-            "Error" = display
+            "Error" = display,
+            "Error Kind" = if user_error { "user" } else { "internal" },
+            "Refunded" = refunded.to_string()
+        );
+
+        refunded
+    }
+
+    async fn log_executed(&self, pool: &PgPool) {
+        log_err!(
+            LogEntry::new(
+                &self.broadcaster_id,
+                LogEntryType::RewardExecuted,
+                format!("{} executed", self.reward_name),
+            )
+            .actor(&self.executing_user_login)
+            .reward(&self.reward_id)
+            .payload(json!({
+                "reward_type": self.reward_type,
+                "input": self.user_input,
+            }))
+            .insert(pool)
+            .await,
+            "Could not create log-entry"
         );
     }
 
@@ -117,7 +201,11 @@ impl RedemptionCtx {
 }
 
 impl RedemptionUpdateHandle {
-    async fn update(self, user: User, status: CustomRewardRedemptionStatus) {
+    pub(super) async fn update(
+        self,
+        user: User,
+        status: CustomRewardRedemptionStatus,
+    ) {
         match update_reward_redemption(
             &self.broadcaster_id,
             &self.reward_id,
@@ -154,10 +242,52 @@ pub async fn receive(
         .await
         .map_err(|_| ReceiveRedemptionError::NoReward)?;
 
-    let ctx = RedemptionCtx::from((&notification, &reward));
+    if reward.data.0.requires_approval() {
+        approval::request_approval(notification, user, &pool).await;
+        return Ok(());
+    }
+
+    execute_redemption(
+        &pool,
+        &executor,
+        notification,
+        user,
+        reward,
+        redemption_received,
+    )
+    .await;
+
+    Ok(())
+}
+
+/// Executes the reward, replies in chat and updates the redemption.
+pub async fn execute_redemption(
+    pool: &PgPool,
+    executor: &Addr<RewardsActor>,
+    notification: Redemption,
+    user: User,
+    reward: Reward,
+    redemption_received: Instant,
+) {
+    let locale = User::get_locale(&user.id, pool).await.unwrap_or_else(|e| {
+        log::warn!("Could not get locale: {}", e);
+        DEFAULT_LOCALE.to_string()
+    });
+    let ctx = RedemptionCtx::from((&notification, &reward, locale));
     let update_handle = RedemptionUpdateHandle::from(&notification);
 
     let auto_accept = reward.auto_accept;
+    // song requests are fulfilled once they're played and refunded if they
+    // never get to play (see services::spotify::queue)
+    let settled_later = matches!(reward.data.0, RewardData::SpotifyQueue(_));
+    let refund_policy = reward.refund_on_error;
+    let reward_templates = reward.reply_templates.0.clone();
+    let channel_templates = User::get_reply_templates(&user.id, pool)
+        .await
+        .unwrap_or_else(|e| {
+            log::warn!("Could not get reply templates: {}", e);
+            ReplyTemplates::default()
+        });
     let status = match executor
         .send(ExecuteRewardMessage {
             redemption: notification,
@@ -166,17 +296,42 @@ pub async fn receive(
         })
         .await
     {
-        Ok(Ok(_)) => CustomRewardRedemptionStatus::Fulfilled,
+        Ok(Ok(reply)) => {
+            if let Some(reply) = reply {
+                reply_to_redemption(
+                    &reply,
+                    pick_template(&reward_templates, &channel_templates, |t| {
+                        &t.success
+                    }),
+                    &ctx.broadcaster_id,
+                    &ctx.executing_user_login,
+                    &ctx.locale,
+                )
+                .await;
+            }
+            ctx.log_executed(pool).await;
+            CustomRewardRedemptionStatus::Fulfilled
+        }
         e => {
-            ctx.handle_execution_error(e).await;
-            CustomRewardRedemptionStatus::Canceled
+            let template =
+                pick_template(&reward_templates, &channel_templates, |t| {
+                    &t.error
+                });
+            if ctx
+                .handle_execution_error(e, refund_policy, template, pool)
+                .await
+            {
+                CustomRewardRedemptionStatus::Canceled
+            } else {
+                CustomRewardRedemptionStatus::Fulfilled
+            }
         }
     };
     // here, the redemption is finally updated, so we'll log this
     ctx.log_redemption(status, redemption_received).await;
-    if auto_accept {
+    // refunds are always applied, even if the reward isn't auto-accepted
+    let refunded = matches!(status, CustomRewardRedemptionStatus::Canceled);
+    if refunded || (auto_accept && !settled_later) {
         update_handle.update(user, status).await;
     }
-
-    Ok(())
 }
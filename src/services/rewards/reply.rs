@@ -3,68 +3,135 @@ use anyhow::Result as AnyResult;
 use crate::{
     log_err,
     services::{
-        rewards::Redemption,
+        i18n::Text,
+        rewards::error::render_error,
+        template,
         twitch::{self, requests::send_chat_message},
     },
+    text,
 };
+use models::reward::ReplyTemplates;
 
 pub enum SpotifyAction {
     Skip,
     Play,
-    Queue,
+}
+
+/// The placeholders available in error templates
+pub const ERROR_PLACEHOLDERS: &[&str] = &["user", "error"];
+
+/// The reply to a successful redemption.
+pub struct RedemptionReply {
+    /// The built-in message used if there's no template
+    pub message: Text,
+    /// Values for the placeholders in templates
+    pub values: Vec<(&'static str, Text)>,
+}
+
+impl RedemptionReply {
+    pub fn new(message: Text) -> Self {
+        Self {
+            message,
+            values: Vec::new(),
+        }
+    }
+
+    pub fn with(mut self, name: &'static str, value: impl Into<Text>) -> Self {
+        self.values.push((name, value.into()));
+        self
+    }
+
+    fn render(
+        &self,
+        template: Option<&str>,
+        user: &str,
+        locale: &str,
+    ) -> String {
+        let Some(template) = template else {
+            return self.message.render(locale);
+        };
+        template::render(template, |var| {
+            // the channel's default template might use placeholders
+            // that aren't available for this reward
+            Some(match var {
+                "user" => user.to_string(),
+                _ => self
+                    .values
+                    .iter()
+                    .find(|(name, _)| *name == var)
+                    .map(|(_, value)| value.render(locale))
+                    .unwrap_or_default(),
+            })
+        })
+    }
 }
 
 pub fn format_spotify_result(
     res: AnyResult<String>,
     action: SpotifyAction,
-) -> AnyResult<String> {
-    res.map(|msg| {
-        format!(
-            "{} {}",
-            match action {
-                SpotifyAction::Skip => "⏭ Skipped",
-                SpotifyAction::Play => "▶ Playing",
-                SpotifyAction::Queue => "🗒 Queued",
-            },
-            msg
-        )
+) -> AnyResult<RedemptionReply> {
+    res.map(|track| {
+        let message = match action {
+            SpotifyAction::Skip => text!("reward.spotify.skipped"),
+            SpotifyAction::Play => text!("reward.spotify.playing"),
+        };
+        RedemptionReply::new(message.arg("track", track.clone()))
+            .with("track", track)
     })
 }
 
-pub fn get_reply_data(redemption: &Redemption) -> (String, String) {
-    (
-        redemption.broadcaster_user_id.clone().take(),
-        redemption.user_login.clone().take(),
-    )
+/// Picks the reward's template and falls back to the channel's default.
+pub fn pick_template<'a>(
+    reward: &'a ReplyTemplates,
+    channel: &'a ReplyTemplates,
+    select: impl Fn(&ReplyTemplates) -> &Option<String>,
+) -> Option<&'a str> {
+    select(reward).as_deref().or(select(channel).as_deref())
 }
 
 pub async fn reply_to_redemption(
-    res: AnyResult<Option<String>>,
+    reply: &RedemptionReply,
+    template: Option<&str>,
     broadcaster_id: &str,
     user: &str,
-) -> AnyResult<()> {
-    match res {
-        Ok(Some(msg)) => {
-            log_err!(
-                send_chat_message(broadcaster_id, &msg, &twitch::get_token())
-                    .await,
-                "Failed to send chat"
-            );
-            // don't return Err() since it will turn the redemption into Cancelled even though it was fulfilled
-            Ok(())
+    locale: &str,
+) {
+    log_err!(
+        send_chat_message(
+            broadcaster_id,
+            &reply.render(template, user, locale),
+            &twitch::get_token()
+        )
+        .await,
+        "Failed to send chat"
+    );
+}
+
+pub async fn reply_to_failed_redemption(
+    error: &anyhow::Error,
+    refunded: bool,
+    template: Option<&str>,
+    broadcaster_id: &str,
+    user: &str,
+    locale: &str,
+) {
+    let error = render_error(error, locale);
+    let msg = match template {
+        Some(template) => template::render(template, |var| match var {
+            "user" => Some(user.to_string()),
+            "error" => Some(error.clone()),
+            _ => None,
+        }),
+        None if refunded => {
+            text!("reward.failed.refunded", user = user, error = error)
+                .render(locale)
         }
-        Ok(None) => Ok(()),
-        Err(e) => {
-            log_err!(
-                send_chat_message(
-                    broadcaster_id,
-                    &format!("@{} [⚠ Refund] {}", user, e),
-                    &twitch::get_token(),
-                )
-                .await,
-                "Failed to send chat"
-            );
-            Err(e)
+        None => {
+            text!("reward.failed", user = user, error = error).render(locale)
         }
-    }
+    };
+    log_err!(
+        send_chat_message(broadcaster_id, &msg, &twitch::get_token()).await,
+        "Failed to send chat"
+    );
 }
@@ -57,6 +57,18 @@ pub async fn save_reward(
                 .await?;
             }
         }
+        RewardData::AnySwap(data) => {
+            if let Some(limit) = &data.swap.limit {
+                swap::update_any_swap_limit(
+                    broadcaster_id,
+                    reward_id,
+                    *limit,
+                    pool,
+                    redis_pool,
+                )
+                .await?;
+            }
+        }
         RewardData::BttvSlot(slot) => {
             let bttv_id = bttv::get_or_fetch_id(broadcaster_id, pool).await?;
 
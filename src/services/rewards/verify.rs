@@ -1,15 +1,34 @@
 use anyhow::{Error as AnyError, Result as AnyResult};
 use sqlx::PgPool;
-use twitch_api::twitch_oauth2::UserToken;
+use twitch_api::twitch_oauth2::{Scope, UserToken};
 
 use crate::services::{
-    bttv, ffz::is_editor_in, rewards::extract, seven_tv,
-    spotify::rewards as spotify, twitch::requests::get_user,
+    bttv,
+    ffz::is_editor_in,
+    rewards::{extract, reply::ERROR_PLACEHOLDERS},
+    seven_tv,
+    spotify::rewards as spotify,
+    template::verify_template,
+    twitch::requests::get_user,
 };
-use models::reward::RewardData;
+use models::{
+    reward::{
+        EmoteSearchMode, ReplyTemplates, RewardData, SpotifyPlayOptions,
+        SwapRewardData,
+    },
+    user::User,
+};
+
+/// Twitch limits chat messages to 500 characters,
+/// leave some room for the values of the placeholders.
+const MAX_TEMPLATE_LENGTH: usize = 400;
+
+const ALL_SUCCESS_PLACEHOLDERS: &[&str] =
+    &["user", "emote", "removed", "platform", "expires", "track"];
 
 pub async fn verify_reward(
     reward: &RewardData,
+    user_input_required: bool,
     broadcaster_id: &str,
     pool: &PgPool,
     token: &UserToken,
@@ -21,12 +40,23 @@ pub async fn verify_reward(
         RewardData::Timeout(data) => {
             extract::duration(&data.duration)?;
         }
+        RewardData::SlowMode(data) => {
+            extract::duration(&data.duration)?;
+            extract::duration(&data.wait_time)?;
+        }
+        RewardData::FollowerOnly(data) => {
+            extract::duration(&data.duration)?;
+            extract::duration(&data.follow_age)?;
+        }
 
         // verify editor
-        RewardData::BttvSwap(_) => {
+        RewardData::BttvSwap(data) => {
             bttv::verify_user(broadcaster_id, pool).await?;
+            verify_swap_data(data)?;
         }
-        RewardData::FfzSwap(_) => {
+        RewardData::FfzSwap(data) => {
+            verify_swap_data(data)?;
+            verify_ffz_search(&data.search)?;
             let user = get_user(broadcaster_id.to_string(), token).await?;
             if !is_editor_in(user.login.as_ref()).await {
                 return Err(AnyError::msg(
@@ -34,9 +64,36 @@ pub async fn verify_reward(
                 ));
             }
         }
-        RewardData::SevenTvSwap(_) => {
+        RewardData::SevenTvSwap(data) => {
+            verify_swap_data(data)?;
             seven_tv::verify_user(broadcaster_id).await?;
         }
+        RewardData::AnySwap(data) => {
+            verify_swap_data(&data.swap)?;
+            // the platform is detected from the link or id
+            if data.swap.search.is_some() {
+                return Err(AnyError::msg(
+                    "Emotes can't be searched by name if any platform is accepted",
+                ));
+            }
+            if !(data.bttv || data.ffz || data.seventv) {
+                return Err(AnyError::msg("No platform is enabled"));
+            }
+            if data.bttv {
+                bttv::verify_user(broadcaster_id, pool).await?;
+            }
+            if data.ffz {
+                let user = get_user(broadcaster_id.to_string(), token).await?;
+                if !is_editor_in(user.login.as_ref()).await {
+                    return Err(AnyError::msg(
+                        "RewardMore isn't an editor for the user",
+                    ));
+                }
+            }
+            if data.seventv {
+                seven_tv::verify_user(broadcaster_id).await?;
+            }
+        }
         RewardData::BttvSlot(slot) => {
             bttv::verify_user(broadcaster_id, pool).await?;
 
@@ -47,6 +104,7 @@ pub async fn verify_reward(
             extract::duration(&slot.expiration)?;
         }
         RewardData::FfzSlot(slot) => {
+            verify_ffz_search(&slot.search)?;
             let user = get_user(broadcaster_id.to_string(), token).await?;
             if !is_editor_in(user.login.as_ref()).await {
                 return Err(AnyError::msg(
@@ -69,11 +127,38 @@ pub async fn verify_reward(
 
             extract::duration(&slot.expiration)?;
         }
-        RewardData::SpotifySkip(_)
-        | RewardData::SpotifyQueue(_)
-        | RewardData::SpotifyPlay(_) => {
+        RewardData::SpotifySkip(_) => {
             spotify::get_spotify_token(broadcaster_id, pool).await?;
         }
+        RewardData::SpotifyPlay(opts) => {
+            spotify::get_spotify_token(broadcaster_id, pool).await?;
+            verify_spotify_play(opts)?;
+        }
+        RewardData::SpotifyQueue(opts) => {
+            spotify::get_spotify_token(broadcaster_id, pool).await?;
+            verify_spotify_play(&opts.play)?;
+
+            if opts.max_per_user == Some(0) {
+                return Err(AnyError::msg("Limits must be at least 1"));
+            }
+            if opts.blocklist.len() > 200 {
+                return Err(AnyError::msg(
+                    "The blocklist is limited to 200 entries",
+                ));
+            }
+        }
+        RewardData::Poll(data) => {
+            verify_user_input(user_input_required)?;
+            extract::duration(&data.duration)?;
+            verify_scope(broadcaster_id, Scope::ChannelManagePolls, pool)
+                .await?;
+        }
+        RewardData::Prediction(data) => {
+            verify_user_input(user_input_required)?;
+            extract::duration(&data.duration)?;
+            verify_scope(broadcaster_id, Scope::ChannelManagePredictions, pool)
+                .await?;
+        }
         RewardData::RemEmote(d) => match d.platform {
             models::emote::SlotPlatform::Bttv => {
                 bttv::verify_user(broadcaster_id, pool).await?;
@@ -94,6 +179,78 @@ pub async fn verify_reward(
     Ok(())
 }
 
+/// The question and options are taken from the input.
+fn verify_user_input(user_input_required: bool) -> AnyResult<()> {
+    if user_input_required {
+        Ok(())
+    } else {
+        Err(AnyError::msg("The reward has to require user input"))
+    }
+}
+
+fn verify_swap_data(data: &SwapRewardData) -> AnyResult<()> {
+    match data.max_per_redemption {
+        Some(0) => Err(AnyError::msg("At least one emote has to be added")),
+        Some(max) if max > 10 => {
+            Err(AnyError::msg("10 emotes per redemption is the max"))
+        }
+        Some(max) if data.limit.is_some_and(|l| max as u16 > l) => {
+            Err(AnyError::msg(
+                "Can't add more emotes per redemption than the limit",
+            ))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Names of FFZ emotes can't be resolved, since there is no search for them.
+fn verify_ffz_search(search: &Option<EmoteSearchMode>) -> AnyResult<()> {
+    if search.is_some() {
+        return Err(AnyError::msg("FFZ emotes can't be searched by name"));
+    }
+    Ok(())
+}
+
+fn verify_spotify_play(opts: &SpotifyPlayOptions) -> AnyResult<()> {
+    if opts.max_duration == Some(0) {
+        return Err(AnyError::msg("Limits must be at least 1"));
+    }
+    if opts.min_popularity.is_some_and(|p| p > 100) {
+        return Err(AnyError::msg("The popularity is at most 100"));
+    }
+    if opts.playlists.len() > 10 {
+        return Err(AnyError::msg("Requests are limited to 10 playlists"));
+    }
+    if let Some(playlist) = opts
+        .playlists
+        .iter()
+        .find(|p| spotify::extract_spotify_id(p).is_none())
+    {
+        return Err(AnyError::msg(format!("Invalid playlist: {}", playlist)));
+    }
+    if opts.genres.len() > 50 {
+        return Err(AnyError::msg("Requests are limited to 50 genres"));
+    }
+
+    Ok(())
+}
+
+/// Users that logged in before the scope was requested have to log in again.
+async fn verify_scope(
+    broadcaster_id: &str,
+    scope: Scope,
+    pool: &PgPool,
+) -> AnyResult<()> {
+    let user = User::get_by_id(broadcaster_id, pool).await?;
+    let scope = scope.to_string();
+    if !user.scopes.split_whitespace().any(|s| s == scope) {
+        return Err(AnyError::msg(format!(
+            "RewardMore doesn't have the {scope} permission - log in again to grant it"
+        )));
+    }
+    Ok(())
+}
+
 pub fn verify_live_delay(delay: &Option<String>) -> AnyResult<()> {
     if let Some(delay) = delay {
         humantime::parse_duration(delay).map_err(|e| {
@@ -102,3 +259,141 @@ pub fn verify_live_delay(delay: &Option<String>) -> AnyResult<()> {
     }
     Ok(())
 }
+
+/// Gets the placeholders available in the success template of a reward.
+/// Returns `None` if the reward doesn't reply on success.
+fn success_placeholders(
+    reward: &RewardData,
+) -> Option<&'static [&'static str]> {
+    match reward {
+        RewardData::BttvSwap(_)
+        | RewardData::FfzSwap(_)
+        | RewardData::SevenTvSwap(_)
+        | RewardData::AnySwap(_) => {
+            Some(&["user", "emote", "removed", "platform"])
+        }
+        RewardData::BttvSlot(_)
+        | RewardData::FfzSlot(_)
+        | RewardData::SevenTvSlot(_) => {
+            Some(&["user", "emote", "platform", "expires"])
+        }
+        RewardData::RemEmote(_) => Some(&["user", "emote", "platform"]),
+        RewardData::SpotifySkip(_)
+        | RewardData::SpotifyQueue(_)
+        | RewardData::SpotifyPlay(_) => Some(&["user", "track"]),
+        RewardData::Poll(_) | RewardData::Prediction(_) => Some(&["user"]),
+        RewardData::Timeout(_)
+        | RewardData::SubOnly(_)
+        | RewardData::EmoteOnly(_)
+        | RewardData::SlowMode(_)
+        | RewardData::FollowerOnly(_) => None,
+    }
+}
+
+/// Verifies the templates of a reward or the channel's defaults
+/// (`reward` is `None`), which may use every placeholder.
+pub fn verify_reply_templates(
+    templates: &ReplyTemplates,
+    reward: Option<&RewardData>,
+) -> AnyResult<()> {
+    if let Some(template) = &templates.success {
+        let allowed = match reward {
+            Some(reward) => success_placeholders(reward).ok_or_else(|| {
+                AnyError::msg("This reward doesn't reply on success")
+            })?,
+            None => ALL_SUCCESS_PLACEHOLDERS,
+        };
+        verify_template(template, allowed, MAX_TEMPLATE_LENGTH)
+            .map_err(|e| AnyError::msg(format!("Success reply: {e}")))?;
+    }
+    if let Some(template) = &templates.error {
+        verify_template(template, ERROR_PLACEHOLDERS, MAX_TEMPLATE_LENGTH)
+            .map_err(|e| AnyError::msg(format!("Error reply: {e}")))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn swap(
+        max_per_redemption: Option<u8>,
+        limit: Option<u16>,
+    ) -> SwapRewardData {
+        SwapRewardData {
+            limit,
+            allow_unlisted: true,
+            max_per_redemption,
+            search: None,
+            reply: true,
+            require_approval: false,
+        }
+    }
+
+    fn templates(success: Option<&str>, error: Option<&str>) -> ReplyTemplates {
+        ReplyTemplates {
+            success: success.map(ToOwned::to_owned),
+            error: error.map(ToOwned::to_owned),
+        }
+    }
+
+    #[test]
+    fn success_template_uses_reward_placeholders() {
+        let reward = RewardData::BttvSwap(swap(None, None));
+        let ok =
+            templates(Some("{user} added {emote}, removed {removed}"), None);
+        assert!(verify_reply_templates(&ok, Some(&reward)).is_ok());
+
+        let track = templates(Some("{user} queued {track}"), None);
+        let err = verify_reply_templates(&track, Some(&reward)).unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("Success reply: Unknown variable"));
+        // the channel's defaults can use every placeholder
+        assert!(verify_reply_templates(&track, None).is_ok());
+    }
+
+    #[test]
+    fn success_template_needs_a_reply() {
+        let reward = RewardData::SubOnly("1m".to_owned());
+        let err = verify_reply_templates(
+            &templates(Some("{user}"), None),
+            Some(&reward),
+        )
+        .unwrap_err();
+        assert_eq!(err.to_string(), "This reward doesn't reply on success");
+        // the error reply is always sent
+        assert!(verify_reply_templates(
+            &templates(None, Some("{user}: {error}")),
+            Some(&reward)
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn error_template_placeholders() {
+        let err =
+            verify_reply_templates(&templates(None, Some("{emote}")), None)
+                .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Error reply: Unknown variable {emote} (available: {user}, {error})"
+        );
+    }
+
+    #[test]
+    fn swap_limits() {
+        assert!(verify_swap_data(&swap(None, Some(1))).is_ok());
+        assert!(verify_swap_data(&swap(Some(3), Some(3))).is_ok());
+        assert!(verify_swap_data(&swap(Some(0), None)).is_err());
+        assert!(verify_swap_data(&swap(Some(11), None)).is_err());
+        assert!(verify_swap_data(&swap(Some(4), Some(3))).is_err());
+    }
+
+    #[test]
+    fn ffz_search_is_rejected() {
+        assert!(verify_ffz_search(&None).is_ok());
+        assert!(verify_ffz_search(&Some(EmoteSearchMode::Exact)).is_err());
+    }
+}
@@ -66,6 +66,36 @@ pub struct SevenEmote {
     pub id: String,
     #[serde(default)]
     pub listed: bool,
+    #[serde(default)]
+    pub animated: bool,
+    /// See [SEVENTV_FLAG_ZERO_WIDTH] and [SEVENTV_FLAG_NSFW]
+    #[serde(default)]
+    pub flags: u32,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub owner: Option<SevenEmoteOwner>,
+}
+
+pub const SEVENTV_FLAG_ZERO_WIDTH: u32 = 1 << 8;
+pub const SEVENTV_FLAG_NSFW: u32 = 1 << 16;
+
+#[derive(Serialize, Deserialize, Debug)]
+#[non_exhaustive]
+pub struct SevenEmoteOwner {
+    pub id: String,
+}
+
+#[derive(Deserialize, Debug)]
+#[non_exhaustive]
+struct SevenSearchResponse {
+    emotes: SevenSearchItems,
+}
+
+#[derive(Deserialize, Debug)]
+#[non_exhaustive]
+struct SevenSearchItems {
+    items: Vec<SevenEmote>,
 }
 
 #[derive(Deserialize, Debug)]
@@ -138,6 +168,11 @@ struct GqlIdVars<'a> {
     id: &'a str,
 }
 
+#[derive(Serialize)]
+struct GqlQueryVars<'a> {
+    query: &'a str,
+}
+
 #[derive(Serialize)]
 struct GqlEmoteInSetVars<'a> {
     set_id: &'a str,
@@ -157,7 +192,8 @@ struct VoidObject {}
 
 pub async fn get_user(user_id: &str) -> AnyResult<SevenUserResponse> {
     seven_tv_get::<SevenUserResponse>(format!(
-        "https://7tv.io/v3/users/twitch/{}",
+        "{}/v3/users/twitch/{}",
+        CONFIG.urls.seven_tv,
         CONFIG.debug_overrides.seventv(user_id)
     ))
     .await
@@ -165,18 +201,18 @@ pub async fn get_user(user_id: &str) -> AnyResult<SevenUserResponse> {
 
 pub async fn get_emote_set(id: &str) -> AnyResult<SevenEmoteSet> {
     seven_tv_get::<SevenEmoteSet>(format!(
-        "https://7tv.io/v3/emote-sets/{}",
-        id
+        "{}/v3/emote-sets/{}",
+        CONFIG.urls.seven_tv, id
     ))
     .await
 }
 
 pub async fn get_emote(emote_id: &str) -> AnyResult<SevenEmote> {
     let emote = seven_tv_post::<SevenEmoteResponse>(
-        "https://7tv.io/v3/gql",
+        gql_v3(),
         &GqlRequest {
             query:
-                "query($id: ObjectID!) { emote(id: $id) { id, name, listed } }",
+                "query($id: ObjectID!) { emote(id: $id) { id, name, listed, animated, flags, tags, owner { id } } }",
             variables: GqlIdVars { id: emote_id },
         },
     )
@@ -185,12 +221,38 @@ pub async fn get_emote(emote_id: &str) -> AnyResult<SevenEmote> {
     Ok(emote.data.emote)
 }
 
+/// Searches public emotes by name, most used first
+pub async fn search_emotes(query: &str) -> AnyResult<Vec<SevenEmote>> {
+    let res = seven_tv_post::<SevenSearchResponse>(
+        gql_v3(),
+        &GqlRequest {
+            query: r#"
+                query ($query: String!) {
+                    emotes(
+                        query: $query
+                        page: 1
+                        limit: 25
+                        filter: { exact_match: false, case_sensitive: false, ignore_tags: true }
+                        sort: { value: "popularity", order: DESCENDING }
+                    ) {
+                        items { id, name, listed }
+                    }
+                }
+            "#,
+            variables: GqlQueryVars { query },
+        },
+    )
+    .await?;
+
+    Ok(res.data.emotes.items)
+}
+
 pub async fn add_emote(
     emote_set_id: &str,
     emote_id: &str,
     overwritten_name: Option<&str>,
 ) -> AnyResult<()> {
-    seven_tv_post::<Option<VoidObject>>("https://7tv.io/v3/gql", &GqlRequest {
+    seven_tv_post::<Option<VoidObject>>(gql_v3(), &GqlRequest {
         query: "mutation($set_id: ObjectID!, $emote_id: ObjectID!, $name: String) { emoteSet(id: $set_id) { emotes(id: $emote_id, action: ADD, name: $name) { id } } }",
         variables: GqlEmoteInSetVars { set_id: emote_set_id, emote_id, name: overwritten_name }
     }).await?;
@@ -199,7 +261,7 @@ pub async fn add_emote(
 }
 
 pub async fn remove_emote(emote_set_id: &str, emote_id: &str) -> AnyResult<()> {
-    seven_tv_post::<Option<VoidObject>>("https://7tv.io/v3/gql", &GqlRequest {
+    seven_tv_post::<Option<VoidObject>>(gql_v3(), &GqlRequest {
         query: "mutation($set_id: ObjectID!, $emote_id: ObjectID!, $name: String) { emoteSet(id: $set_id) { emotes(id: $emote_id, action: REMOVE, name: $name) { id } } }",
         variables: GqlEmoteInSetVars { set_id: emote_set_id, emote_id, name: None }
     }).await?;
@@ -209,7 +271,7 @@ pub async fn remove_emote(emote_set_id: &str, emote_id: &str) -> AnyResult<()> {
 
 pub async fn get_editor_relations() -> AnyResult<Vec<SevenEditorRelation>> {
     let res = seven_tv_post::<GqlEditorRelationResponse>(
-        "https://7tv.io/v4/gql",
+        gql_v4(),
         &GqlRequest {
             query: r#"
                 query ($id: Id!) {
@@ -236,7 +298,7 @@ pub async fn get_editor_relations() -> AnyResult<Vec<SevenEditorRelation>> {
 
 pub async fn approve_editor(user_id: &str, editor_id: &str) -> AnyResult<()> {
     seven_tv_post::<VoidObject>(
-        "https://7tv.io/v4/gql",
+        gql_v4(),
         &GqlRequest {
             query: r#"
                 mutation ($user_id: Id!, $editor_id: Id!) {
@@ -264,7 +326,7 @@ pub async fn logged_in() -> bool {
         actor: Option<VoidObject>,
     }
     seven_tv_post::<Data>(
-        "https://7tv.io/v3/gql",
+        gql_v3(),
         &GqlRequest {
             query: "query {actor { id } }",
             variables: (),
@@ -275,6 +337,14 @@ pub async fn logged_in() -> bool {
     .unwrap_or(false)
 }
 
+fn gql_v3() -> String {
+    format!("{}/v3/gql", CONFIG.urls.seven_tv)
+}
+
+fn gql_v4() -> String {
+    format!("{}/v4/gql", CONFIG.urls.seven_tv)
+}
+
 async fn seven_tv_post<J>(
     url: impl IntoUrl,
     request: &GqlRequest<'_, impl Serialize>,
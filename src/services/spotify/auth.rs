@@ -32,7 +32,7 @@ pub fn get_auth_url(user_id: String) -> AnyResult<(String, String)> {
         state: &jwt,
     })?;
     Ok((
-        format!("https://accounts.spotify.com/authorize?{}", query),
+        format!("{}/authorize?{}", CONFIG.urls.spotify_accounts, query),
         jwt,
     ))
 }
@@ -1,5 +1,8 @@
 pub mod auth;
+pub mod overlay;
+pub mod queue;
 pub mod requests;
 pub mod responses;
+pub mod restrictions;
 pub mod rewards;
 pub mod types;
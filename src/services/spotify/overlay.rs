@@ -0,0 +1,179 @@
+use crate::{
+    actors::now_playing::NowPlayingSubscription,
+    services::spotify::{
+        requests, responses::PlayingItem, rewards::get_spotify_token,
+    },
+};
+use actix_web::web::Bytes;
+use anyhow::Result as AnyResult;
+use futures::Stream;
+use models::song_request::SongRequest;
+use serde::Serialize;
+use sqlx::PgPool;
+use std::{convert::Infallible, time::Duration};
+use tokio::sync::broadcast::error::RecvError;
+
+/// A comment is sent if nothing changed for this long, so proxies keep the
+/// connection open.
+const KEEP_ALIVE: Duration = Duration::from_secs(15);
+
+/// State of the now-playing overlay, sent as JSON to its subscribers.
+#[derive(Serialize, Clone, PartialEq, Eq, Debug)]
+#[serde(tag = "state", rename_all = "snake_case")]
+pub enum OverlayState {
+    Playing(NowPlaying),
+    Stopped,
+}
+
+#[derive(Serialize, Clone, PartialEq, Eq, Debug)]
+pub struct NowPlaying {
+    pub title: String,
+    pub artists: Vec<String>,
+    pub album_art: Option<String>,
+    pub url: String,
+    /// Viewer that requested the track through the song queue
+    pub requested_by: Option<String>,
+    #[serde(skip)]
+    uri: String,
+}
+
+/// Gets the current item of the broadcaster's player. The requester is only
+/// looked up if the item changed since `previous`.
+pub async fn get_state(
+    broadcaster_id: &str,
+    previous: Option<&OverlayState>,
+    pool: &PgPool,
+) -> AnyResult<OverlayState> {
+    let token = get_spotify_token(broadcaster_id, pool).await?;
+    let player = requests::get_player(&token).await?;
+    let Some(item) = player.item.filter(|_| player.is_playing) else {
+        return Ok(OverlayState::Stopped);
+    };
+
+    let uri = item.uri().to_owned();
+    let url = item.spotify_url().to_owned();
+    let requested_by = match previous {
+        Some(OverlayState::Playing(previous)) if previous.uri == uri => {
+            previous.requested_by.clone()
+        }
+        _ => {
+            SongRequest::get_recent_requester(broadcaster_id, &uri, pool)
+                .await?
+        }
+    };
+    let (title, artists, album_art) = match item {
+        PlayingItem::Track(track) => (
+            track.name,
+            track.artists.into_iter().map(|a| a.name).collect(),
+            track
+                .album
+                .and_then(|album| album.images.into_iter().next())
+                .map(|image| image.url),
+        ),
+        PlayingItem::Episode(episode) => (
+            episode.name,
+            vec![episode.show.name],
+            episode.images.into_iter().next().map(|image| image.url),
+        ),
+    };
+
+    Ok(OverlayState::Playing(NowPlaying {
+        title,
+        artists,
+        album_art,
+        url,
+        requested_by,
+        uri,
+    }))
+}
+
+/// Formats the updates of a subscription as server-sent events.
+pub fn event_stream(
+    subscription: NowPlayingSubscription,
+) -> impl Stream<Item = Result<Bytes, Infallible>> {
+    let NowPlayingSubscription {
+        current,
+        mut receiver,
+    } = subscription;
+
+    async_stream::stream! {
+        if let Some(state) = current {
+            yield Ok(to_event(&state));
+        }
+        loop {
+            match tokio::time::timeout(KEEP_ALIVE, receiver.recv()).await {
+                Ok(Ok(state)) => yield Ok(to_event(&state)),
+                Ok(Err(RecvError::Lagged(_))) => continue,
+                Ok(Err(RecvError::Closed)) => break,
+                Err(_) => yield Ok(Bytes::from_static(b": keep-alive\n\n")),
+            }
+        }
+    }
+}
+
+fn to_event(state: &OverlayState) -> Bytes {
+    Bytes::from(format!(
+        "data: {}\n\n",
+        serde_json::to_string(state).unwrap_or_default()
+    ))
+}
+
+/// Generates a secret for the overlay URL.
+pub fn generate_token() -> String {
+    hex::encode(rand::random::<[u8; 24]>())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{json, Value};
+
+    fn event_json(state: &OverlayState) -> Value {
+        let event = to_event(state);
+        let event = std::str::from_utf8(&event).unwrap();
+        let data = event
+            .strip_prefix("data: ")
+            .and_then(|e| e.strip_suffix("\n\n"))
+            .expect("not a single SSE event");
+        serde_json::from_str(data).unwrap()
+    }
+
+    #[test]
+    fn playing_events_hide_the_uri() {
+        let state = OverlayState::Playing(NowPlaying {
+            title: "Track".to_owned(),
+            artists: vec!["A".to_owned(), "B".to_owned()],
+            album_art: None,
+            url: "https://open.spotify.com/track/track".to_owned(),
+            requested_by: Some("viewer".to_owned()),
+            uri: "spotify:track:track".to_owned(),
+        });
+        assert_eq!(
+            event_json(&state),
+            json!({
+                "state": "playing",
+                "title": "Track",
+                "artists": ["A", "B"],
+                "album_art": null,
+                "url": "https://open.spotify.com/track/track",
+                "requested_by": "viewer",
+            })
+        );
+    }
+
+    #[test]
+    fn stopped_events() {
+        assert_eq!(
+            event_json(&OverlayState::Stopped),
+            json!({ "state": "stopped" })
+        );
+    }
+
+    #[test]
+    fn tokens_are_random() {
+        let token = generate_token();
+        assert_eq!(token.len(), 48);
+        assert!(token.chars().all(|c| c.is_ascii_hexdigit()));
+        assert_ne!(token, generate_token());
+    }
+}
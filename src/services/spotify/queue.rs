@@ -0,0 +1,368 @@
+use crate::{
+    actors::song_queue::{FeedQueueMessage, SongQueueActor},
+    services::{
+        rewards::{
+            error::{is_user_error, user_error},
+            Redemption,
+        },
+        spotify::{
+            requests, responses::TrackObject, rewards::get_token_and_verify,
+        },
+        twitch::eventsub::update_reward_redemption,
+    },
+    text,
+};
+use actix::SystemService;
+use anyhow::Result as AnyResult;
+use chrono::{Duration, Utc};
+use itertools::Itertools;
+use models::{
+    reward::SpotifyQueueOptions,
+    song_request::{NewSongRequest, SongRequest, SongRequestStatus},
+    user::User,
+};
+use sqlx::PgPool;
+use twitch_api::helix::points::CustomRewardRedemptionStatus;
+
+/// Spotify's queue might not include a track right after it was sent.
+const SENT_GRACE_PERIOD_SECS: i64 = 10;
+
+/// Saves a requested track. Returns the request and its position in the
+/// queue (starting at 0).
+pub async fn request_track(
+    opts: &SpotifyQueueOptions,
+    redemption: &Redemption,
+    track: TrackObject,
+    pool: &PgPool,
+) -> AnyResult<(SongRequest, i64)> {
+    let broadcaster_id = redemption.broadcaster_user_id.as_str();
+    // the request would only be played once the broadcaster is live
+    get_token_and_verify(broadcaster_id, false, pool).await?;
+    check_request(opts, redemption, &track, pool).await?;
+
+    let artists = track.artists.iter().map(|a| &a.name).join(", ");
+    let request = NewSongRequest {
+        user_id: broadcaster_id,
+        reward_id: redemption.reward.id.as_str(),
+        redemption_id: redemption.id.as_str(),
+        requested_by: redemption.user_login.as_str(),
+        track_uri: &track.uri,
+        track_name: &track.name,
+        artists: &artists,
+        duration_ms: i32::try_from(track.duration_ms).unwrap_or(i32::MAX),
+        status: if opts.require_approval {
+            SongRequestStatus::Pending
+        } else {
+            SongRequestStatus::Queued
+        },
+    }
+    .insert(pool)
+    .await?;
+    let position = request.position(pool).await?;
+
+    if request.status == SongRequestStatus::Queued {
+        request_feed(broadcaster_id);
+    }
+
+    Ok((request, position))
+}
+
+async fn check_request(
+    opts: &SpotifyQueueOptions,
+    redemption: &Redemption,
+    track: &TrackObject,
+    pool: &PgPool,
+) -> AnyResult<()> {
+    let broadcaster_id = redemption.broadcaster_user_id.as_str();
+
+    if is_blocked(&opts.blocklist, track) {
+        return Err(user_error(text!("spotify.queue.blocked")));
+    }
+    if let Some(max) = opts.max_per_user {
+        let open = SongRequest::count_open_by(
+            broadcaster_id,
+            redemption.reward.id.as_str(),
+            redemption.user_login.as_str(),
+            pool,
+        )
+        .await?;
+        if open >= i64::from(max) {
+            return Err(user_error(text!(
+                "spotify.queue.user_limit",
+                max = max.to_string()
+            )));
+        }
+    }
+    if let Some(hours) = opts.repeat_cooldown {
+        let since = Utc::now() - Duration::hours(i64::from(hours));
+        if SongRequest::was_requested_since(
+            broadcaster_id,
+            &track.uri,
+            since,
+            pool,
+        )
+        .await?
+        {
+            return Err(user_error(text!(
+                "spotify.queue.repeated",
+                hours = hours.to_string()
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+/// Entries match track or artist names (case-insensitive), ids or the
+/// track's URI.
+fn is_blocked(blocklist: &[String], track: &TrackObject) -> bool {
+    let matches = |entry: &str, name: &str, id: Option<&str>| {
+        id == Some(entry) || name.to_lowercase() == entry.to_lowercase()
+    };
+    blocklist
+        .iter()
+        .map(|entry| entry.trim())
+        .filter(|entry| !entry.is_empty())
+        .any(|entry| {
+            track.uri == entry
+                || matches(entry, &track.name, track.id.as_deref())
+                || track
+                    .artists
+                    .iter()
+                    .any(|a| matches(entry, &a.name, a.id.as_deref()))
+        })
+}
+
+/// Formats the track of a request like a [TrackObject].
+pub fn describe(request: &SongRequest) -> String {
+    format!("\"{}\" by {}", request.track_name, request.artists)
+}
+
+/// Asks the [SongQueueActor] to feed the channel's queue now.
+pub fn request_feed(broadcaster_id: &str) {
+    SongQueueActor::from_registry().do_send(FeedQueueMessage {
+        broadcaster_id: broadcaster_id.to_owned(),
+    });
+}
+
+/// Sends the next request to Spotify once the previous one is playing.
+/// Only one request is in Spotify's queue at a time, so requests can be
+/// removed until they're sent. Requests removed after that are skipped
+/// once they come up.
+pub async fn feed(broadcaster_id: &str, pool: &PgPool) -> AnyResult<()> {
+    let token = match get_token_and_verify(broadcaster_id, false, pool).await {
+        Ok(token) => token,
+        // e.g. the broadcaster went offline
+        Err(e) if is_user_error(&e) => return Ok(()),
+        Err(e) => return Err(e),
+    };
+
+    let player = requests::get_player(&token).await?;
+    if let Some(sent) = SongRequest::get_in_flight(broadcaster_id, pool).await?
+    {
+        if sent.sent_at.is_some_and(|at| {
+            Utc::now() - at < Duration::seconds(SENT_GRACE_PERIOD_SECS)
+        }) {
+            return Ok(());
+        }
+        let is_playing = player
+            .item
+            .as_ref()
+            .is_some_and(|item| item.uri() == sent.track_uri);
+        if !is_playing {
+            let queue = requests::get_queue(&token).await?;
+            if queue.queue.iter().any(|item| item.uri() == sent.track_uri) {
+                return Ok(());
+            }
+        }
+
+        if sent.status == SongRequestStatus::Skipping {
+            if is_playing {
+                requests::skip_next(&token).await?;
+            }
+            if SongRequest::mark_removed(sent.id, pool).await? {
+                // it reached the player, so it isn't refunded
+                settle(&sent, CustomRewardRedemptionStatus::Fulfilled, pool)
+                    .await;
+            }
+        } else if SongRequest::mark_played(sent.id, pool).await? {
+            // it's either playing or it was played already
+            settle(&sent, CustomRewardRedemptionStatus::Fulfilled, pool).await;
+        }
+    }
+
+    let Some(next) = SongRequest::get_next_queued(broadcaster_id, pool).await?
+    else {
+        return Ok(());
+    };
+    // queueing fails if there's no active player
+    if !player.is_playing {
+        return Ok(());
+    }
+    requests::queue_item(&next.track_uri, &token).await?;
+    SongRequest::mark_sent(next.id, pool).await?;
+
+    Ok(())
+}
+
+pub async fn approve(
+    broadcaster_id: &str,
+    id: i32,
+    resolved_by: &str,
+    pool: &PgPool,
+) -> AnyResult<SongRequest> {
+    let request = SongRequest::approve(id, broadcaster_id, resolved_by, pool)
+        .await?
+        .ok_or_else(|| user_error(text!("spotify.queue.not_found")))?;
+    request_feed(broadcaster_id);
+
+    Ok(request)
+}
+
+/// Rejects a pending request and refunds it.
+pub async fn reject(
+    broadcaster_id: &str,
+    id: i32,
+    resolved_by: &str,
+    pool: &PgPool,
+) -> AnyResult<SongRequest> {
+    let request = SongRequest::reject(id, broadcaster_id, resolved_by, pool)
+        .await?
+        .ok_or_else(|| user_error(text!("spotify.queue.not_found")))?;
+    settle(&request, CustomRewardRedemptionStatus::Canceled, pool).await;
+
+    Ok(request)
+}
+
+/// Removes an open request. Requests in Spotify's queue are skipped once
+/// they come up. Only requests that never reached the player are refunded.
+/// Returns the request and whether it was refunded.
+pub async fn remove(
+    broadcaster_id: &str,
+    id: i32,
+    resolved_by: &str,
+    pool: &PgPool,
+) -> AnyResult<(SongRequest, bool)> {
+    let request = SongRequest::remove(id, broadcaster_id, resolved_by, pool)
+        .await?
+        .ok_or_else(|| user_error(text!("spotify.queue.not_found")))?;
+    // skipped requests are settled by `feed`
+    let refunded = request.status != SongRequestStatus::Skipping
+        && settle(&request, CustomRewardRedemptionStatus::Canceled, pool).await;
+
+    Ok((request, refunded))
+}
+
+/// The open requests of a viewer and their positions in the queue
+/// (starting at 0).
+pub async fn get_open_by(
+    broadcaster_id: &str,
+    requested_by: &str,
+    pool: &PgPool,
+) -> AnyResult<Vec<(SongRequest, usize)>> {
+    Ok(SongRequest::get_open(broadcaster_id, pool)
+        .await?
+        .into_iter()
+        .enumerate()
+        .filter(|(_, r)| r.requested_by == requested_by)
+        .map(|(pos, r)| (r, pos))
+        .collect())
+}
+
+/// Removes a viewer's own request (see [remove]).
+/// Without an id, their latest request is removed.
+pub async fn remove_own(
+    broadcaster_id: &str,
+    requested_by: &str,
+    id: Option<i32>,
+    pool: &PgPool,
+) -> AnyResult<(SongRequest, bool)> {
+    let (request, _) = get_open_by(broadcaster_id, requested_by, pool)
+        .await?
+        .into_iter()
+        .filter(|(r, _)| id.map_or(true, |id| r.id == id))
+        .last()
+        .ok_or_else(|| {
+            user_error(match id {
+                Some(_) => text!("spotify.queue.not_found"),
+                None => text!("spotify.queue.none_by_you"),
+            })
+        })?;
+
+    remove(broadcaster_id, request.id, requested_by, pool).await
+}
+
+/// Redemptions of song requests stay unfulfilled until the request is
+/// settled: they're fulfilled once the track is played and refunded if it
+/// never plays. Returns `true` if the redemption was updated.
+async fn settle(
+    request: &SongRequest,
+    status: CustomRewardRedemptionStatus,
+    pool: &PgPool,
+) -> bool {
+    let user = match User::get_by_id(&request.user_id, pool).await {
+        Ok(user) => user,
+        Err(e) => {
+            log::warn!("Could not get user to settle song request: {}", e);
+            return false;
+        }
+    };
+    match update_reward_redemption(
+        &request.user_id,
+        &request.reward_id,
+        &request.redemption_id,
+        status,
+        &user.into(),
+    )
+    .await
+    {
+        Ok(_) => true,
+        Err(e) => {
+            log::warn!("Could not settle song request: {}", e);
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn track() -> TrackObject {
+        serde_json::from_value(json!({
+            "id": "4uLU6hMCjMI75M1A2tKUQC",
+            "name": "Never Gonna Give You Up",
+            "artists": [{ "id": "0gxyHStUsqpMadRV0Di1Qt", "name": "Rick Astley" }],
+            "uri": "spotify:track:4uLU6hMCjMI75M1A2tKUQC",
+            "duration_ms": 213573,
+            "explicit": false,
+            "external_urls": {
+                "spotify": "https://open.spotify.com/track/4uLU6hMCjMI75M1A2tKUQC",
+            },
+        }))
+        .unwrap()
+    }
+
+    fn blocked(entries: &[&str]) -> bool {
+        let blocklist = entries.iter().map(|e| e.to_string()).collect_vec();
+        is_blocked(&blocklist, &track())
+    }
+
+    #[test]
+    fn blocks_by_name_id_or_uri() {
+        assert!(blocked(&["never gonna give you up"]));
+        assert!(blocked(&[" RICK ASTLEY "]));
+        assert!(blocked(&["4uLU6hMCjMI75M1A2tKUQC"]));
+        assert!(blocked(&["0gxyHStUsqpMadRV0Di1Qt"]));
+        assert!(blocked(&["other", "spotify:track:4uLU6hMCjMI75M1A2tKUQC"]));
+    }
+
+    #[test]
+    fn ignores_partial_and_empty_entries() {
+        assert!(!blocked(&[]));
+        assert!(!blocked(&["", "  "]));
+        assert!(!blocked(&["Rick"]));
+        assert!(!blocked(&["4ulu6hmcjmi75m1a2tkuqc"]));
+    }
+}
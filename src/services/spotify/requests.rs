@@ -1,7 +1,8 @@
 use crate::services::spotify::{
     auth::get_redirect_url,
     responses::{
-        AccessTokenResponse, PlayerResponse, RefreshTokenResponse,
+        AccessTokenResponse, ArtistsResponse, PlayerResponse,
+        PlaylistItemsResponse, QueueResponse, RefreshTokenResponse,
         SearchResponse, TrackObject,
     },
 };
@@ -53,9 +54,23 @@ struct SearchQuery<'a> {
     r#type: &'a str,
 }
 
+#[derive(Serialize)]
+struct IdsQuery {
+    ids: String,
+}
+
+#[derive(Serialize)]
+struct PlaylistItemsQuery<'a> {
+    fields: &'a str,
+    limit: u32,
+}
+
+/// Playlists are read up to this many pages (100 tracks each).
+const MAX_PLAYLIST_PAGES: usize = 50;
+
 pub async fn get_token(code: &str) -> AnyResult<AccessTokenResponse> {
     Ok(SPOTIFY_CLIENT
-        .post("https://accounts.spotify.com/api/token")
+        .post(format!("{}/api/token", CONFIG.urls.spotify_accounts))
         .form(&TokenRequest {
             grant_type: "authorization_code",
             code,
@@ -79,7 +94,7 @@ pub async fn refresh_token(
     refresh_token: &str,
 ) -> AnyResult<RefreshTokenResponse> {
     Ok(SPOTIFY_CLIENT
-        .post("https://accounts.spotify.com/api/token")
+        .post(format!("{}/api/token", CONFIG.urls.spotify_accounts))
         .form(&RefreshRequest {
             grant_type: "refresh_token",
             refresh_token,
@@ -101,7 +116,7 @@ pub async fn refresh_token(
 
 pub async fn skip_next(auth_token: &str) -> AnyResult<()> {
     post204_or_200_because_the_docs_are_wrong(
-        "https://api.spotify.com/v1/me/player/next",
+        format!("{}/me/player/next", CONFIG.urls.spotify_api),
         auth_token,
     )
     .await?;
@@ -111,7 +126,8 @@ pub async fn skip_next(auth_token: &str) -> AnyResult<()> {
 pub async fn queue_item(uri: &str, auth_token: &str) -> AnyResult<()> {
     post204_or_200_because_the_docs_are_wrong(
         format!(
-            "https://api.spotify.com/v1/me/player/queue?{}",
+            "{}/me/player/queue?{}",
+            CONFIG.urls.spotify_api,
             serde_qs::to_string(&QueueTrack { uri })
                 .unwrap_or_else(|_| String::new())
         ),
@@ -123,7 +139,7 @@ pub async fn queue_item(uri: &str, auth_token: &str) -> AnyResult<()> {
 
 pub async fn play_track(uri: &str, auth_token: &str) -> AnyResult<()> {
     put204(
-        "https://api.spotify.com/v1/me/player/play",
+        format!("{}/me/player/play", CONFIG.urls.spotify_api),
         &PlayTracks { uris: &[uri] },
         auth_token,
     )
@@ -133,7 +149,7 @@ pub async fn play_track(uri: &str, auth_token: &str) -> AnyResult<()> {
 
 pub async fn get_player(auth_token: &str) -> AnyResult<PlayerResponse> {
     maybe_get(
-        "https://api.spotify.com/v1/me/player/currently-playing",
+        format!("{}/me/player/currently-playing", CONFIG.urls.spotify_api),
         auth_token,
     )
     .await
@@ -145,6 +161,14 @@ pub async fn get_player(auth_token: &str) -> AnyResult<PlayerResponse> {
     })
 }
 
+pub async fn get_queue(auth_token: &str) -> AnyResult<QueueResponse> {
+    get(
+        format!("{}/me/player/queue", CONFIG.urls.spotify_api),
+        auth_token,
+    )
+    .await
+}
+
 const FRAGMENT: &AsciiSet =
     &CONTROLS.add(b' ').add(b'"').add(b'<').add(b'>').add(b'`');
 pub async fn get_track(
@@ -153,7 +177,8 @@ pub async fn get_track(
 ) -> AnyResult<TrackObject> {
     get(
         format!(
-            "https://api.spotify.com/v1/tracks/{}",
+            "{}/tracks/{}",
+            CONFIG.urls.spotify_api,
             percent_encoding::utf8_percent_encode(track_id, FRAGMENT)
         ),
         auth_token,
@@ -167,7 +192,8 @@ pub async fn search_track(
 ) -> AnyResult<SearchResponse> {
     get(
         format!(
-            "https://api.spotify.com/v1/search?{}",
+            "{}/search?{}",
+            CONFIG.urls.spotify_api,
             serde_qs::to_string(&SearchQuery { q, r#type: "track" })?
         ),
         auth_token,
@@ -175,6 +201,54 @@ pub async fn search_track(
     .await
 }
 
+/// Gets up to 50 artists.
+pub async fn get_artists(
+    artist_ids: &[&str],
+    auth_token: &str,
+) -> AnyResult<ArtistsResponse> {
+    get(
+        format!(
+            "{}/artists?{}",
+            CONFIG.urls.spotify_api,
+            serde_qs::to_string(&IdsQuery {
+                ids: artist_ids.join(",")
+            })?
+        ),
+        auth_token,
+    )
+    .await
+}
+
+/// Gets the URIs of all tracks and episodes in a playlist.
+pub async fn get_playlist_uris(
+    playlist_id: &str,
+    auth_token: &str,
+) -> AnyResult<Vec<String>> {
+    let mut url = format!(
+        "{}/playlists/{}/tracks?{}",
+        CONFIG.urls.spotify_api,
+        percent_encoding::utf8_percent_encode(playlist_id, FRAGMENT),
+        serde_qs::to_string(&PlaylistItemsQuery {
+            fields: "next,items(track(uri))",
+            limit: 100
+        })?
+    );
+    let mut uris = Vec::new();
+    for _ in 0..MAX_PLAYLIST_PAGES {
+        let page: PlaylistItemsResponse = get(url, auth_token).await?;
+        uris.extend(
+            page.items
+                .into_iter()
+                .filter_map(|item| item.track.map(|track| track.uri)),
+        );
+        match page.next {
+            Some(next) => url = next,
+            None => break,
+        }
+    }
+    Ok(uris)
+}
+
 async fn put204<U: IntoUrl, T: Serialize>(
     url: U,
     body: &T,
@@ -47,15 +47,37 @@ impl PlayingItem {
             PlayingItem::Track(track) => &track.external_urls.spotify,
         }
     }
+
+    pub fn uri(&self) -> &str {
+        match self {
+            PlayingItem::Episode(ep) => &ep.uri,
+            PlayingItem::Track(track) => &track.uri,
+        }
+    }
+}
+
+#[derive(Deserialize, Debug)]
+#[non_exhaustive]
+pub struct QueueResponse {
+    pub currently_playing: Option<PlayingItem>,
+    pub queue: Vec<PlayingItem>,
 }
 
 #[derive(Deserialize, Debug)]
 #[non_exhaustive]
 pub struct TrackObject {
+    /// `None` for local tracks
+    pub id: Option<String>,
     pub name: String,
     pub artists: Vec<ArtistObject>,
     pub uri: String,
+    pub duration_ms: u32,
     pub explicit: bool,
+    /// 0 to 100, 0 for local tracks
+    #[serde(default)]
+    pub popularity: u32,
+    #[serde(default)]
+    pub album: Option<SimplifiedAlbumObject>,
     pub external_urls: ExternalUrls,
 }
 
@@ -73,9 +95,38 @@ impl std::fmt::Display for TrackObject {
 #[derive(Deserialize, Debug)]
 #[non_exhaustive]
 pub struct ArtistObject {
+    /// `None` for local tracks
+    pub id: Option<String>,
     pub name: String,
 }
 
+#[derive(Deserialize, Debug)]
+#[non_exhaustive]
+pub struct FullArtistObject {
+    pub id: String,
+    pub genres: Vec<String>,
+}
+
+#[derive(Deserialize, Debug)]
+#[non_exhaustive]
+pub struct ArtistsResponse {
+    /// `None` for unknown ids
+    pub artists: Vec<Option<FullArtistObject>>,
+}
+
+#[derive(Deserialize, Debug)]
+#[non_exhaustive]
+pub struct SimplifiedAlbumObject {
+    pub images: Vec<ImageObject>,
+}
+
+/// Images are ordered by size, the widest first.
+#[derive(Deserialize, Debug)]
+#[non_exhaustive]
+pub struct ImageObject {
+    pub url: String,
+}
+
 #[derive(Deserialize, Debug)]
 #[non_exhaustive]
 pub struct ExternalUrls {
@@ -86,6 +137,9 @@ pub struct ExternalUrls {
 #[non_exhaustive]
 pub struct EpisodeObject {
     pub name: String,
+    pub uri: String,
+    #[serde(default)]
+    pub images: Vec<ImageObject>,
     pub show: SimplifiedShowObject,
     pub external_urls: ExternalUrls,
 }
@@ -107,3 +161,25 @@ pub struct SearchResponse {
 pub struct PagingObject<T> {
     pub items: Vec<T>,
 }
+
+#[derive(Deserialize)]
+#[non_exhaustive]
+pub struct PlaylistItemsResponse {
+    pub items: Vec<PlaylistItemObject>,
+    /// URL of the next page
+    pub next: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[non_exhaustive]
+pub struct PlaylistItemObject {
+    /// `None` for removed tracks
+    pub track: Option<PlaylistTrackObject>,
+}
+
+/// Only the URI is requested, this is either a track or an episode.
+#[derive(Deserialize)]
+#[non_exhaustive]
+pub struct PlaylistTrackObject {
+    pub uri: String,
+}
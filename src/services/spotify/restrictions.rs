@@ -0,0 +1,296 @@
+use crate::{
+    services::{
+        i18n::Text,
+        spotify::{
+            requests, responses::TrackObject, rewards::extract_spotify_id,
+        },
+    },
+    text,
+};
+use anyhow::{Error as AnyError, Result as AnyResult};
+use itertools::Itertools;
+use models::reward::SpotifyPlayOptions;
+use std::collections::{HashMap, HashSet};
+
+/// Checks tracks against the restrictions of a reward.
+/// Playlists and genres are loaded once for all candidates of a request.
+pub struct TrackFilter<'a> {
+    options: &'a SpotifyPlayOptions,
+    /// URIs of all tracks in the allowed playlists
+    playlist_uris: Option<HashSet<String>>,
+    /// Lowercase genres by artist id
+    artist_genres: HashMap<String, Vec<String>>,
+}
+
+impl<'a> TrackFilter<'a> {
+    pub async fn load(
+        options: &'a SpotifyPlayOptions,
+        candidates: &[TrackObject],
+        token: &str,
+    ) -> AnyResult<Self> {
+        let playlist_uris = if options.playlists.is_empty() {
+            None
+        } else {
+            Some(load_playlists(&options.playlists, token).await?)
+        };
+        let artist_genres = if options.genres.is_empty() {
+            HashMap::new()
+        } else {
+            load_genres(candidates, token).await?
+        };
+
+        Ok(Self {
+            options,
+            playlist_uris,
+            artist_genres,
+        })
+    }
+
+    /// Returns the reason why a track can't be requested.
+    pub fn check(&self, track: &TrackObject) -> Result<(), Text> {
+        let options = self.options;
+        if track.explicit && !options.allow_explicit {
+            return Err(text!("spotify.explicit"));
+        }
+        if let Some(max) = options.max_duration {
+            if track.duration_ms / 1000 > max {
+                return Err(text!(
+                    "spotify.too_long",
+                    max = format_duration(max)
+                ));
+            }
+        }
+        if let Some(min) = options.min_popularity {
+            if track.popularity < min {
+                return Err(text!("spotify.not_popular"));
+            }
+        }
+        if let Some(uris) = &self.playlist_uris {
+            if !uris.contains(&track.uri) {
+                return Err(text!("spotify.not_in_playlist"));
+            }
+        }
+        if !options.genres.is_empty() && !self.has_genre(track) {
+            return Err(text!(
+                "spotify.wrong_genre",
+                genres = options.genres.join(", ")
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// A genre matches if it's part of an artist's genre (e.g. "rock"
+    /// matches "indie rock").
+    fn has_genre(&self, track: &TrackObject) -> bool {
+        let wanted = self
+            .options
+            .genres
+            .iter()
+            .map(|g| g.trim().to_lowercase())
+            .filter(|g| !g.is_empty())
+            .collect_vec();
+        track
+            .artists
+            .iter()
+            .filter_map(|a| a.id.as_ref())
+            .filter_map(|id| self.artist_genres.get(id))
+            .flatten()
+            .any(|genre| wanted.iter().any(|w| genre.contains(w.as_str())))
+    }
+}
+
+async fn load_playlists(
+    playlists: &[String],
+    token: &str,
+) -> AnyResult<HashSet<String>> {
+    let mut uris = HashSet::new();
+    for playlist in playlists {
+        let id = extract_spotify_id(playlist).ok_or_else(|| {
+            AnyError::msg(format!("Invalid playlist: {}", playlist))
+        })?;
+        uris.extend(requests::get_playlist_uris(id, token).await.map_err(
+            |e| {
+                log::warn!("Could not get playlist {}: {}", id, e);
+                AnyError::msg(format!("Could not get playlist {}", id))
+            },
+        )?);
+    }
+    Ok(uris)
+}
+
+async fn load_genres(
+    candidates: &[TrackObject],
+    token: &str,
+) -> AnyResult<HashMap<String, Vec<String>>> {
+    let ids = candidates
+        .iter()
+        .flat_map(|t| &t.artists)
+        .filter_map(|a| a.id.as_deref())
+        .unique()
+        .collect_vec();
+
+    let mut genres = HashMap::new();
+    for chunk in ids.chunks(50) {
+        let artists = requests::get_artists(chunk, token).await?.artists;
+        genres.extend(artists.into_iter().flatten().map(|a| {
+            let lowercase =
+                a.genres.iter().map(|g| g.to_lowercase()).collect_vec();
+            (a.id, lowercase)
+        }));
+    }
+    Ok(genres)
+}
+
+fn format_duration(secs: u32) -> String {
+    format!("{}:{:02}", secs / 60, secs % 60)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn options() -> SpotifyPlayOptions {
+        SpotifyPlayOptions {
+            allow_explicit: true,
+            max_duration: None,
+            min_popularity: None,
+            playlists: Vec::new(),
+            genres: Vec::new(),
+        }
+    }
+
+    fn track(duration_ms: u32, explicit: bool, popularity: u32) -> TrackObject {
+        serde_json::from_value(json!({
+            "id": "track",
+            "name": "Track",
+            "artists": [
+                { "id": "artist-1", "name": "Artist 1" },
+                { "id": null, "name": "Local Artist" },
+            ],
+            "uri": "spotify:track:track",
+            "duration_ms": duration_ms,
+            "explicit": explicit,
+            "popularity": popularity,
+            "external_urls": { "spotify": "https://open.spotify.com/track/track" },
+        }))
+        .unwrap()
+    }
+
+    fn filter(options: &SpotifyPlayOptions) -> TrackFilter<'_> {
+        TrackFilter {
+            options,
+            playlist_uris: None,
+            artist_genres: HashMap::from([(
+                "artist-1".to_owned(),
+                vec!["indie rock".to_owned(), "shoegaze".to_owned()],
+            )]),
+        }
+    }
+
+    fn rejection(
+        filter: &TrackFilter,
+        track: &TrackObject,
+    ) -> Option<&'static str> {
+        match filter.check(track) {
+            Ok(()) => None,
+            Err(Text::Message { key, .. }) => Some(key),
+            Err(Text::Raw(text)) => panic!("untranslated rejection: {text}"),
+        }
+    }
+
+    #[test]
+    fn unrestricted_tracks_pass() {
+        let options = options();
+        assert_eq!(
+            rejection(&filter(&options), &track(600_000, true, 0)),
+            None
+        );
+    }
+
+    #[test]
+    fn explicit_tracks() {
+        let options = SpotifyPlayOptions {
+            allow_explicit: false,
+            ..options()
+        };
+        let filter = filter(&options);
+        assert_eq!(
+            rejection(&filter, &track(1000, true, 50)),
+            Some("spotify.explicit")
+        );
+        assert_eq!(rejection(&filter, &track(1000, false, 50)), None);
+    }
+
+    #[test]
+    fn duration_and_popularity() {
+        let options = SpotifyPlayOptions {
+            max_duration: Some(180),
+            min_popularity: Some(20),
+            ..options()
+        };
+        let filter = filter(&options);
+        // partial seconds are ignored
+        assert_eq!(rejection(&filter, &track(180_999, false, 20)), None);
+        assert_eq!(
+            rejection(&filter, &track(181_000, false, 20)),
+            Some("spotify.too_long")
+        );
+        assert_eq!(
+            rejection(&filter, &track(1000, false, 19)),
+            Some("spotify.not_popular")
+        );
+    }
+
+    #[test]
+    fn playlists() {
+        let options = options();
+        let mut filter = filter(&options);
+        filter.playlist_uris =
+            Some(HashSet::from(["spotify:track:other".to_owned()]));
+        assert_eq!(
+            rejection(&filter, &track(1000, false, 0)),
+            Some("spotify.not_in_playlist")
+        );
+        filter
+            .playlist_uris
+            .as_mut()
+            .unwrap()
+            .insert("spotify:track:track".to_owned());
+        assert_eq!(rejection(&filter, &track(1000, false, 0)), None);
+    }
+
+    #[test]
+    fn genres_match_partially_and_ignore_case() {
+        let genres = |genres: &[&str]| SpotifyPlayOptions {
+            genres: genres.iter().map(|g| g.to_string()).collect(),
+            ..options()
+        };
+        let track = track(1000, false, 0);
+
+        for matching in [&["Rock"][..], &[" shoegaze "], &["metal", "indie"]] {
+            let options = genres(matching);
+            assert_eq!(
+                rejection(&filter(&options), &track),
+                None,
+                "{matching:?}"
+            );
+        }
+        for other in [&["metal"][..], &["", " "]] {
+            let options = genres(other);
+            assert_eq!(
+                rejection(&filter(&options), &track),
+                Some("spotify.wrong_genre"),
+                "{other:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn durations_are_formatted_as_minutes() {
+        assert_eq!(format_duration(59), "0:59");
+        assert_eq!(format_duration(180), "3:00");
+        assert_eq!(format_duration(3725), "62:05");
+    }
+}
@@ -1,11 +1,17 @@
-use crate::services::{
-    spotify::{
-        requests,
-        responses::{PlayerResponse, TrackObject},
+use crate::{
+    services::{
+        i18n::Text,
+        rewards::error::user_error,
+        spotify::{
+            requests,
+            responses::{PlayerResponse, TrackObject},
+            restrictions::TrackFilter,
+        },
+        twitch::live,
     },
-    twitch::requests::is_user_live,
+    text,
 };
-use anyhow::{bail, Error as AnyError, Result as AnyResult};
+use anyhow::{Error as AnyError, Result as AnyResult};
 use lazy_static::lazy_static;
 use models::{reward::SpotifyPlayOptions, spotify::SpotifyData, user::User};
 use regex::Regex;
@@ -18,7 +24,7 @@ pub async fn get_spotify_token(
 ) -> AnyResult<String> {
     SpotifyData::get_by_id(user_id, pool)
         .await?
-        .ok_or_else(|| AnyError::msg("No spotify connection"))
+        .ok_or_else(|| AnyError::new(text!("spotify.not_connected")))
         .map(|s| s.access_token)
 }
 
@@ -41,25 +47,6 @@ pub async fn skip_track(
         .unwrap_or_else(|| "?".to_string()))
 }
 
-pub async fn queue_track(
-    user_id: &str,
-    track: TrackObject,
-    pool: &PgPool,
-) -> AnyResult<String> {
-    let token = get_token_and_verify(user_id, false, pool).await?;
-
-    get_playing_player(&token).await?;
-
-    requests::queue_item(&track.uri, &token)
-        .await
-        .map_err(|e| {
-            log::warn!("Could not queue: {}", e);
-            AnyError::msg(format!("Couldn't queue - {}", e))
-        })?;
-
-    Ok(track.to_string())
-}
-
 pub async fn play_track(
     user_id: &str,
     track: TrackObject,
@@ -79,6 +66,8 @@ pub async fn play_track(
     Ok(track.to_string())
 }
 
+/// Finds the requested track. Search results are checked in order, if none
+/// of them can be requested, the reason for the first one is returned.
 pub async fn get_track_uri_from_input(
     input: &str,
     broadcaster_id: &str,
@@ -86,29 +75,37 @@ pub async fn get_track_uri_from_input(
     pool: &PgPool,
 ) -> AnyResult<TrackObject> {
     let token = get_spotify_token(broadcaster_id, pool).await?;
-    if let Some(id) = extract_spotify_id(input) {
-        let track = requests::get_track(id, &token)
+    let candidates = if let Some(id) = extract_spotify_id(input) {
+        vec![requests::get_track(id, &token)
             .await
-            .map_err(|_| AnyError::msg("Could not find your track"))?;
-
-        if track.explicit && !options.allow_explicit {
-            return Err(AnyError::msg("Explicit tracks are disallowed!"));
-        }
-        Ok(track)
+            .map_err(|_| user_error(text!("spotify.track_not_found")))?]
     } else {
-        let tracks = requests::search_track(input, &token).await?;
-        tracks
+        requests::search_track(input, &token)
+            .await?
             .tracks
-            .and_then(|tracks| {
-                tracks
-                    .items
-                    .into_iter()
-                    .find(|track| options.allow_explicit || !track.explicit)
-            })
-            .ok_or_else(|| AnyError::msg("No track found"))
+            .map(|tracks| tracks.items)
+            .unwrap_or_default()
+    };
+    if candidates.is_empty() {
+        return Err(user_error(text!("spotify.no_track")));
+    }
+
+    let filter = TrackFilter::load(options, &candidates, &token).await?;
+    let mut refusal = None;
+    for track in candidates {
+        match filter.check(&track) {
+            Ok(()) => return Ok(track),
+            Err(reason) => {
+                refusal.get_or_insert(reason);
+            }
+        }
     }
+    Err(user_error(
+        refusal.unwrap_or_else(|| text!("spotify.no_track")),
+    ))
 }
-fn extract_spotify_id(str: &str) -> Option<&str> {
+
+pub fn extract_spotify_id(str: &str) -> Option<&str> {
     lazy_static! {
         static ref SPOTIFY_REGEX: Regex =
             Regex::new("\\b([A-Za-z0-9]{22,})\\b").expect("must compile");
@@ -124,34 +121,47 @@ pub async fn get_playing_player(token: &str) -> AnyResult<PlayerResponse> {
         e
     })?;
     if !player.is_playing {
-        return Err(AnyError::msg("There's no song playing"));
+        return Err(user_error(text!("spotify.nothing_playing")));
     }
     Ok(player)
 }
 
+/// Formats the currently playing track for chat.
+pub async fn format_current_track(
+    user_id: &str,
+    pool: &PgPool,
+) -> AnyResult<Text> {
+    let token = get_token_and_verify(user_id, true, pool).await?;
+    let player = requests::get_player(&token).await?;
+    Ok(match player.item.as_ref().filter(|_| player.is_playing) {
+        Some(item) => text!(
+            "spotify.current",
+            track = item.to_string(),
+            url = item.spotify_url()
+        ),
+        None => text!("spotify.nothing_playing"),
+    })
+}
+
 pub async fn get_token_and_verify(
     user_id: &str,
     is_command: bool,
     pool: &PgPool,
 ) -> AnyResult<String> {
-    // TODO: for now, every user has only_while_live set to true, so the token is always needed.
-    // In the future this might not be true anymore.
-    let (data, user) = futures::future::try_join(
-        SpotifyData::get_by_id(user_id, pool),
-        User::get_by_id(user_id, pool),
-    )
-    .await?;
-    let data = data.ok_or_else(|| AnyError::msg("No spotify connection"))?;
-    let user_token = user.into();
+    let data = SpotifyData::get_by_id(user_id, pool)
+        .await?
+        .ok_or_else(|| text!("spotify.not_connected"))?;
 
     if !data.allow_commands && is_command {
-        bail!("Spotify commands are not allowed");
+        return Err(user_error(text!("spotify.commands_disabled")));
     }
 
-    if data.only_while_live
-        && !is_user_live::<UserToken>(user_id, &user_token).await?
-    {
-        return Err(AnyError::msg("The broadcaster isn't live"));
+    if data.only_while_live {
+        let user_token: UserToken =
+            User::get_by_id(user_id, pool).await?.into();
+        if !live::is_live(user_id, &user_token).await? {
+            return Err(user_error(text!("spotify.offline")));
+        }
     }
 
     Ok(data.access_token)
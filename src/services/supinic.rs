@@ -28,7 +28,7 @@ lazy_static! {
 
 pub async fn update_activity() -> AnyResult<()> {
     SUPINIC_CLIENT
-        .put("https://supinic.com/api/bot-program/bot/active")
+        .put(format!("{}/bot-program/bot/active", CONFIG.urls.supinic))
         .send()
         .await
         .map_err(AnyError::from)
@@ -0,0 +1,104 @@
+use anyhow::{bail, Result as AnyResult};
+use lazy_static::lazy_static;
+use regex::{Captures, Regex};
+
+lazy_static! {
+    static ref VARIABLE_REGEX: Regex =
+        Regex::new("\\{([\\w.]+)\\}").expect("must compile");
+}
+
+/// Returns the names of all variables (`{name}`) used in the template.
+pub fn variables(template: &str) -> impl Iterator<Item = &str> {
+    VARIABLE_REGEX
+        .captures_iter(template)
+        .filter_map(|c| c.get(1).map(|m| m.as_str()))
+}
+
+pub fn verify_template(
+    template: &str,
+    allowed: &[&str],
+    max_length: usize,
+) -> AnyResult<()> {
+    if template.trim().is_empty() {
+        bail!("The template is empty");
+    }
+    if template.chars().count() > max_length {
+        bail!("The template can't be longer than {max_length} characters");
+    }
+    if let Some(var) = variables(template).find(|v| !allowed.contains(v)) {
+        bail!(
+            "Unknown variable {{{var}}} (available: {})",
+            allowed
+                .iter()
+                .map(|v| format!("{{{v}}}"))
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+    }
+
+    Ok(())
+}
+
+/// Replaces all variables in the template.
+/// Variables without a value are left as they are.
+pub fn render(
+    template: &str,
+    mut value: impl FnMut(&str) -> Option<String>,
+) -> String {
+    VARIABLE_REGEX
+        .replace_all(template, |c: &Captures| {
+            value(&c[1]).unwrap_or_else(|| c[0].to_string())
+        })
+        .into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VARS: &[&str] = &["user", "emote"];
+
+    #[test]
+    fn finds_variables() {
+        assert_eq!(
+            variables("{user} added {emote} ({user}) {not a var}")
+                .collect::<Vec<_>>(),
+            ["user", "emote", "user"]
+        );
+        assert_eq!(variables("{a.b}").collect::<Vec<_>>(), ["a.b"]);
+    }
+
+    #[test]
+    fn accepts_known_variables() {
+        assert!(verify_template("{user} added {emote}", VARS, 50).is_ok());
+        assert!(verify_template("no variables", VARS, 50).is_ok());
+        // unknown braces aren't variables
+        assert!(verify_template("{ user }", VARS, 50).is_ok());
+    }
+
+    #[test]
+    fn rejects_unknown_variables() {
+        let err = verify_template("{user} got {track}", VARS, 50).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Unknown variable {track} (available: {user}, {emote})"
+        );
+    }
+
+    #[test]
+    fn rejects_empty_and_long_templates() {
+        assert!(verify_template("", VARS, 50).is_err());
+        assert!(verify_template("  \n", VARS, 50).is_err());
+        // the length is counted in characters
+        assert!(verify_template(&"ä".repeat(5), VARS, 5).is_ok());
+        assert!(verify_template(&"a".repeat(6), VARS, 5).is_err());
+    }
+
+    #[test]
+    fn renders_known_variables() {
+        let rendered = render("{user} added {emote} {other}", |var| {
+            (var == "user" || var == "emote").then(|| var.to_uppercase())
+        });
+        assert_eq!(rendered, "USER added EMOTE {other}");
+    }
+}
@@ -16,7 +16,7 @@ use twitch_api::{
         },
         Response,
     },
-    twitch_oauth2::{AppAccessToken, UserToken},
+    twitch_oauth2::{AppAccessToken, TwitchToken, UserToken},
     types::{EventSubIdRef, IntoCow, RedemptionIdRef, RewardIdRef, UserIdRef},
 };
 
@@ -31,22 +31,23 @@ pub async fn delete_subscription<'a>(
     Ok(())
 }
 
+pub fn webhook_transport() -> Transport {
+    Transport::webhook(
+        format!("{}/api/v1/eventsub/reward", CONFIG.server.url),
+        CONFIG.twitch.eventsub.secret.to_string(),
+    )
+}
+
 pub async fn subscribe_to<T>(
-    token: &AppAccessToken,
+    token: &(impl TwitchToken + Send + Sync),
     subscription: T,
+    transport: Transport,
 ) -> HelixResult<CreateEventSubSubscription<T>>
 where
     T: twitch_api::eventsub::EventSubscription + Send,
 {
     RHelixClient::new()
-        .create_eventsub_subscription(
-            subscription,
-            Transport::webhook(
-                format!("{}/api/v1/eventsub/reward", CONFIG.server.url),
-                CONFIG.twitch.eventsub.secret.to_string(),
-            ),
-            token,
-        )
+        .create_eventsub_subscription(subscription, transport, token)
         .await
         .err_into()
 }
@@ -0,0 +1,90 @@
+use crate::services::twitch::{requests::is_user_live, HelixResult};
+use std::{
+    collections::HashMap,
+    sync::{PoisonError, RwLock},
+    time::{Duration, Instant},
+};
+use twitch_api::twitch_oauth2::UserToken;
+
+/// Entries are refreshed from Helix after this time in case an EventSub
+/// notification was missed.
+const MAX_AGE: Duration = Duration::from_secs(30 * 60);
+
+struct LiveStatus {
+    is_live: bool,
+    updated_at: Instant,
+}
+
+lazy_static::lazy_static! {
+    /// Live status by channel, updated by the
+    /// [LiveActor](crate::actors::live::LiveActor).
+    static ref LIVE_STATUS: RwLock<HashMap<String, LiveStatus>> =
+        RwLock::default();
+}
+
+pub fn set_live(user_id: &str, is_live: bool) {
+    LIVE_STATUS
+        .write()
+        .unwrap_or_else(PoisonError::into_inner)
+        .insert(
+            user_id.to_owned(),
+            LiveStatus {
+                is_live,
+                updated_at: Instant::now(),
+            },
+        );
+}
+
+/// The cached status if it's recent enough.
+fn cached_live(user_id: &str) -> Option<bool> {
+    LIVE_STATUS
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .get(user_id)
+        .filter(|status| status.updated_at.elapsed() < MAX_AGE)
+        .map(|status| status.is_live)
+}
+
+/// Checks if the channel is live. Only channels without a (recent) status
+/// are requested from Helix.
+pub async fn is_live(user_id: &str, token: &UserToken) -> HelixResult<bool> {
+    if let Some(is_live) = cached_live(user_id) {
+        return Ok(is_live);
+    }
+
+    let is_live = is_user_live::<UserToken>(user_id, token).await?;
+    set_live(user_id, is_live);
+    Ok(is_live)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn uses_the_latest_status() {
+        assert_eq!(cached_live("live-test-1"), None);
+        set_live("live-test-1", true);
+        assert_eq!(cached_live("live-test-1"), Some(true));
+        set_live("live-test-1", false);
+        assert_eq!(cached_live("live-test-1"), Some(false));
+    }
+
+    #[test]
+    fn old_statuses_are_ignored() {
+        let Some(updated_at) =
+            Instant::now().checked_sub(MAX_AGE + Duration::from_secs(1))
+        else {
+            // the clock started less than MAX_AGE ago
+            return;
+        };
+        LIVE_STATUS.write().unwrap().insert(
+            "live-test-2".to_owned(),
+            LiveStatus {
+                is_live: true,
+                updated_at,
+            },
+        );
+        assert_eq!(cached_live("live-test-2"), None);
+    }
+}
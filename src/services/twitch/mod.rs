@@ -12,6 +12,7 @@ use twitch_api::{
 
 pub mod errors;
 pub mod eventsub;
+pub mod live;
 pub mod requests;
 mod token;
 pub use token::*;
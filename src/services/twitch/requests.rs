@@ -19,12 +19,22 @@ use twitch_api::{
             CustomReward, DeleteCustomRewardRequest, GetCustomRewardRequest,
             UpdateCustomRewardBody, UpdateCustomRewardRequest,
         },
+        polls::{
+            CreatePollBody, CreatePollRequest, GetPollsRequest, NewPollChoice,
+        },
+        predictions::{
+            CreatePredictionBody, CreatePredictionRequest,
+            GetPredictionsRequest, NewPredictionOutcome,
+        },
         streams::{GetStreamsRequest, Stream},
         users::{GetUsersRequest, User},
         ClientRequestError, HelixRequestPostError, Response,
     },
     twitch_oauth2::{tokens::errors::ValidationError, TwitchToken, UserToken},
-    types::{IntoCow, RewardIdRef, UserId, UserIdRef, UserNameRef},
+    types::{
+        IntoCow, PollStatus, PredictionStatus, RewardIdRef, UserId, UserIdRef,
+        UserNameRef,
+    },
 };
 
 pub async fn create_reward<'a>(
@@ -270,6 +280,8 @@ pub async fn update_chat_settings<'a>(
     moderator_id: impl IntoCow<'a, UserIdRef> + 'a,
     timed_mode: timed_mode::Mode,
     enable: bool,
+    // wait time (seconds) for slow mode or follow age (minutes) for followers
+    setting: Option<u64>,
     token: &impl TwitchToken,
 ) -> anyhow::Result<()> {
     HELIX_CLIENT
@@ -286,12 +298,23 @@ pub async fn update_chat_settings<'a>(
                 .subscriber_mode(
                     (timed_mode == timed_mode::Mode::Subonly).then_some(enable),
                 )
-                .follower_mode(None)
-                .follower_mode_duration(None)
-                .slow_mode(None)
+                .follower_mode(
+                    (timed_mode == timed_mode::Mode::Followers)
+                        .then_some(enable),
+                )
+                .follower_mode_duration(setting.filter(|_| {
+                    enable && timed_mode == timed_mode::Mode::Followers
+                }))
+                .slow_mode(
+                    (timed_mode == timed_mode::Mode::Slow).then_some(enable),
+                )
                 .non_moderator_chat_delay(None)
                 .non_moderator_chat_delay_duration(None)
-                .slow_mode_wait_time(None)
+                .slow_mode_wait_time(
+                    setting.filter(|_| {
+                        enable && timed_mode == timed_mode::Mode::Slow
+                    }),
+                )
                 .unique_chat_mode(None)
                 .build(),
             token,
@@ -327,3 +350,93 @@ pub async fn send_chat_message(
         ))
     }
 }
+
+pub async fn has_active_poll(
+    broadcaster_id: &str,
+    token: &UserToken,
+) -> anyhow::Result<bool> {
+    let res = HELIX_CLIENT
+        .req_get(
+            GetPollsRequest::broadcaster_id(
+                CONFIG.debug_overrides.twitch(broadcaster_id),
+            ),
+            token,
+        )
+        .await?;
+    Ok(res
+        .data
+        .iter()
+        .any(|p| matches!(p.status, PollStatus::Active)))
+}
+
+pub async fn create_poll(
+    broadcaster_id: &str,
+    title: &str,
+    choices: &[&str],
+    duration: u64,
+    token: &UserToken,
+) -> anyhow::Result<()> {
+    let choices = choices
+        .iter()
+        .map(|c| NewPollChoice::new(*c))
+        .collect::<Vec<_>>();
+    HELIX_CLIENT
+        .req_post(
+            CreatePollRequest::new(),
+            CreatePollBody::new(
+                CONFIG.debug_overrides.twitch(broadcaster_id),
+                title,
+                duration as i64,
+                choices.as_slice(),
+            ),
+            token,
+        )
+        .await?;
+    Ok(())
+}
+
+pub async fn has_active_prediction(
+    broadcaster_id: &str,
+    token: &UserToken,
+) -> anyhow::Result<bool> {
+    let res = HELIX_CLIENT
+        .req_get(
+            GetPredictionsRequest::broadcaster_id(
+                CONFIG.debug_overrides.twitch(broadcaster_id),
+            ),
+            token,
+        )
+        .await?;
+    Ok(res.data.iter().any(|p| {
+        matches!(
+            p.status,
+            PredictionStatus::Active | PredictionStatus::Locked
+        )
+    }))
+}
+
+pub async fn create_prediction(
+    broadcaster_id: &str,
+    title: &str,
+    outcomes: &[&str],
+    prediction_window: u64,
+    token: &UserToken,
+) -> anyhow::Result<()> {
+    let outcomes = outcomes
+        .iter()
+        .map(|o| NewPredictionOutcome::new(*o))
+        .collect::<Vec<_>>();
+    HELIX_CLIENT
+        .req_post(
+            CreatePredictionRequest::new(),
+            CreatePredictionBody::new(
+                CONFIG.debug_overrides.twitch(broadcaster_id),
+                title,
+                outcomes.as_slice(),
+                prediction_window as i64,
+            ),
+            token,
+        )
+        .await?;
+    Ok(())
+}
@@ -1,10 +1,19 @@
 import {
+  BanRule,
   Connections,
+  CreateBanRule,
+  EmoteEventFilter,
+  EmoteEventsPage,
   InputReward,
   InternalCustomReward,
   ListSwapEmotesResponse,
-  LogEntry,
+  LocaleSettings,
+  LogFilter,
+  LogsPage,
+  PendingApproval,
+  ReplyTemplates,
   Reward,
+  SongRequest,
   SpotifySettings,
   TwitchReward,
   TwitchUser,
@@ -65,6 +74,14 @@ class HttpClient extends BaseClient {
     return this.patch(settings, 'connections', 'spotify');
   }
 
+  resetSpotifyOverlay() {
+    return this.post<{ token: string }>(undefined, 'connections', 'spotify', 'overlay');
+  }
+
+  removeSpotifyOverlay() {
+    return this.delete('connections', 'spotify', 'overlay');
+  }
+
   removeConnection(name: 'spotify') {
     return this.delete('connections', name);
   }
@@ -83,25 +100,20 @@ class HttpClient extends BaseClient {
         el.data = internal.data;
         el.live_delay = internal.live_delay || '';
         el.auto_accept = internal.auto_accept;
+        el.refund_on_error = internal.refund_on_error;
+        el.reply_templates = internal.reply_templates;
       }
     }
 
     return [...map.values()] as Reward[];
   }
 
-  async getLogs(id: string): Promise<LogEntry[]> {
-    const response = await this.get<{ date: string; content: string }[]>('logs', id);
-    const fmt = new Intl.DateTimeFormat(undefined, { dateStyle: 'medium', timeStyle: 'medium' });
-    return response
-      .map(({ date, content }) => ({ date: new Date(date), content }))
-      .sort(({ date: a }, { date: b }) => Number(b) - Number(a))
-      .map(({ date, content }) => {
-        try {
-          return { date: fmt.format(date), content };
-        } catch {
-          return { date: '?', content };
-        }
-      });
+  getLogs(id: string, filter: LogFilter, before?: number) {
+    return this.getWithQuery<LogsPage>(
+      { ...filter, types: filter.types?.join(','), before: before?.toString() },
+      'logs',
+      id,
+    );
   }
 
   addReward(broadcasterId: string, reward: InputReward) {
@@ -116,6 +128,68 @@ class HttpClient extends BaseClient {
     return this.delete('rewards', broadcasterId, reward.twitch.id);
   }
 
+  getReplyTemplates(broadcasterId: string) {
+    return this.get<ReplyTemplates>('rewards', broadcasterId, 'reply-templates');
+  }
+
+  updateReplyTemplates(broadcasterId: string, templates: ReplyTemplates) {
+    return this.put(templates, 'rewards', broadcasterId, 'reply-templates');
+  }
+
+  getLocale(broadcasterId: string) {
+    return this.get<LocaleSettings>('users', broadcasterId, 'locale');
+  }
+
+  updateLocale(broadcasterId: string, locale: string) {
+    return this.put({ locale }, 'users', broadcasterId, 'locale');
+  }
+
+  getPendingApprovals(broadcasterId: string) {
+    return this.get<PendingApproval[]>('rewards', broadcasterId, 'approvals');
+  }
+
+  resolvePendingApproval(broadcasterId: string, id: number, approve: boolean) {
+    return this.patch<PendingApproval>({ approve }, 'rewards', broadcasterId, 'approvals', id.toString());
+  }
+
+  getSongRequests(broadcasterId: string) {
+    return this.get<SongRequest[]>('rewards', broadcasterId, 'song-queue');
+  }
+
+  resolveSongRequest(broadcasterId: string, id: number, approve: boolean) {
+    return this.patch<SongRequest>({ approve }, 'rewards', broadcasterId, 'song-queue', id.toString());
+  }
+
+  removeSongRequest(broadcasterId: string, id: number) {
+    return this.delete('rewards', broadcasterId, 'song-queue', id.toString());
+  }
+
+  getEmoteEvents(broadcasterId: string, filter: EmoteEventFilter, before?: number) {
+    return this.getWithQuery<EmoteEventsPage>(
+      { ...filter, before: before?.toString() },
+      'logs',
+      broadcasterId,
+      'emotes',
+    );
+  }
+
+  async exportEmoteEvents(broadcasterId: string, filter: EmoteEventFilter, format: 'csv' | 'json'): Promise<string> {
+    const data = await this.getWithQuery<unknown>({ ...filter, format }, 'logs', broadcasterId, 'emotes', 'export');
+    return typeof data === 'string' ? data : JSON.stringify(data, null, 2);
+  }
+
+  getBanRules(broadcasterId: string) {
+    return this.get<BanRule[]>('rewards', broadcasterId, 'ban-rules');
+  }
+
+  createBanRule(broadcasterId: string, rule: CreateBanRule) {
+    return this.put<BanRule>(rule, 'rewards', broadcasterId, 'ban-rules');
+  }
+
+  deleteBanRule(broadcasterId: string, id: number) {
+    return this.delete('rewards', broadcasterId, 'ban-rules', id.toString());
+  }
+
   setDiscordUrl(broadcasterId: string, url: string) {
     return this.patch({ url }, 'logs', broadcasterId, 'discord');
   }
@@ -21,6 +21,23 @@ export class BaseClient {
     return this.baseRequest(buildUrl(segments), {});
   }
 
+  protected getWithQuery<T>(query: Record<string, string | undefined>, ...segments: string[]): Promise<T> {
+    const params = new URLSearchParams();
+    for (const [key, value] of Object.entries(query)) {
+      if (value) params.set(key, value);
+    }
+    const search = params.toString();
+    return this.baseRequest(search ? `${buildUrl(segments)}?${search}` : buildUrl(segments), {});
+  }
+
+  protected post<T>(data: AnyObject | undefined, ...segments: string[]): Promise<T> {
+    return this.baseRequest(buildUrl(segments), {
+      method: 'POST',
+      body: (data && JSON.stringify(data)) ?? null,
+      headers: { 'Content-Type': 'application/json' },
+    });
+  }
+
   protected put<T>(data: AnyObject | undefined, ...segments: string[]): Promise<T> {
     return this.baseRequest(buildUrl(segments), {
       method: 'PUT',
@@ -67,7 +84,7 @@ export class BaseClient {
   }
 }
 
-function makeApiUrl(path: string) {
+export function makeApiUrl(path: string) {
   return `${import.meta.env.MODE === 'development' ? (import.meta.env['VITE_API_BASE_URL'] ?? '') : ''}/api/v1/${path}`;
 }
 
@@ -1,4 +1,4 @@
-import { InputReward, Reward, RewardData } from './types';
+import { InputReward, RefundPolicy, Reward, RewardData } from './types';
 import { parseDuration } from '../utilities';
 import { defaultNewReward, StaticRewardData } from './rewards-data';
 
@@ -14,6 +14,9 @@ export interface VRewardModel {
 
   liveDelay: string;
   autoAccept: boolean;
+  refundOnError: RefundPolicy;
+  replySuccess: string;
+  replyError: string;
 
   action: RewardData;
 }
@@ -37,6 +40,9 @@ export function toVRewardModel(reward: Reward): VRewardModel {
 
     liveDelay: reward.live_delay ?? '',
     autoAccept: reward.auto_accept,
+    refundOnError: reward.refund_on_error,
+    replySuccess: reward.reply_templates?.success ?? '',
+    replyError: reward.reply_templates?.error ?? '',
   };
 }
 
@@ -54,6 +60,9 @@ export function assignToVRewardModel(reward: Reward, model: VRewardModel): void
 
   model.liveDelay = reward.live_delay ?? '';
   model.autoAccept = reward.auto_accept;
+  model.refundOnError = reward.refund_on_error;
+  model.replySuccess = reward.reply_templates?.success ?? '';
+  model.replyError = reward.reply_templates?.error ?? '';
 }
 
 export function toInputReward(vmodel: VRewardModel): InputReward {
@@ -81,6 +90,11 @@ export function toInputReward(vmodel: VRewardModel): InputReward {
     data: cloneRewardData(vmodel.action),
     live_delay: vmodel.liveDelay.trim() || undefined,
     auto_accept: vmodel.autoAccept,
+    refund_on_error: vmodel.refundOnError,
+    reply_templates: {
+      success: vmodel.replySuccess.trim() || null,
+      error: vmodel.replyError.trim() || null,
+    },
   };
 }
 
@@ -3,9 +3,14 @@ import {
   SlotRewardData,
   RewardDataMap,
   SpotifyPlayOptions,
+  SpotifyQueueOptions,
   SwapRewardData,
+  AnySwapRewardData,
   TimeoutRewardData,
   RemEmoteRewardData,
+  PollRewardData,
+  SlowModeRewardData,
+  FollowerOnlyRewardData,
 } from './types';
 
 interface StaticData<K extends keyof RewardDataMap> {
@@ -34,6 +39,18 @@ export const StaticRewardData: { [K in keyof RewardDataMap]: StaticData<K> } = {
     validOptions: SEValid,
     defaultOptions: '1s',
   },
+  SlowMode: {
+    display: 'Slow Mode',
+    inputRequired: false,
+    validOptions: slowModeValid,
+    defaultOptions: { duration: '5m', wait_time: '30s' },
+  },
+  FollowerOnly: {
+    display: 'Follower-only',
+    inputRequired: false,
+    validOptions: followerOnlyValid,
+    defaultOptions: { duration: '10m', follow_age: '10m' },
+  },
   BttvSwap: {
     display: 'Add/Swap Bttv Emote',
     inputRequired: true,
@@ -52,6 +69,12 @@ export const StaticRewardData: { [K in keyof RewardDataMap]: StaticData<K> } = {
     validOptions: emoteSwapValid,
     defaultOptions: { limit: null, allow_unlisted: true, reply: true },
   },
+  AnySwap: {
+    display: 'Add/Swap Any Emote',
+    inputRequired: true,
+    validOptions: anySwapValid,
+    defaultOptions: { limit: null, allow_unlisted: true, reply: true, bttv: true, ffz: true, seventv: true },
+  },
   BttvSlot: {
     display: 'Bttv Slots',
     inputRequired: true,
@@ -97,14 +120,26 @@ export const StaticRewardData: { [K in keyof RewardDataMap]: StaticData<K> } = {
     validOptions: spotifyPlayValid,
     defaultOptions: {
       allow_explicit: false,
+      max_duration: null,
+      min_popularity: null,
+      playlists: [],
+      genres: [],
     },
   },
   SpotifyQueue: {
     display: 'Queue Spotify Track',
     inputRequired: true,
-    validOptions: spotifyPlayValid,
+    validOptions: spotifyQueueValid,
     defaultOptions: {
       allow_explicit: false,
+      max_duration: null,
+      min_popularity: null,
+      playlists: [],
+      genres: [],
+      max_per_user: null,
+      repeat_cooldown: null,
+      blocklist: [],
+      require_approval: false,
     },
   },
   RemEmote: {
@@ -116,6 +151,24 @@ export const StaticRewardData: { [K in keyof RewardDataMap]: StaticData<K> } = {
       reply: true,
     },
   },
+  Poll: {
+    display: 'Poll',
+    inputRequired: true,
+    validOptions: pollValid,
+    defaultOptions: {
+      duration: '2m',
+      reply: true,
+    },
+  },
+  Prediction: {
+    display: 'Prediction',
+    inputRequired: true,
+    validOptions: pollValid,
+    defaultOptions: {
+      duration: '2m',
+      reply: true,
+    },
+  },
 };
 
 function timeoutValid(opts: unknown): boolean {
@@ -130,6 +183,22 @@ function SEValid(opts: unknown): boolean {
   return typeof opts === 'string';
 }
 
+function slowModeValid(opts: unknown): boolean {
+  if (typeof opts !== 'object' || opts === null) return false;
+  return (
+    typeof (opts as SlowModeRewardData).duration === 'string' &&
+    typeof (opts as SlowModeRewardData).wait_time === 'string'
+  );
+}
+
+function followerOnlyValid(opts: unknown): boolean {
+  if (typeof opts !== 'object' || opts === null) return false;
+  return (
+    typeof (opts as FollowerOnlyRewardData).duration === 'string' &&
+    typeof (opts as FollowerOnlyRewardData).follow_age === 'string'
+  );
+}
+
 function emoteSlotValid(opts: unknown): boolean {
   if (typeof opts !== 'object' || opts === null) return false;
   return typeof (opts as SlotRewardData).slots === 'number' && typeof (opts as SlotRewardData).expiration === 'string';
@@ -140,11 +209,21 @@ function emoteSwapValid(opts: unknown): boolean {
   return opts === null || typeof (opts as SwapRewardData).limit === 'number' || (opts as SwapRewardData).limit === null;
 }
 
+function anySwapValid(opts: unknown): boolean {
+  if (!emoteSwapValid(opts) || opts === null) return false;
+  const data = opts as AnySwapRewardData;
+  return typeof data.bttv === 'boolean' && typeof data.ffz === 'boolean' && typeof data.seventv === 'boolean';
+}
+
 function spotifyPlayValid(opts: unknown): boolean {
   if (typeof opts !== 'object' || opts === null) return false;
   return typeof (opts as SpotifyPlayOptions).allow_explicit === 'boolean';
 }
 
+function spotifyQueueValid(opts: unknown): boolean {
+  return spotifyPlayValid(opts) && Array.isArray((opts as SpotifyQueueOptions).blocklist);
+}
+
 function remEmoteValid(opts: unknown): boolean {
   if (typeof opts !== 'object' || opts === null) return false;
   return (
@@ -153,6 +232,11 @@ function remEmoteValid(opts: unknown): boolean {
   );
 }
 
+function pollValid(opts: unknown): boolean {
+  if (typeof opts !== 'object' || opts === null) return false;
+  return typeof (opts as PollRewardData).duration === 'string';
+}
+
 export const RewardTypes = Object.entries(StaticRewardData).map(([key, { display }]) => ({ value: key, display }));
 
 export function defaultNewReward(): VRewardModel {
@@ -171,5 +255,8 @@ export function defaultNewReward(): VRewardModel {
     liveDelay: '',
     imageUrl: null,
     autoAccept: true,
+    refundOnError: 'always',
+    replySuccess: '',
+    replyError: '',
   };
 }
@@ -90,16 +90,21 @@ export interface RewardDataMap {
   Timeout: TimeoutRewardData;
   SubOnly: string;
   EmoteOnly: string;
+  SlowMode: SlowModeRewardData;
+  FollowerOnly: FollowerOnlyRewardData;
   BttvSwap: SwapRewardData | null;
   FfzSwap: SwapRewardData | null;
   SevenTvSwap: SwapRewardData | null;
+  AnySwap: AnySwapRewardData;
   BttvSlot: SlotRewardData;
   FfzSlot: SlotRewardData;
   SevenTvSlot: SlotRewardData;
   SpotifySkip: null;
   SpotifyPlay: SpotifyPlayOptions;
-  SpotifyQueue: SpotifyPlayOptions;
+  SpotifyQueue: SpotifyQueueOptions;
   RemEmote: RemEmoteRewardData;
+  Poll: PollRewardData;
+  Prediction: PollRewardData;
 }
 
 export interface TimeoutRewardData {
@@ -107,21 +112,166 @@ export interface TimeoutRewardData {
   vip: boolean;
 }
 
+export interface SlowModeRewardData {
+  duration: string;
+  wait_time: string;
+}
+
+export interface FollowerOnlyRewardData {
+  duration: string;
+  follow_age: string;
+}
+
 export interface SlotRewardData {
   slots: number;
   expiration: string;
   allow_unlisted?: boolean;
+  search?: EmoteSearchMode | null;
   reply?: boolean;
+  require_approval?: boolean;
 }
 
+export type EmoteSearchMode = 'Exact' | 'MostUsed' | 'ErrorIfAmbiguous';
+
 export interface SwapRewardData {
   limit: number | null;
   allow_unlisted?: boolean;
+  max_per_redemption?: number | null;
+  search?: EmoteSearchMode | null;
+  reply?: boolean;
+  require_approval?: boolean;
+}
+
+export interface AnySwapRewardData extends SwapRewardData {
+  bttv: boolean;
+  ffz: boolean;
+  seventv: boolean;
+}
+
+export interface PollRewardData {
+  duration: string;
   reply?: boolean;
 }
 
 export interface SpotifyPlayOptions {
   allow_explicit: boolean;
+  /** in seconds */
+  max_duration: number | null;
+  /** 0 to 100 */
+  min_popularity: number | null;
+  /** ids or links */
+  playlists: string[];
+  genres: string[];
+}
+
+export interface SpotifyQueueOptions extends SpotifyPlayOptions {
+  max_per_user: number | null;
+  /** in hours */
+  repeat_cooldown: number | null;
+  blocklist: string[];
+  require_approval: boolean;
+}
+
+export type SongRequestStatus = 'pending' | 'queued' | 'sent' | 'skipping' | 'played' | 'removed' | 'rejected';
+
+export interface SongRequest {
+  id: number;
+  user_id: string;
+  reward_id: string;
+  redemption_id: string;
+  requested_by: string;
+  track_uri: string;
+  track_name: string;
+  artists: string;
+  duration_ms: number;
+  status: SongRequestStatus;
+  created_at: string;
+  sent_at: string | null;
+  resolved_by: string | null;
+}
+
+export type RefundPolicy = 'always' | 'never' | 'user_error_only';
+
+export interface PendingApproval {
+  id: number;
+  user_id: string;
+  reward_id: string;
+  redemption_id: string;
+  redeemed_by: string;
+  user_input: string;
+  created_at: string;
+  expires_at: string;
+}
+
+export type BanRuleKind = 'name' | 'owner' | 'tag' | 'zero_width' | 'nsfw' | 'animated' | 'static' | 'hash';
+
+export interface BanRule {
+  id: number;
+  channel_id: string;
+  platform: SlotPlatform | null;
+  kind: BanRuleKind;
+  value: string | null;
+  created_at: string;
+}
+
+export interface CreateBanRule {
+  platform: SlotPlatform | null;
+  kind: BanRuleKind;
+  value: string | null;
+  /** takes the owner or hash from this emote */
+  emote_id: string | null;
+}
+
+export type EmoteEventKind = 'added' | 'removed';
+export type EmoteEventReason =
+  | 'redeemed'
+  | 'swapped_out'
+  | 'expired'
+  | 'refreshed'
+  | 'ejected'
+  | 'injected'
+  | 'banned'
+  | 'removed'
+  | 'untracked';
+
+export interface EmoteEvent {
+  id: number;
+  channel_id: string;
+  platform: SlotPlatform;
+  kind: EmoteEventKind;
+  reason: EmoteEventReason;
+  emote_id: string;
+  emote_name: string;
+  actor: string | null;
+  reward_id: string | null;
+  command: string | null;
+  created_at: string;
+}
+
+export interface EmoteEventFilter {
+  platform?: SlotPlatform;
+  kind?: EmoteEventKind;
+  reason?: EmoteEventReason;
+  actor?: string;
+  emote?: string;
+  reward_id?: string;
+  since?: string;
+  until?: string;
+}
+
+export interface EmoteEventsPage {
+  events: EmoteEvent[];
+  next: number | null;
+}
+
+export interface LocaleSettings {
+  locale: string;
+  available: string[];
+}
+
+export interface ReplyTemplates {
+  success?: string | null;
+  error?: string | null;
 }
 
 export interface InternalCustomReward {
@@ -130,6 +280,8 @@ export interface InternalCustomReward {
   data: RewardData;
   live_delay?: string;
   auto_accept: boolean;
+  refund_on_error: RefundPolicy;
+  reply_templates: ReplyTemplates;
 }
 
 export type SlotPlatform = 'Bttv' | 'Ffz' | 'SevenTv';
@@ -144,6 +296,8 @@ export interface Reward {
   data: RewardData;
   live_delay?: string;
   auto_accept: boolean;
+  refund_on_error: RefundPolicy;
+  reply_templates: ReplyTemplates;
 }
 
 export interface InputReward {
@@ -151,18 +305,58 @@ export interface InputReward {
   data: RewardData;
   live_delay?: string | undefined;
   auto_accept: boolean;
+  refund_on_error: RefundPolicy;
+  reply_templates: ReplyTemplates;
 }
 
+export type LogEntryType =
+  | 'message'
+  | 'reward_executed'
+  | 'reward_failed'
+  | 'emote_added'
+  | 'emote_removed'
+  | 'settings_changed'
+  | 'editor_changed';
+
 export interface LogEntry {
+  id: number;
   date: string;
+  type: LogEntryType;
   content: string;
+  actor: string | null;
+  target: string | null;
+  reward_id: string | null;
+  payload: Record<string, unknown> | null;
+}
+
+export interface LogFilter {
+  types?: LogEntryType[];
+  since?: string;
+  until?: string;
+}
+
+export interface LogsPage {
+  entries: LogEntry[];
+  next: number | null;
 }
 
 export interface SpotifySettings {
   only_while_live: boolean;
   allow_commands: boolean;
+  /** secret of the now-playing overlay, `null` if it's disabled */
+  overlay_token?: string | null;
 }
 
+export interface NowPlaying {
+  title: string;
+  artists: string[];
+  album_art: string | null;
+  url: string;
+  requested_by: string | null;
+}
+
+export type OverlayState = ({ state: 'playing' } & NowPlaying) | { state: 'stopped' };
+
 export interface Connections {
   spotify?: SpotifySettings | undefined;
 }
@@ -12,6 +12,7 @@ const EditReward = () => import('./routes/EditReward.vue');
 const NewReward = () => import('./routes/NewReward.vue');
 const TestRewardEditor = () => import('./routes/TestRewardEditor.vue');
 const SwapEmoteDashboard = () => import('./routes/SwapEmoteDashboard.vue');
+const SpotifyOverlay = () => import('./routes/SpotifyOverlay.vue');
 
 const router = createRouter({
   routes: [
@@ -76,6 +77,16 @@ const router = createRouter({
         ignoreNav: true,
       },
     },
+    {
+      name: 'Now Playing',
+      path: '/overlay/spotify/:token',
+      component: SpotifyOverlay,
+      meta: {
+        open: true,
+        ignoreNav: true,
+        overlay: true,
+      },
+    },
     {
       path: '/:pathMatch(.*)*',
       component: NotFound,
@@ -30,3 +30,11 @@ export function isValidRewardDurationExpression(expr: string): boolean {
   }
   return true;
 }
+
+/** Splits a comma-separated list and removes empty entries. */
+export function parseList(value: string): string[] {
+  return value
+    .split(',')
+    .map(entry => entry.trim())
+    .filter(entry => entry.length);
+}